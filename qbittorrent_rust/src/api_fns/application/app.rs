@@ -1,5 +1,28 @@
+use serde::{Deserialize, Serialize};
 use serde_json::{self, Value};
-use crate::{core::api::QbitApi, error_handling::error_type::ErrorType, post_request, post_request_no_return, Error};
+use crate::{core::api::QbitApi, error_handling::errors::parse_json, post_request, post_request_no_return, Error};
+
+/// ## Info
+/// represents the versions of the libraries qbittorrent was built with, as returned by `/app/buildInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// QT version.
+    pub qt: String,
+    /// libtorrent version.
+    pub libtorrent: String,
+    /// Boost version.
+    pub boost: String,
+    /// OpenSSL version.
+    pub openssl: String,
+    /// zlib version (since WebUI API v2.7.0).
+    #[serde(default)]
+    pub zlib: Option<String>,
+    /// application bitness (eg: 64-bit).
+    pub bitness: u8,
+    /// the os qbittorrent runs on (qbittorrent 5.x).
+    #[serde(default)]
+    pub platform: Option<String>,
+}
 
 impl QbitApi {
     post_request!{
@@ -27,8 +50,14 @@ impl QbitApi {
 
     /// ## Usage
     /// Gets the build info as a json [`Value`].
-    pub async fn app_build_info(&mut self) -> Result<Value, Error> {
-        serde_json::from_str(Self::app_build_info_raw(self).await?.as_str()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn app_build_info(&self) -> Result<Value, Error> {
+        parse_json(&Self::app_build_info_raw(self).await?, "app_build_info", "/app/buildInfo")
+    }
+
+    /// ## Usage
+    /// Gets the build info as a [`BuildInfo`].
+    pub async fn app_build_info_typed(&self) -> Result<BuildInfo, Error> {
+        parse_json(&Self::app_build_info_raw(self).await?, "app_build_info_typed", "/app/buildInfo")
     }
 
     post_request_no_return!{
@@ -1,7 +1,7 @@
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
-use crate::error_handling::error_type::ErrorType;
+use crate::error_handling::{error_type::ErrorType, errors::parse_json};
 use crate::extended_matches;
 use crate::{core::api::QbitApi, Error};
 
@@ -12,7 +12,8 @@ use std::collections::HashMap;
 /// represents the scan_dirs field in [`QBittorrentConfig`].
 /// ## Example
 /// ```
-/// let multiple_scan_dirs = MultipleScanDirs::new(vec![("folder_path", ScanDirValue::DownloadToCustomPath("./custom/path/here".to_string())), ("folder_path_2", ScanDirValue::DownloadToDefaultPath)]);
+/// # use qbittorrent_rust::{ScanDirs, ScanDirsValue};
+/// let scan_dirs = ScanDirs::new(vec![("folder_path", ScanDirsValue::DownloadToCustomPath("./custom/path/here".to_string())), ("folder_path_2", ScanDirsValue::DownloadToDefaultPath)]);
 /// ```
 #[derive(Debug, Clone)]
 pub struct ScanDirs {
@@ -55,6 +56,19 @@ impl Serialize for ScanDirs {
     }
 }
 
+impl<'de> Deserialize<'de> for ScanDirs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        let hashmap = HashMap::<String, ScanDirsValue>::deserialize(deserializer)?;
+
+        Ok(Self {
+            vec: hashmap.into_iter().map(|(key, value)| ScanDir::new(key, value)).collect()
+        })
+    }
+}
+
 
 /// ## Info
 /// used with [`ScanDirs`]
@@ -76,10 +90,96 @@ pub enum ScanDirsValue {
     }
 }
 
+impl<'de> Deserialize<'de> for ScanDirsValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u8),
+            Path(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(0) => Ok(ScanDirsValue::DownloadToMonitoredFolder),
+            Raw::Number(1) => Ok(ScanDirsValue::DownloadToDefaultPath),
+            Raw::Number(n) => Err(serde::de::Error::custom(format!("`{}` isn't a valid scan_dirs value", n))),
+            Raw::Path(path) => Ok(ScanDirsValue::DownloadToCustomPath(path)),
+        }
+    }
+}
+
+/// qbittorrent sends and expects `scan_dirs` as a single object, so the [`ScanDirs`] are merged into one.
+fn serialize_scan_dirs<S>(scan_dirs: &Option<Vec<ScanDirs>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer
+{
+    let hashmap = scan_dirs
+        .iter()
+        .flatten()
+        .flat_map(|scan_dirs| scan_dirs.vec.iter())
+        .map(|scan_dir| (scan_dir.key.clone(), scan_dir.value.clone()))
+        .collect::<HashMap<String, ScanDirsValue>>();
+
+    hashmap.serialize(serializer)
+}
+
+fn deserialize_scan_dirs<'de, D>(deserializer: D) -> Result<Option<Vec<ScanDirs>>, D::Error>
+where
+    D: Deserializer<'de>
+{
+    Ok(Option::<ScanDirs>::deserialize(deserializer)?.map(|scan_dirs| vec![scan_dirs]))
+}
+
+/// the proxy types qbittorrent 4.6 and later report by name (eg: `"SOCKS5"`), with the number older versions use for them.
+/// the numbers of the proxies with authentication (`3` and `4`) have no name: it's the `proxy_auth_enabled` preference instead.
+const PROXY_TYPES: [(&str, i32); 4] = [("None", -1), ("HTTP", 1), ("SOCKS5", 2), ("SOCKS4", 5)];
+
+/// the name of the proxy type `code`, as qbittorrent 4.6 and later expect it.
+fn proxy_type_name(code: i32) -> Option<&'static str> {
+    match code {
+        3 => Some("HTTP"),
+        4 => Some("SOCKS5"),
+        _ => PROXY_TYPES.iter().find(|(_, number)| *number == code).map(|(name, _)| *name),
+    }
+}
+
+/// reads the proxy type as a number, whether it's sent as one or by name (by qbittorrent 4.6 and later).
+fn deserialize_proxy_type<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
+where
+    D: Deserializer<'de>
+{
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::Number(number)) => number.as_i64().and_then(|n| i32::try_from(n).ok()),
+        Some(Value::String(name)) => PROXY_TYPES.iter().find(|(known, _)| known.eq_ignore_ascii_case(&name)).map(|(_, number)| *number),
+        _ => None,
+    })
+}
+
+/// the fields that became `i32` to hold qbittorrent's `-1` (unlimited) are still set from a `u32` by the builder.
+fn saturating_i32(value: u32) -> i32 {
+    i32::try_from(value).unwrap_or(i32::MAX)
+}
+
 /// ## Info
 /// Represents the qBittorrent application configuration.
 /// explanation of each field: <https://github.com/qbittorrent/qBittorrent/wiki/WebUI-API-(qBittorrent-4.1)#get-application-preferences>
-#[derive(Debug, Default, serde::Serialize)]
+///
+/// it's both written with [`QbitApi::app_set_preferences()`] and read with [`QbitApi::app_get_preferences_typed()`], so settings can be changed
+/// without going through json: the fields that aren't set aren't sent, and the preferences this struct doesn't know about are ignored when reading.
+/// ```no_run
+/// # use qbittorrent_rust::core::api::QbitApi;
+/// # async fn example(api: QbitApi) -> Result<(), qbittorrent_rust::Error> {
+/// let mut config = api.app_get_preferences_typed().await?;
+/// config.max_active_downloads = config.max_active_downloads.map(|max| max + 1);
+///
+/// api.app_set_preferences(config).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QBittorrentConfig {
     // General settings
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -122,7 +222,7 @@ pub struct QBittorrentConfig {
     pub temp_path: Option<String>,
 
     // Scan directories
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_scan_dirs", deserialize_with = "deserialize_scan_dirs", default)]
     pub scan_dirs: Option<Vec<ScanDirs>>,
 
     // Export directories
@@ -169,13 +269,13 @@ pub struct QBittorrentConfig {
     pub queueing_enabled: Option<bool>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_active_downloads: Option<u32>,
+    pub max_active_downloads: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_active_torrents: Option<u32>,
+    pub max_active_torrents: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_active_uploads: Option<u32>,
+    pub max_active_uploads: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dont_count_slow_torrents: Option<bool>,
@@ -210,22 +310,22 @@ pub struct QBittorrentConfig {
     pub random_port: Option<bool>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub dl_limit: Option<u32>,
+    pub dl_limit: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub up_limit: Option<u32>,
+    pub up_limit: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_connec: Option<u32>,
+    pub max_connec: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_connec_per_torrent: Option<u32>,
+    pub max_connec_per_torrent: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_uploads: Option<u32>,
+    pub max_uploads: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_uploads_per_torrent: Option<u32>,
+    pub max_uploads_per_torrent: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_tracker_timeout: Option<u32>,
@@ -284,7 +384,7 @@ pub struct QBittorrentConfig {
     pub encryption: Option<u32>,
 
     // Proxy settings
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_proxy_type", default)]
     pub proxy_type: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -442,7 +542,7 @@ pub struct QBittorrentConfig {
     pub max_seeding_time_enabled: Option<bool>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_seeding_time: Option<u32>,
+    pub max_seeding_time: Option<i32>,
 
     // Announce settings
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -458,7 +558,7 @@ pub struct QBittorrentConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub async_io_threads: Option<u32>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "banned_IPs")]
     pub banned_ips: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -471,7 +571,7 @@ pub struct QBittorrentConfig {
     pub current_network_interface: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub disk_cache: Option<u32>,
+    pub disk_cache: Option<i32>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub disk_cache_ttl: Option<u32>,
@@ -692,17 +792,17 @@ impl QBittorrentConfigBuilder {
     }
 
     pub fn max_active_downloads(mut self, value: u32) -> Self {
-        self.config.max_active_downloads = Some(value);
+        self.config.max_active_downloads = Some(saturating_i32(value));
         self
     }
 
     pub fn max_active_torrents(mut self, value: u32) -> Self {
-        self.config.max_active_torrents = Some(value);
+        self.config.max_active_torrents = Some(saturating_i32(value));
         self
     }
 
     pub fn max_active_uploads(mut self, value: u32) -> Self {
-        self.config.max_active_uploads = Some(value);
+        self.config.max_active_uploads = Some(saturating_i32(value));
         self
     }
 
@@ -760,32 +860,32 @@ impl QBittorrentConfigBuilder {
     }
 
     pub fn dl_limit(mut self, value: u32) -> Self {
-        self.config.dl_limit = Some(value);
+        self.config.dl_limit = Some(saturating_i32(value));
         self
     }
 
     pub fn up_limit(mut self, value: u32) -> Self {
-        self.config.up_limit = Some(value);
+        self.config.up_limit = Some(saturating_i32(value));
         self
     }
 
     pub fn max_connec(mut self, value: u32) -> Self {
-        self.config.max_connec = Some(value);
+        self.config.max_connec = Some(saturating_i32(value));
         self
     }
 
     pub fn max_connec_per_torrent(mut self, value: u32) -> Self {
-        self.config.max_connec_per_torrent = Some(value);
+        self.config.max_connec_per_torrent = Some(saturating_i32(value));
         self
     }
 
     pub fn max_uploads(mut self, value: u32) -> Self {
-        self.config.max_uploads = Some(value);
+        self.config.max_uploads = Some(saturating_i32(value));
         self
     }
 
     pub fn max_uploads_per_torrent(mut self, value: u32) -> Self {
-        self.config.max_uploads_per_torrent = Some(value);
+        self.config.max_uploads_per_torrent = Some(saturating_i32(value));
         self
     }
 
@@ -1141,7 +1241,7 @@ impl QBittorrentConfigBuilder {
     }
 
     pub fn max_seeding_time(mut self, value: u32) -> Self {
-        self.config.max_seeding_time = Some(value);
+        self.config.max_seeding_time = Some(saturating_i32(value));
         self
     }
 
@@ -1188,7 +1288,7 @@ impl QBittorrentConfigBuilder {
     }
 
     pub fn disk_cache(mut self, value: u32) -> Self {
-        self.config.disk_cache = Some(value);
+        self.config.disk_cache = Some(saturating_i32(value));
         self
     }
 
@@ -1336,9 +1436,14 @@ impl QbitApi {
     /// ## Usage
     /// Gets the app preferences as a json [`Value`].
     /// For a list of all the information in the app preferences, refer to the [qbittorrent docs](https://github.com/qbittorrent/qBittorrent/wiki/WebUI-API-(qBittorrent-4.1)#get-application-preferences)
-    pub async fn app_get_preferences(&mut self) -> Result<Value, Error> {
-        serde_json::from_str(Self::get_preferences_raw(self).await?.as_str())
-            .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn app_get_preferences(&self) -> Result<Value, Error> {
+        parse_json(&Self::get_preferences_raw(self).await?, "app_get_preferences", "/app/preferences")
+    }
+
+    /// ## Usage
+    /// Gets the app preferences as a [`QBittorrentConfig`], which can be changed and given back to [`QbitApi::app_set_preferences()`].
+    pub async fn app_get_preferences_typed(&self) -> Result<QBittorrentConfig, Error> {
+        parse_json(&Self::get_preferences_raw(self).await?, "app_get_preferences_typed", "/app/preferences")
     }
 
     crate::post_request!(
@@ -1351,15 +1456,29 @@ impl QbitApi {
 
     /// ## Usage
     /// Sets the app preferences according to the [`QBittorrentConfig`].
+    ///
+    /// qbittorrent 4.6 and later take the proxy type by name: when it's set, it's sent the way the server reports it.
     pub async fn app_set_preferences(
-        &mut self,
+        &self,
         config: impl Borrow<QBittorrentConfig>,
     ) -> Result<(), Error> {
+        let config = config.borrow();
+        let mut json = serde_json::to_value(config)
+            .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?;
+
+        if let Some(name) = config.proxy_type.and_then(proxy_type_name) {
+            let current: Value = parse_json(&self.get_preferences_raw().await?, "app_set_preferences", "/app/preferences")?;
+
+            if current["proxy_type"].is_string() {
+                json["proxy_type"] = Value::from(name);
+            }
+        }
+
         let mut hashmap = HashMap::new();
 
-        hashmap.insert("json", config.borrow());
+        hashmap.insert("json", json.to_string());
 
-        self.make_request_with_form("/app/setPreferences", "set_preferences", hashmap)
+        self.make_request_with_form("app_set_preferences", "/app/setPreferences", hashmap)
             .await?;
         Ok(())
     }
@@ -3,7 +3,7 @@ use std::borrow::Borrow;
 use proc_macros_qbittorrent_rust::Builder;
 use serde_json::Value;
 
-use crate::{core::api::QbitApi, error_handling::error_type::ErrorType, url, Error};
+use crate::{core::api::QbitApi, error_handling::errors::parse_json, url, Error};
 
 /// ## Info
 /// Descriptor for which kinds of logs to get with [`QbitApi::log_get_log()`].
@@ -44,6 +44,12 @@ impl GetLogConfig {
     }
 }
 
+impl Default for GetLogConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Builder)]
 /// ## Info
 /// Builder struct for [`GetLogConfig`].
@@ -90,38 +96,29 @@ impl GetLogConfigBuilder {
     /// 
     /// last_known_id: -1 
     pub fn build(self) -> GetLogConfig {
-        let a = match self.info {
-            Some(value) => value,
-            None => true,
-        };
-
-        let b = match self.normal {
-            Some(value) => value,
-            None => true,
-        };
-
-        let c = match self.warning {
-            Some(value) => value,
-            None => true,
-        };
-
-        let d = match self.critical {
-            Some(val) => val,
-            None => true,
-        };
-
-        let e = match self.last_known_id {
-            Some(value) => value,
-            None => -1,
-        };
-
-        return GetLogConfig {
+        let a = self.normal.unwrap_or(true);
+
+        let b = self.info.unwrap_or(true);
+
+        let c = self.warning.unwrap_or(true);
+
+        let d = self.critical.unwrap_or(true);
+
+        let e = self.last_known_id.unwrap_or(-1);
+
+        GetLogConfig {
             normal: a,
             info: b,
             warning: c,
             critical: d,
             last_known_id: e,
-        };
+        }
+    }
+}
+
+impl Default for GetLogConfigBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -129,19 +126,16 @@ impl QbitApi {
     /// ## Usage
     /// Gets the log as a json [`Value`] based on the [`GetLogConfig`].
     pub async fn log_get_log(
-        &mut self,
+        &self,
         config: impl Borrow<GetLogConfig>,
     ) -> Result<Value, crate::Error> {
-        Ok(
-            serde_json::from_str(Self::log_get_log_raw(self, config).await?.as_str())
-                .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?,
-        )
+        parse_json(&Self::log_get_log_raw(self, config).await?, "log_get_log", "/log/main")
     }
 
     /// ## Usage
     /// Gets the log as a [`String`] based on the [`GetLogConfig`].
     pub async fn log_get_log_raw(
-        &mut self,
+        &self,
         config: impl Borrow<GetLogConfig>,
     ) -> Result<String, crate::Error> {
         let config: &GetLogConfig = config.borrow();
@@ -154,35 +148,27 @@ impl QbitApi {
             ("critical", Some(config.critical)),
             ("last_known_id", Some(config.last_known_id))
         );
-        Self::make_request(self, url, "get_log".to_string()).await
+        Self::make_request(self, "log_get_log_raw", url).await
     }
 
     /// ## Usage
     /// Gets the peer log as a json [`Value`] based on the [`GetLogConfig`].
-    pub async fn log_get_peer_log(&mut self, last_known_id: Option<i64>) -> Result<Value, Error> {
-        Ok(serde_json::from_str(
-            Self::log_get_peer_log_raw(self, last_known_id)
-                .await?
-                .as_str(),
-        )
-        .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?)
+    pub async fn log_get_peer_log(&self, last_known_id: Option<i64>) -> Result<Value, Error> {
+        parse_json(&Self::log_get_peer_log_raw(self, last_known_id).await?, "log_get_peer_log", "/log/peers")
     }
 
     /// ## Usage
     /// Gets the peer log as a [`String`] based on the [`GetLogConfig`].
     pub async fn log_get_peer_log_raw(
-        &mut self,
+        &self,
         last_known_id: Option<i64>,
     ) -> Result<String, Error> {
-        let x = match last_known_id {
-            Some(val) => val,
-            None => -1,
-        };
+        let x = last_known_id.unwrap_or(-1);
 
         Self::make_request(
             self,
-            format!("/log/main?last_known_id={}", x),
-            "get_peer_log".to_string(),
+            "log_get_peer_log_raw",
+            format!("/log/peers?last_known_id={}", x),
         )
         .await
     }
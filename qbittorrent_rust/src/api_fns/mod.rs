@@ -1,7 +1,14 @@
+#[cfg(feature = "torrents")]
 pub mod torrents;
+#[cfg(feature = "app")]
 pub mod application;
+#[cfg(feature = "log")]
 pub mod log;
+#[cfg(feature = "sync")]
 pub mod sync;
+#[cfg(feature = "transfer")]
 pub mod transfer_info;
+#[cfg(feature = "rss")]
 pub mod rss;
-pub mod search;
\ No newline at end of file
+#[cfg(feature = "search")]
+pub mod search;
@@ -4,11 +4,12 @@ use proc_macros_qbittorrent_rust::{experimental, Builder};
 use serde::Serialize;
 use serde_json::Value;
 
-use crate::{core::api::QbitApi, error_handling::error_type::ErrorType, hashmap, request_error_focus, Error};
+use crate::{core::api::QbitApi, error_handling::{error_type::ErrorType, errors::parse_json}, hashmap, request_error_focus, Error};
 
 /// ## Info
 /// Describes a rss auto download rule.
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RssAutoDownloadRule {
     enabled: bool,
     must_contain: String,
@@ -80,16 +81,22 @@ pub struct RssAutoDownloadRuleBuilder{
     }
 }
 
+impl Default for RssAutoDownloadRuleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl QbitApi {
     /// ## Usage
     /// Adds a new rss folder.
     #[experimental]
-    pub async fn rss_add_folder(&mut self, path: impl Into<String>) -> Result<(), Error> {
+    pub async fn rss_add_folder(&self, path: impl Into<String>) -> Result<(), Error> {
         let path: String = path.into();
 
         let hashmap = hashmap!(("path", path));
 
-        request_error_focus!(self, rss_add_folder, "/rss/addFolder", hashmap, (409, ErrorType::MiscError("failure to add feed".to_string())))?;
+        request_error_focus!(self, rss_add_folder, "/rss/addFolder", hashmap, (409, ErrorType::Conflict("failure to add feed".to_string())))?;
 
         Ok(())
     }
@@ -97,7 +104,7 @@ impl QbitApi {
     /// ## Usage
     /// Adds a new feed.
     #[experimental]
-    pub async fn rss_add_feed(&mut self, url: impl Into<String>, path: Option<impl Into<String>>) -> Result<(), Error> {
+    pub async fn rss_add_feed(&self, url: impl Into<String>, path: Option<impl Into<String>>) -> Result<(), Error> {
         match path {
             Some(path) => {
                 let x: String = path.into();
@@ -105,11 +112,11 @@ impl QbitApi {
                 request_error_focus!(
                     self,
                     rss_add_feed,
-                    "/search/addFeed",
+                    "/rss/addFeed",
                     hashmap,
                     (
                         409,
-                        ErrorType::MiscError(
+                        ErrorType::Conflict(
                             "Failure to add feed"
                                 .to_string()
                         )
@@ -123,11 +130,11 @@ impl QbitApi {
                 request_error_focus!(
                     self,
                     rss_add_feed,
-                    "/search/addFeed",
+                    "/rss/addFeed",
                     hashmap,
                     (
                         409,
-                        ErrorType::MiscError(
+                        ErrorType::Conflict(
                             "Failure to add feed"
                                 .to_string()
                         )
@@ -141,12 +148,12 @@ impl QbitApi {
     /// ## Usage
     /// Removes a feed or folder.
     #[experimental]
-    pub async fn rss_remove_item(&mut self, path: impl Into<String>) -> Result<(), Error> {
+    pub async fn rss_remove_item(&self, path: impl Into<String>) -> Result<(), Error> {
         let path: String = path.into();
 
         let hashmap = hashmap!(("path", path));
 
-        request_error_focus!(self, rss_add_folder, "/rss/removeItem", hashmap, (409, ErrorType::MiscError("failure to remove item".to_string())))?;
+        request_error_focus!(self, rss_remove_item, "/rss/removeItem", hashmap, (409, ErrorType::Conflict("failure to remove item".to_string())))?;
 
         Ok(())
     }
@@ -154,13 +161,13 @@ impl QbitApi {
     /// ## Usage
     /// Moves/renames folder or feed.
     #[experimental]
-    pub async fn rss_move_item(&mut self, original_path: impl Into<String>, destination_path: impl Into<String>) -> Result<(), Error> {
+    pub async fn rss_move_item(&self, original_path: impl Into<String>, destination_path: impl Into<String>) -> Result<(), Error> {
         let path_orig: String = original_path.into();
         let path_dest: String = destination_path.into();
 
         let hashmap = hashmap!(("itemPath", path_orig), ("destPath", path_dest));
 
-        request_error_focus!(self, rss_add_folder, "/rss/moveItem", hashmap, (409, ErrorType::MiscError("failure to move item".to_string())))?;
+        request_error_focus!(self, rss_move_item, "/rss/moveItem", hashmap, (409, ErrorType::Conflict("failure to move item".to_string())))?;
 
         Ok(())
     }
@@ -168,12 +175,12 @@ impl QbitApi {
     /// ## Usage
     /// Gets all items as a [`String`].
     #[experimental]
-    pub async fn rss_get_all_items_raw(&mut self, with_data: Option<bool>) -> Result<String, Error> {
+    pub async fn rss_get_all_items_raw(&self, with_data: Option<bool>) -> Result<String, Error> {
         if let Some(x) = with_data {
-            let y = self.make_request_with_form("/rss/items", "rss_get_all_items", hashmap!(("withData", x))).await?;
-            return Ok(y);
+            let y = self.make_request_with_form("rss_get_all_items_raw", "/rss/items", hashmap!(("withData", x))).await?;
+            Ok(y)
         } else {
-            let y = self.make_request("/rss/items", "rss_get_all_items_raw").await?;
+            let y = self.make_request("rss_get_all_items_raw", "/rss/items").await?;
             Ok(y)
         }
     }
@@ -181,103 +188,103 @@ impl QbitApi {
     /// ## Usage
     /// Gets all items as a json [`Value`]
     #[experimental]
-    pub async fn rss_get_all_items(&mut self, with_data: Option<bool>) -> Result<Value, Error> {
-        serde_json::from_str(self.rss_get_all_items_raw(with_data).await?.as_str()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn rss_get_all_items(&self, with_data: Option<bool>) -> Result<Value, Error> {
+        parse_json(&self.rss_get_all_items_raw(with_data).await?, "rss_get_all_items", "/rss/items")
     }
 
     /// ## Usage
     /// If article_id is provided only the article is marked as read otherwise the whole feed is going to be marked as read.
     #[experimental]
-    pub async fn rss_mark_as_read(&mut self, item_path: impl Into<String>, article_id: Option<impl Into<String>>) -> Result<(), Error> {
+    pub async fn rss_mark_as_read(&self, item_path: impl Into<String>, article_id: Option<impl Into<String>>) -> Result<(), Error> {
         let path = item_path.into() as String;
         let mut hashmap = hashmap!(("itemPath", path));
 
         if let Some(x) = article_id {
             let id = x.into() as String;
             hashmap.insert("articleId", id);
-            self.make_request_with_form("/rss/markAsRead", "rss_mark_as_read", hashmap).await?;
-            return Ok(());
+            self.make_request_with_form("rss_mark_as_read", "/rss/markAsRead", hashmap).await?;
+            Ok(())
         } else {
-            self.make_request_with_form("/rss/markAsRead", "rss_mark_as_read", hashmap).await?;
-            return Ok(());
+            self.make_request_with_form("rss_mark_as_read", "/rss/markAsRead", hashmap).await?;
+            Ok(())
         }
     }
 
     /// ## Usage 
     /// Refreshes folder or feed.
     #[experimental]
-    pub async fn rss_refresh_item(&mut self, item_path: impl Into<String>) -> Result<(), Error> {
+    pub async fn rss_refresh_item(&self, item_path: impl Into<String>) -> Result<(), Error> {
         let path = item_path.into() as String;
         let hashmap = hashmap!(("itemPath", path));
-        self.make_request_with_form("/rss/refreshItem", "rss_refresh_item", hashmap).await?;
+        self.make_request_with_form("rss_refresh_item", "/rss/refreshItem", hashmap).await?;
         Ok(())
     }
 
     /// ## Usage
     /// Sets a new auto-downloading rule based on a [`RssAutoDownloadRule`].
     #[experimental]
-    pub async fn rss_set_auto_downloading_rule(&mut self, rule_name: impl Into<String>, rule: impl Borrow<RssAutoDownloadRule>) -> Result<(), Error> {
+    pub async fn rss_set_auto_downloading_rule(&self, rule_name: impl Into<String>, rule: impl Borrow<RssAutoDownloadRule>) -> Result<(), Error> {
         let name = rule_name.into() as String;
         let rule: RssAutoDownloadRule = rule.borrow().clone();
         let rule = serde_json::to_string(&rule).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?;
         let hashmap = hashmap!(("ruleName", name), ("ruleDef", rule));
-        self.make_request_with_form("/rss/setRule", "rss_set_auto_downloading_rule", hashmap).await?;
+        self.make_request_with_form("rss_set_auto_downloading_rule", "/rss/setRule", hashmap).await?;
         Ok(())
     }
 
     /// ## Usage
     /// Renames an auto-downloading rule.
     #[experimental]
-    pub async fn rss_rename_auto_downloading_rule(&mut self, original_name: impl Into<String>, new_name: impl Into<String>) -> Result<(), Error> {
+    pub async fn rss_rename_auto_downloading_rule(&self, original_name: impl Into<String>, new_name: impl Into<String>) -> Result<(), Error> {
         let name_orig = original_name.into() as String;
         let name_new = new_name.into() as String;
         
         let hashmap = hashmap!(("ruleName", name_orig), ("newRuleName", name_new));
-        self.make_request_with_form("/rss/renameRule", "rss_rename_auto_downloading_rule", hashmap).await?;
+        self.make_request_with_form("rss_rename_auto_downloading_rule", "/rss/renameRule", hashmap).await?;
         Ok(())
     }
 
     /// ## Usage
     /// Removes an auto-downloading rule.
     #[experimental]
-    pub async fn rss_remove_auto_downloading_rule(&mut self, rule_name: impl Into<String>) -> Result<(), Error> {
+    pub async fn rss_remove_auto_downloading_rule(&self, rule_name: impl Into<String>) -> Result<(), Error> {
         let name = rule_name.into() as String;
         
         let hashmap = hashmap!(("ruleName", name));
-        self.make_request_with_form("/rss/removeRule", "rss_remove_auto_downloading_rule", hashmap).await?;
+        self.make_request_with_form("rss_remove_auto_downloading_rule", "/rss/removeRule", hashmap).await?;
         Ok(())
     }
 
     /// ## Usage
     /// Gets all auto-downloading rules as a [`String`].
     #[experimental]
-    pub async fn rss_get_all_auto_downloading_rules_raw(&mut self) -> Result<String, Error> {
-        let x = self.make_request("/rss/rules", "rss_get_all_auto_downloading_rules_raw").await?;
+    pub async fn rss_get_all_auto_downloading_rules_raw(&self) -> Result<String, Error> {
+        let x = self.make_request("rss_get_all_auto_downloading_rules_raw", "/rss/rules").await?;
         Ok(x)
     }
 
     /// ## Usage
     /// Gets all auto-downloading rules as a json [`Value`].
     #[experimental]
-    pub async fn rss_get_all_auto_downloading_rules(&mut self) -> Result<Value, Error> {
-        serde_json::from_str(self.rss_get_all_auto_downloading_rules_raw().await?.as_str()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn rss_get_all_auto_downloading_rules(&self) -> Result<Value, Error> {
+        parse_json(&self.rss_get_all_auto_downloading_rules_raw().await?, "rss_get_all_auto_downloading_rules", "/rss/rules")
     }
 
     /// ## Usage
     /// Gets all articles matching a rule as a [`String`].
     #[experimental]
-    pub async fn rss_get_all_articles_matching_a_rule_raw(&mut self, rule_name: impl Into<String>) -> Result<String, Error> {
+    pub async fn rss_get_all_articles_matching_a_rule_raw(&self, rule_name: impl Into<String>) -> Result<String, Error> {
         let name = rule_name.into() as String;
         
         let hashmap = hashmap!(("ruleName", name));
-        let x = self.make_request_with_form("/rss/matchingArticles", "rss_get_all_articles_matching_a_rule_raw", hashmap).await?;
+        let x = self.make_request_with_form("rss_get_all_articles_matching_a_rule_raw", "/rss/matchingArticles", hashmap).await?;
         Ok(x)
     }
 
     /// ## Usage
     /// Gets all articles matching a rule as a json [`Value`].
     #[experimental]
-    pub async fn rss_get_all_articles_matching_a_rule(&mut self, rule_name: impl Into<String>) -> Result<Value, Error> {
-        serde_json::from_str(self.rss_get_all_articles_matching_a_rule_raw(rule_name).await?.as_str()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn rss_get_all_articles_matching_a_rule(&self, rule_name: impl Into<String>) -> Result<Value, Error> {
+        parse_json(&self.rss_get_all_articles_matching_a_rule_raw(rule_name).await?, "rss_get_all_articles_matching_a_rule", "/rss/matchingArticles")
     }
 }
\ No newline at end of file
@@ -1,11 +1,10 @@
 use std::{borrow::Borrow, collections::HashMap};
 
 use proc_macros_qbittorrent_rust::requires_id;
-use serde::Serialize;
 use serde_json::Value;
 
 use crate::{
-    core::api::QbitApi, error_handling::error_type::ErrorType, hashmap, misc::sep_vec::SepVec,
+    core::api::QbitApi, error_handling::{error_type::ErrorType, errors::parse_json}, hashmap, misc::sep_vec::SepVec,
     request_error_focus, Error,
 };
 
@@ -36,7 +35,7 @@ impl SearchPlugin {
         Self {
             name: search_plugin_name.into(),
             categories: Borrow::<Vec<(X, X)>>::borrow(&categories)
-                .into_iter()
+                .iter()
                 .map(|(g, h)| {
                     (
                         Into::<String>::into(g.clone()),
@@ -58,7 +57,7 @@ impl SearchPlugin {
     ) -> Vec<Self> {
         let x: &Vec<S> = search_plugins_vec.borrow();
 
-        x.into_iter()
+        x.iter()
             .zip(Borrow::<Vec<Vec<(X, X)>>>::borrow(&categories).iter())
             .map(|k| Self::new::<S, X>(k.0.clone(), k.1.clone()))
             .collect()
@@ -94,16 +93,12 @@ pub enum SearchPluginsDescriptor {
     Custom(Vec<SearchPlugin>),
 }
 impl SearchPluginsDescriptor {
+    /// returns the plugins of the `Custom` variant, or `None` for `All` and `Enabled`.
     #[allow(dead_code)]
-    pub(crate) fn get_inner(&self) -> Vec<SearchPlugin> {
+    pub(crate) fn get_inner(&self) -> Option<Vec<SearchPlugin>> {
         match self {
-            SearchPluginsDescriptor::All => {
-                panic!("tried to get the inner value of SearchPulginsDescriptor::All")
-            }
-            SearchPluginsDescriptor::Enabled => {
-                panic!("tried to get the inner value of SearchPulginsDescriptor::Enabled")
-            }
-            SearchPluginsDescriptor::Custom(vec) => vec.clone(),
+            SearchPluginsDescriptor::All | SearchPluginsDescriptor::Enabled => None,
+            SearchPluginsDescriptor::Custom(vec) => Some(vec.clone()),
         }
     }
 }
@@ -133,65 +128,60 @@ pub enum Categories {
 impl QbitApi {
     /// ## Usage
     /// Gets all available search plugins as a [`String`].
-    pub async fn search_get_search_plugins_raw(&mut self) -> Result<String, crate::Error> {
-        self.make_request("/search/plugins", "search_get_search_plugins_raw")
+    pub async fn search_get_search_plugins_raw(&self) -> Result<String, crate::Error> {
+        self.make_request("search_get_search_plugins_raw", "/search/plugins")
             .await
     }
 
     /// ## Usage
     /// Gets all available search plugins as a json [`Value`].
-    pub async fn search_get_search_plugins_json(&mut self) -> Result<Value, crate::Error> {
-        serde_json::from_str(self.search_get_search_plugins_raw().await?.as_str())
-            .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn search_get_search_plugins_json(&self) -> Result<Value, crate::Error> {
+        parse_json(&self.search_get_search_plugins_raw().await?, "search_get_search_plugins_json", "/search/plugins")
     }
 
     /// ## Usage
-    /// Gets the available search plugins as a [`Vec`] of [`SearchPlugin`]s.
+    /// Gets the available search plugins as a [`Vec`] of [`SearchPlugin`]s. plugins without a name are left out.
+    ///
+    /// ## Errors
+    /// - returns an [`Error`] with error type [`ErrorType::UnexpectedResponse`] if qbittorrent doesn't answer with a list of plugins.
     pub async fn search_get_search_plugins(
-        &mut self,
+        &self,
     ) -> Result<Vec<SearchPlugin>, Error> {
         let value = self.search_get_search_plugins_json().await?;
-        let names = value
-            .as_array()
-            .iter()
-            .map(|x| {
-                x.into_iter()
-                    .filter_map(|item| {
-                        item.get("name")
-                            .and_then(|s| s.as_str().map(|l| l.to_string()))
-                    })
-                    .collect::<Vec<String>>()
-            })
-            .collect::<Vec<Vec<String>>>()
+        let plugins = value.as_array().ok_or_else(|| {
+            Error::build(ErrorType::UnexpectedResponse("expected a list of search plugins.".to_string()), None)
+                .with_method("search_get_search_plugins")
+                .with_endpoint("/search/plugins")
+        })?;
+
+        // the name and the categories are read together, so that a plugin without a name is skipped as a whole.
+        let plugins = plugins
             .iter()
-            .flatten()
-            .map(|l| l.to_owned())
-            .collect::<Vec<String>>();
-
-        let categories = value
-            .as_array()
-            .unwrap() // Get the outer array
-            .iter() 
-            .map(|site| {
-                site.get("supportedCategories")
+            .filter_map(|site| {
+                let name = site.get("name")?.as_str()?.to_string();
+                let categories = site
+                    .get("supportedCategories")
                     .and_then(|categories| categories.as_array()) // Get the `supportedCategories` array
-                    .unwrap_or(&vec![]) 
-                    .iter() 
+                    .map(|categories| categories.as_slice())
+                    .unwrap_or_default()
+                    .iter()
                     .filter_map(|category| {
                         let name = category.get("name")?.as_str()?.to_string();
                         let id = category.get("id")?.as_str()?.to_string();
-                        Some((name, id))
+                        Some((id, name))
                     })
-                    .collect::<Vec<(String, String)>>()
+                    .collect::<Vec<(String, String)>>();
+
+                Some(SearchPlugin { name, categories })
             })
-            .collect::<Vec<Vec<(String, String)>>>();
+            .collect::<Vec<SearchPlugin>>();
 
-        Ok(SearchPlugin::from_vec(names, categories))
+        Ok(plugins)
     }
 
     /// ## Usage
     /// gets the names of the plugins in a [`Vec`].
-    pub async fn search_get_search_plugins_names(&mut self) -> Result<Vec<String>, Error> {
+    pub async fn search_get_search_plugins_names(&self) -> Result<Vec<String>, Error> {
         Ok(self.search_get_search_plugins().await?.into_iter().map(|el|el.get_name()).collect::<Vec<String>>())
     }
 
@@ -200,8 +190,11 @@ impl QbitApi {
     /// 
     /// ## Returns
     /// If everything goes well, it returns the search id.
+    ///
+    /// ## Errors
+    /// - returns an [`Error`] with error type [`ErrorType::UnexpectedResponse`] if qbittorrent's answer doesn't contain the search id.
     pub async fn search_start(
-        &mut self,
+        &self,
         pattern: impl Into<String>,
         plugins: impl Borrow<SearchPluginsSpec>,
         categories: impl Borrow<Categories>,
@@ -220,7 +213,7 @@ impl QbitApi {
 
         match categories {
             Categories::All => hashmap.insert("category", "all".to_string()),
-            Categories::Custom(vec) => hashmap.insert("plugins", SepVec::new(vec, "|").to_string()),
+            Categories::Custom(vec) => hashmap.insert("category", SepVec::new(vec, "|").to_string()),
         };
 
         let string = request_error_focus!(
@@ -230,22 +223,26 @@ impl QbitApi {
             hashmap,
             (
                 409,
-                ErrorType::MiscError(
+                ErrorType::Conflict(
                     "user has reached the limit of max 'Running' searches (currently set to 5)"
                         .to_string()
                 )
             )
         )?;
-        let val: Value = serde_json::from_str(string.as_str())
-            .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?;
-        Ok(val.get("id").unwrap().as_u64().unwrap())
+        let val: Value = parse_json(&string, "search_start", "/search/start")?;
+        val.get("id").and_then(Value::as_u64).ok_or_else(|| {
+            Error::build(ErrorType::UnexpectedResponse("the search id is missing.".to_string()), None)
+                .with_method("search_start")
+                .with_endpoint("/search/start")
+                .with_body(string)
+        })
     }
 
 
     /// ## Usage
     /// stops a search.
     #[requires_id]
-    pub async fn search_stop(&mut self, id: u64) -> Result<(), Error> {
+    pub async fn search_stop(&self, id: u64) -> Result<(), Error> {
         let hashmap = hashmap!(("id", id));
         request_error_focus!(
             self,
@@ -254,7 +251,7 @@ impl QbitApi {
             hashmap,
             (
                 404,
-                ErrorType::MiscError("search job was not found".to_string())
+                ErrorType::NotFound("search job was not found".to_string())
             )
         )?;
         Ok(())
@@ -263,7 +260,7 @@ impl QbitApi {
     /// ## Usage
     /// Gets the status of a search job as a [`String`].
     #[requires_id]
-    pub async fn search_status_raw(&mut self, id: Option<u64>) -> Result<String, Error> {
+    pub async fn search_status_raw(&self, id: Option<u64>) -> Result<String, Error> {
         match id {
             Some(n) => {
                 let hashmap = hashmap!(("id", n));
@@ -274,20 +271,17 @@ impl QbitApi {
                     hashmap,
                     (
                         404,
-                        ErrorType::MiscError("search job was not found".to_string())
+                        ErrorType::NotFound("search job was not found".to_string())
                     )
                 )
             }
             None => self
-                .make_request("/search/status", "search_status_raw")
+                .make_request("search_status_raw", "/search/status")
                 .await
                 .map_err(|e| {
                     if let Some(num) = e.code {
                         match num {
-                            404 => Error::build(
-                                ErrorType::MiscError("search job was not found".to_string()),
-                                Some(num),
-                            ),
+                            404 => e.retype(ErrorType::NotFound("search job was not found".to_string())),
 
                             _ => e,
                         }
@@ -301,9 +295,8 @@ impl QbitApi {
     /// ## Usage
     /// Gets the status of a search job as a json [`Value`].
     #[requires_id]
-    pub async fn search_status(&mut self, id: Option<u64>) -> Result<Value, Error> {
-        serde_json::from_str(self.search_status_raw(id).await?.as_str())
-            .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn search_status(&self, id: Option<u64>) -> Result<Value, Error> {
+        parse_json(&self.search_status_raw(id).await?, "search_status", "/search/status")
     }
 
     /// ## Usage
@@ -314,7 +307,7 @@ impl QbitApi {
     /// offset: result to start at. A negative number means count backwards (e.g. -2 returns the 2 most recent results)
     #[requires_id]
     pub async fn search_results_raw(
-        &mut self,
+        &self,
         id: u64,
         limit: Option<i64>,
         offset: Option<i64>,
@@ -329,7 +322,7 @@ impl QbitApi {
             hashmap.insert("offset", ofst);
         }
 
-        let res = request_error_focus!(self, search_results_raw, "/search/results", hashmap, (404, ErrorType::MiscError("search job was not found".to_string())), (409, ErrorType::MiscError("Offset is too large, or too small (e.g. absolute value of negative number is greater than # results)".to_string())))?;
+        let res = request_error_focus!(self, search_results_raw, "/search/results", hashmap, (404, ErrorType::NotFound("search job was not found".to_string())), (409, ErrorType::Conflict("Offset is too large, or too small (e.g. absolute value of negative number is greater than # results)".to_string())))?;
 
         Ok(res)
     }
@@ -343,28 +336,27 @@ impl QbitApi {
     /// offset: result to start at. A negative number means count backwards (e.g. -2 returns the 2 most recent results)
     #[requires_id]
     pub async fn search_results(
-        &mut self,
+        &self,
         id: u64,
         limit: Option<i64>,
         offset: Option<i64>,
     ) -> Result<Value, Error> {
-        serde_json::from_str(self.search_results_raw(id, limit, offset).await?.as_str())
-            .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+        parse_json(&self.search_results_raw(id, limit, offset).await?, "search_results", "/search/results")
     }
 
     /// ## Usage
     /// deletes a search
     #[requires_id]
-    pub async fn search_delete(&mut self, id: u64) -> Result<(), Error> {
+    pub async fn search_delete(&self, id: u64) -> Result<(), Error> {
         let hashmap = hashmap!(("id", id));
         request_error_focus!(
             self,
-            search_stop,
+            search_delete,
             "/search/delete",
             hashmap,
             (
                 404,
-                ErrorType::MiscError("search job was not found".to_string())
+                ErrorType::NotFound("search job was not found".to_string())
             )
         )?;
         Ok(())
@@ -376,7 +368,7 @@ impl QbitApi {
     /// ## Arguments
     /// sources: the urls to the plugins
     pub async fn search_install_plugins<S: Into<String> + Clone>(
-        &mut self,
+        &self,
         sources: impl Borrow<Vec<S>>,
     ) -> Result<(), Error> {
         let sources: Vec<S> = sources.borrow().clone();
@@ -390,7 +382,7 @@ impl QbitApi {
 
         let hashmap = hashmap!(("sources", string));
 
-        self.make_request_with_form("/search/installPlugin", "search_install_plugins", hashmap)
+        self.make_request_with_form("search_install_plugins", "/search/installPlugin", hashmap)
             .await?;
         Ok(())
     }
@@ -401,7 +393,7 @@ impl QbitApi {
     /// ## Arguments
     /// sources: the urls to the plugins
     pub async fn search_uninstall_plugins<S: Into<String> + Clone>(
-        &mut self,
+        &self,
         names: impl Borrow<Vec<S>>,
     ) -> Result<(), Error> {
         let sources: Vec<S> = names.borrow().clone();
@@ -416,8 +408,8 @@ impl QbitApi {
         let hashmap = hashmap!(("names", string));
 
         self.make_request_with_form(
-            "/search/uninstallPlugin",
             "search_uninstall_plugins",
+            "/search/uninstallPlugin",
             hashmap,
         )
         .await?;
@@ -430,16 +422,10 @@ impl QbitApi {
     /// ## Arguments
     /// - enable: whether to enable (true) or disable (false) the plugins.
     pub async fn search_enable_plugins<S: Into<String> + Clone>(
-        &mut self,
+        &self,
         names: impl Borrow<Vec<S>>,
         enable: bool,
     ) -> Result<(), Error> {
-        #[derive(Serialize)]
-        struct Temp {
-            names: String,
-            enable: bool,
-        }
-
         let sources: Vec<S> = names.borrow().clone();
 
         let srcs = sources
@@ -449,43 +435,17 @@ impl QbitApi {
 
         let string = SepVec::new(srcs, "|").to_string();
 
-        let temp = Temp {
-            names: string,
-            enable,
-        };
+        let hashmap = hashmap!(("names", string), ("enable", enable.to_string()));
 
-        let response = self
-            .reqwest_client
-            .post(format!(
-                "{}/api/v2{}",
-                self.authority, "/search/enablePlugin"
-            ))
-            .header(
-                reqwest::header::COOKIE,
-                format!("SID={}", self.get_cookie().await?),
-            )
-            .form(&temp)
-            .send()
-            .await
-            .map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
-
-        if response.status().is_success() {
-            return Ok(());
-        } else {
-            return Err(Error::build(
-                ErrorType::MiscError(format!(
-                    "function name: {}",
-                    "search_enable_plugins"
-                )),
-                Some(response.status().as_u16()),
-            ));
-        }
+        self.make_request_with_form("search_enable_plugins", "/search/enablePlugin", hashmap)
+            .await?;
+        Ok(())
     }
 
     /// ## Usage
     /// updates the search plugins
-    pub async fn search_update_plugins(&mut self) -> Result<(), Error> {
-        self.make_request("search/updatePlugins", "search_update_plugins").await?;
+    pub async fn search_update_plugins(&self) -> Result<(), Error> {
+        self.make_request("search_update_plugins", "/search/updatePlugins").await?;
         Ok(())
     }
 }
@@ -0,0 +1,308 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use futures_util::stream::{self, Stream};
+
+use crate::{
+    api_fns::torrents::info::{TorrentInfo, TorrentState},
+    core::api::QbitApi,
+    Error,
+};
+
+use super::main_data::{MainDataSync, ServerState, SyncState};
+
+/// ## Info
+/// represents a change noticed between two consecutive [`SyncState`]s.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncEvent {
+    /// a new torrent appeared.
+    TorrentAdded(TorrentInfo),
+    /// a torrent was removed; contains its last known information.
+    TorrentRemoved(TorrentInfo),
+    /// the state of a torrent changed.
+    StateChanged {
+        hash: String,
+        from: TorrentState,
+        to: TorrentState,
+    },
+    /// a torrent finished downloading (its progress reached 100%).
+    Completed(TorrentInfo),
+    /// the category of a torrent changed. an empty string means no category.
+    CategoryChanged {
+        hash: String,
+        from: String,
+        to: String,
+    },
+    /// a tag was added to a torrent.
+    TagAdded {
+        hash: String,
+        tag: String,
+    },
+    /// the connection status, the external addresses, or one of the global settings of the server state changed; contains the new server state.
+    ///
+    /// the rates, the transferred data and the other counters change on (almost) every poll, so they don't emit it on their own.
+    ServerStateChanged(ServerState),
+}
+impl SyncEvent {
+    /// ## Usage
+    /// returns all the [`SyncEvent`]s that describe how `old` became `new`.
+    ///
+    /// events of the same torrent are grouped together, and torrents come in the order of their hashes:
+    /// first the added and changed ones, then the removed ones. the [`SyncEvent::ServerStateChanged`] event comes last.
+    pub fn between(old: &SyncState, new: &SyncState) -> Vec<SyncEvent> {
+        changes(old.torrents(), new.torrents().keys(), old.server_state(), new)
+    }
+}
+
+/// the events that describe how `new` changed, given the `previous` version of the torrents it may have changed or removed,
+/// the hashes of the torrents that were `changed` (or added), and the previous server state.
+fn changes<'a>(previous: &HashMap<String, TorrentInfo>, changed: impl IntoIterator<Item = &'a String>, server_state: &ServerState, new: &SyncState) -> Vec<SyncEvent> {
+    let mut events = vec![];
+
+    let mut changed: Vec<&String> = changed.into_iter().collect();
+    changed.sort_unstable();
+
+    for hash in changed {
+        let Some(torrent) = new.torrent(hash) else { continue };
+        let Some(previous) = previous.get(hash) else {
+            events.push(SyncEvent::TorrentAdded(torrent.clone()));
+            continue;
+        };
+
+        if previous.state != torrent.state {
+            events.push(SyncEvent::StateChanged {
+                hash: hash.clone(),
+                from: previous.state,
+                to: torrent.state,
+            });
+        }
+
+        if previous.progress < 1.0 && torrent.progress >= 1.0 {
+            events.push(SyncEvent::Completed(torrent.clone()));
+        }
+
+        if previous.category != torrent.category {
+            events.push(SyncEvent::CategoryChanged {
+                hash: hash.clone(),
+                from: previous.category.clone(),
+                to: torrent.category.clone(),
+            });
+        }
+
+        for tag in torrent.tags.iter().filter(|tag| !previous.tags.contains(tag)) {
+            events.push(SyncEvent::TagAdded {
+                hash: hash.clone(),
+                tag: tag.clone(),
+            });
+        }
+    }
+
+    let mut removed: Vec<&TorrentInfo> = previous.iter().filter(|(hash, _)| new.torrent(hash).is_none()).map(|(_, torrent)| torrent).collect();
+    removed.sort_unstable_by(|a, b| a.hash.cmp(&b.hash));
+    events.extend(removed.into_iter().map(|torrent| SyncEvent::TorrentRemoved(torrent.clone())));
+
+    if settings(server_state) != settings(new.server_state()) {
+        events.push(SyncEvent::ServerStateChanged(new.server_state().clone()));
+    }
+
+    events
+}
+
+/// the fields of the server state that change only when something worth an event happens.
+fn settings(state: &ServerState) -> impl PartialEq + '_ {
+    (
+        &state.connection_status,
+        &state.last_external_address_v4,
+        &state.last_external_address_v6,
+        state.dl_rate_limit,
+        state.up_rate_limit,
+        state.queueing,
+        state.use_alt_speed_limits,
+        state.use_subcategories,
+        state.refresh_interval,
+    )
+}
+
+impl MainDataSync {
+    /// ## Usage
+    /// like `update`, but returns the [`SyncEvent`]s describing what changed since the previous update, in the order of [`SyncEvent::between()`].
+    pub async fn update_events(&mut self) -> Result<Vec<SyncEvent>, Error> {
+        let replaced = self.apply_next_delta().await?;
+        let new = self.state();
+        Ok(changes(&replaced.torrents, &replaced.changed, replaced.server_state.as_ref().unwrap_or(new.server_state()), new))
+    }
+
+    /// ## Usage
+    /// turns the [`MainDataSync`] into a [`Stream`] of [`SyncEvent`]s, polling `/sync/maindata` every `interval`.
+    ///
+    /// if no update was done yet, the first poll only establishes the starting snapshot, and doesn't emit any event.
+    /// a failed poll is yielded as an [`Error`], and the stream keeps polling afterwards.
+    ///
+    /// the returned stream isn't [`Unpin`]; pin it (eg: with `Box::pin`) before polling it.
+    pub fn into_event_stream(self, interval: Duration) -> impl Stream<Item = Result<SyncEvent, Error>> {
+        let baseline = self.state().rid() == 0;
+
+        stream::unfold((self, VecDeque::new(), baseline, true), move |(mut sync, mut pending, mut baseline, mut first)| async move {
+            loop {
+                if let Some(event) = pending.pop_front() {
+                    return Some((Ok(event), (sync, pending, baseline, first)));
+                }
+
+                if !first {
+                    futures_timer::Delay::new(interval).await;
+                }
+                first = false;
+
+                if baseline {
+                    match sync.update().await {
+                        Ok(_) => baseline = false,
+                        Err(e) => return Some((Err(e), (sync, pending, baseline, first))),
+                    }
+                    continue;
+                }
+
+                match sync.update_events().await {
+                    Ok(events) => pending.extend(events),
+                    Err(e) => return Some((Err(e), (sync, pending, baseline, first))),
+                }
+            }
+        })
+    }
+}
+
+impl QbitApi {
+    /// ## Usage
+    /// Returns a [`Stream`] of [`SyncEvent`]s, polling the main sync data every `interval`.
+    /// The first poll only establishes the starting snapshot. For more details, look at [`MainDataSync::into_event_stream()`].
+    pub fn sync_get_event_stream(&self, interval: Duration) -> impl Stream<Item = Result<SyncEvent, Error>> {
+        MainDataSync::new(self.clone()).into_event_stream(interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::api_fns::sync::main_data::MainDataDelta;
+
+    const DEBIAN: &str = "8c212779b4abde7c6bc608063a0d008b7e40ce32";
+    const ARCH: &str = "c4b2b8a7e1d9f3a6b5c4d3e2f1a0b9c8d7e6f5a4";
+
+    fn state(value: Value) -> SyncState {
+        let mut state = SyncState::new();
+        state.apply(serde_json::from_value::<MainDataDelta>(value).unwrap()).unwrap();
+        state
+    }
+
+    /// the events between `old` and `old` with `changes` applied.
+    fn events(old: &SyncState, changes: Value) -> Vec<SyncEvent> {
+        let mut new = old.clone();
+        new.apply(serde_json::from_value::<MainDataDelta>(changes).unwrap()).unwrap();
+        SyncEvent::between(old, &new)
+    }
+
+    /// the events `update_events` finds when it applies `delta` to `old`, which must be the ones between the two snapshots.
+    fn applied_events(old: &SyncState, delta: Value) -> Vec<SyncEvent> {
+        let mut new = old.clone();
+        let replaced = new.apply_replacing(serde_json::from_value::<MainDataDelta>(delta).unwrap()).unwrap();
+        let events = changes(&replaced.torrents, &replaced.changed, replaced.server_state.as_ref().unwrap_or(new.server_state()), &new);
+
+        assert_eq!(events, SyncEvent::between(old, &new));
+        events
+    }
+
+    fn debian() -> SyncState {
+        state(json!({
+            "full_update": true,
+            "torrents": { DEBIAN: { "name": "debian.iso", "state": "downloading", "progress": 0.5, "category": "linux", "tags": "iso" } },
+            "server_state": { "connection_status": "connected", "dl_rate_limit": 0 },
+        }))
+    }
+
+    #[test]
+    fn nothing_changed() {
+        assert_eq!(events(&debian(), json!({})), []);
+    }
+
+    #[test]
+    fn torrents_added_and_removed() {
+        let events = events(&debian(), json!({ "torrents": { ARCH: { "name": "arch.iso" } }, "torrents_removed": [DEBIAN] }));
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], SyncEvent::TorrentAdded(torrent) if torrent.hash == ARCH && torrent.name == "arch.iso"));
+        assert!(matches!(&events[1], SyncEvent::TorrentRemoved(torrent) if torrent.hash == DEBIAN && torrent.name == "debian.iso"));
+    }
+
+    #[test]
+    fn state_changed_and_completed() {
+        let events = events(&debian(), json!({ "torrents": { DEBIAN: { "state": "uploading", "progress": 1.0 } } }));
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], SyncEvent::StateChanged { hash: DEBIAN.to_string(), from: TorrentState::Downloading, to: TorrentState::Uploading });
+        assert!(matches!(&events[1], SyncEvent::Completed(torrent) if torrent.hash == DEBIAN));
+    }
+
+    #[test]
+    fn category_changed_and_tags_added() {
+        let events = events(&debian(), json!({ "torrents": { DEBIAN: { "category": "", "tags": "iso, new, debian" } } }));
+
+        assert_eq!(
+            events,
+            [
+                SyncEvent::CategoryChanged { hash: DEBIAN.to_string(), from: "linux".to_string(), to: String::new() },
+                SyncEvent::TagAdded { hash: DEBIAN.to_string(), tag: "new".to_string() },
+                SyncEvent::TagAdded { hash: DEBIAN.to_string(), tag: "debian".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn server_state_changed_only_for_meaningful_fields() {
+        let old = debian();
+
+        let noise = json!({ "server_state": { "dl_info_speed": 1024, "dl_info_data": 4096, "dht_nodes": 312, "free_space_on_disk": 1 } });
+        assert_eq!(events(&old, noise), []);
+
+        let events = events(&old, json!({ "server_state": { "dl_rate_limit": 1024, "dl_info_speed": 1024 } }));
+        assert!(matches!(&events[..], [SyncEvent::ServerStateChanged(state)] if state.dl_rate_limit == 1024 && state.dl_info_speed == 1024));
+    }
+
+    #[test]
+    fn torrents_come_in_the_order_of_their_hashes() {
+        let hashes = ["f0", "0a", "9c", "5e", "c1"];
+        let torrents: serde_json::Map<String, Value> = hashes.iter().map(|hash| (hash.to_string(), json!({}))).collect();
+
+        let added = applied_events(&SyncState::new(), json!({ "torrents": torrents }));
+        assert!(added.iter().all(|event| matches!(event, SyncEvent::TorrentAdded(_))));
+
+        let removed = applied_events(&state(json!({ "torrents": torrents })), json!({ "torrents_removed": hashes }));
+        assert!(removed.iter().all(|event| matches!(event, SyncEvent::TorrentRemoved(_))));
+
+        for events in [added, removed] {
+            let order: Vec<&str> = events
+                .iter()
+                .filter_map(|event| match event {
+                    SyncEvent::TorrentAdded(torrent) | SyncEvent::TorrentRemoved(torrent) => Some(torrent.hash.as_str()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(order, ["0a", "5e", "9c", "c1", "f0"]);
+        }
+    }
+
+    #[test]
+    fn applied_deltas_give_the_same_events_as_the_snapshots() {
+        let old = debian();
+
+        applied_events(&old, json!({}));
+        applied_events(&old, json!({ "torrents": { DEBIAN: { "state": "uploading", "progress": 1.0, "tags": "iso, new" } } }));
+        applied_events(&old, json!({ "torrents": { DEBIAN: { "state": "uploading" } }, "torrents_removed": [DEBIAN] }));
+        applied_events(&old, json!({ "server_state": { "connection_status": "firewalled" } }));
+
+        let events = applied_events(&old, json!({ "full_update": true, "torrents": { ARCH: { "name": "arch.iso" } } }));
+        assert!(matches!(&events[..], [SyncEvent::TorrentAdded(_), SyncEvent::TorrentRemoved(_), SyncEvent::ServerStateChanged(_)]));
+    }
+}
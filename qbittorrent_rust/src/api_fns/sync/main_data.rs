@@ -0,0 +1,473 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{api_fns::torrents::info::TorrentInfo, core::api::QbitApi, error_handling::error_type::ErrorType, Error};
+
+/// ## Info
+/// represents a category, as reported by `/sync/maindata`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CategoryInfo {
+    /// name of the category.
+    pub name: String,
+    /// save path of the category, empty if the default one is used.
+    #[serde(rename = "savePath")]
+    pub save_path: String,
+}
+
+/// ## Info
+/// represents the global state of the qbittorrent server, as reported in the `server_state` section of `/sync/maindata`.
+///
+/// every field falls back to its default value if qbittorrent doesn't send it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerState {
+    /// connection status, either `connected`, `firewalled` or `disconnected`.
+    pub connection_status: String,
+    /// number of DHT nodes connected to.
+    pub dht_nodes: i64,
+    /// data downloaded this session (bytes).
+    pub dl_info_data: i64,
+    /// global download rate (bytes/s).
+    pub dl_info_speed: i64,
+    /// download rate limit (bytes/s).
+    pub dl_rate_limit: i64,
+    /// data uploaded this session (bytes).
+    pub up_info_data: i64,
+    /// global upload rate (bytes/s).
+    pub up_info_speed: i64,
+    /// upload rate limit (bytes/s).
+    pub up_rate_limit: i64,
+    /// data downloaded since the first start of the client (bytes).
+    pub alltime_dl: i64,
+    /// data uploaded since the first start of the client (bytes).
+    pub alltime_ul: i64,
+    /// free space left on the default save path's disk (bytes).
+    pub free_space_on_disk: i64,
+    /// global share ratio, formatted as a string by qbittorrent (eg: `"1.25"`).
+    pub global_ratio: String,
+    /// whether torrent queueing is enabled.
+    pub queueing: bool,
+    /// whether the alternative speed limits are enabled.
+    pub use_alt_speed_limits: bool,
+    /// whether subcategories are enabled.
+    pub use_subcategories: bool,
+    /// suggested interval (milliseconds) between two sync requests.
+    pub refresh_interval: i64,
+    /// total number of peer connections.
+    pub total_peer_connections: i64,
+    /// data wasted this session (bytes).
+    pub total_wasted_session: i64,
+    /// average time a job spends in the disk queue (milliseconds).
+    pub average_time_queue: i64,
+    /// number of queued disk jobs.
+    pub queued_io_jobs: i64,
+    /// total size of the disk buffers (bytes).
+    pub total_buffers_size: i64,
+    /// total size of the queued disk jobs (bytes).
+    pub total_queued_size: i64,
+    /// read cache hits, formatted as a string by qbittorrent.
+    pub read_cache_hits: String,
+    /// read cache overload, formatted as a string by qbittorrent.
+    pub read_cache_overload: String,
+    /// write cache overload, formatted as a string by qbittorrent.
+    pub write_cache_overload: String,
+    /// last known external IPv4 address.
+    pub last_external_address_v4: String,
+    /// last known external IPv6 address.
+    pub last_external_address_v6: String,
+}
+
+/// ## Info
+/// represents a single response of `/sync/maindata`: either a full snapshot or a partial update (delta) relative to a previous `rid`.
+///
+/// torrents, categories and the server state are kept as raw json objects, since a partial update only contains the fields that changed.
+/// apply it to a [`SyncState`] to get a typed view.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MainDataDelta {
+    /// response id, to be sent back on the next request.
+    pub rid: u64,
+    /// whether the response contains all the data, instead of only what changed.
+    pub full_update: bool,
+    /// changed torrents, keyed by hash.
+    pub torrents: Option<HashMap<String, Map<String, Value>>>,
+    /// hashes of the removed torrents.
+    pub torrents_removed: Option<Vec<String>>,
+    /// changed categories, keyed by name.
+    pub categories: Option<HashMap<String, Map<String, Value>>>,
+    /// names of the removed categories.
+    pub categories_removed: Option<Vec<String>>,
+    /// added tags.
+    pub tags: Option<Vec<String>>,
+    /// removed tags.
+    pub tags_removed: Option<Vec<String>>,
+    /// changed trackers, keyed by url, each with the hashes of the torrents using it.
+    pub trackers: Option<HashMap<String, Vec<String>>>,
+    /// urls of the removed trackers.
+    pub trackers_removed: Option<Vec<String>>,
+    /// changed fields of the server state.
+    pub server_state: Option<Map<String, Value>>,
+}
+
+/// ## Info
+/// a typed, in-memory snapshot of the qbittorrent main data, kept up to date by applying [`MainDataDelta`]s.
+///
+/// most of the time you'll want to use [`MainDataSync`], which does the requests and keeps track of the `rid` for you.
+#[derive(Debug, Clone, Default)]
+pub struct SyncState {
+    rid: u64,
+    torrents: HashMap<String, TorrentInfo>,
+    categories: HashMap<String, CategoryInfo>,
+    tags: Vec<String>,
+    trackers: HashMap<String, Vec<String>>,
+    server_state: ServerState,
+}
+impl SyncState {
+    /// ## Usage
+    /// creates a new, empty [`SyncState`], with `rid` set to 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Usage
+    /// returns the `rid` of the last applied [`MainDataDelta`].
+    pub fn rid(&self) -> u64 {
+        self.rid
+    }
+
+    /// ## Usage
+    /// returns all the torrents, keyed by hash.
+    pub fn torrents(&self) -> &HashMap<String, TorrentInfo> {
+        &self.torrents
+    }
+
+    /// ## Usage
+    /// returns the torrent with the specified hash, if it exists.
+    pub fn torrent(&self, hash: impl AsRef<str>) -> Option<&TorrentInfo> {
+        self.torrents.get(hash.as_ref())
+    }
+
+    /// ## Usage
+    /// returns all the categories, keyed by name.
+    pub fn categories(&self) -> &HashMap<String, CategoryInfo> {
+        &self.categories
+    }
+
+    /// ## Usage
+    /// returns all the tags.
+    pub fn tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    /// ## Usage
+    /// returns all the trackers, keyed by url, each with the hashes of the torrents using it.
+    pub fn trackers(&self) -> &HashMap<String, Vec<String>> {
+        &self.trackers
+    }
+
+    /// ## Usage
+    /// returns the server state.
+    pub fn server_state(&self) -> &ServerState {
+        &self.server_state
+    }
+
+    /// ## Usage
+    /// applies a [`MainDataDelta`] to the snapshot.
+    /// if the delta is a full update, everything previously known is discarded first.
+    ///
+    /// ## Errors
+    /// - returns an [`Error`] with error type [`ErrorType::JsonSerdeError`] if a changed object doesn't match its typed representation.
+    ///   in that case, the state is left untouched.
+    pub fn apply(&mut self, delta: MainDataDelta) -> Result<(), Error> {
+        self.apply_replacing(delta).map(|_| ())
+    }
+
+    /// like `apply`, but returns what the delta replaced, to tell what changed without keeping a copy of the whole snapshot.
+    pub(crate) fn apply_replacing(&mut self, delta: MainDataDelta) -> Result<Replaced, Error> {
+        // the changed objects are merged before anything is touched, so that a delta that doesn't fit leaves the state as it was.
+        let known = !delta.full_update;
+
+        let torrents = delta
+            .torrents
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(hash, changes)| {
+                let mut torrent: TorrentInfo = merge(self.torrents.get(&hash).filter(|_| known), changes)?;
+                torrent.hash = hash.clone();
+                Ok((hash, torrent))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let categories = delta
+            .categories
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, changes)| {
+                let mut category: CategoryInfo = merge(self.categories.get(&name).filter(|_| known), changes)?;
+                category.name = name.clone();
+                Ok((name, category))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let server_state = delta.server_state.map(|changes| merge(Some(&self.server_state).filter(|_| known), changes)).transpose()?;
+
+        let mut replaced = Replaced {
+            changed: torrents.iter().map(|(hash, _)| hash.clone()).collect(),
+            ..Replaced::default()
+        };
+
+        if delta.full_update {
+            let previous = std::mem::take(self);
+            replaced.torrents = previous.torrents;
+            replaced.server_state = Some(previous.server_state);
+        }
+
+        for (hash, torrent) in torrents {
+            if let Some(previous) = self.torrents.insert(hash.clone(), torrent) {
+                replaced.torrents.insert(hash, previous);
+            }
+        }
+
+        for hash in delta.torrents_removed.unwrap_or_default() {
+            if let Some(previous) = self.torrents.remove(&hash) {
+                // if the torrent also changed in this delta, the version it had before is already there.
+                replaced.torrents.entry(hash).or_insert(previous);
+            }
+        }
+
+        self.categories.extend(categories);
+        for name in delta.categories_removed.unwrap_or_default() {
+            self.categories.remove(&name);
+        }
+
+        for tag in delta.tags.unwrap_or_default() {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+
+        if let Some(removed) = delta.tags_removed {
+            self.tags.retain(|tag| !removed.contains(tag));
+        }
+
+        if let Some(trackers) = delta.trackers {
+            self.trackers.extend(trackers);
+        }
+
+        for url in delta.trackers_removed.unwrap_or_default() {
+            self.trackers.remove(&url);
+        }
+
+        if let Some(server_state) = server_state {
+            let previous = std::mem::replace(&mut self.server_state, server_state);
+            replaced.server_state.get_or_insert(previous);
+        }
+
+        self.rid = delta.rid;
+        Ok(replaced)
+    }
+}
+
+/// what applying a [`MainDataDelta`] to a [`SyncState`] replaced.
+#[derive(Debug, Default)]
+pub(crate) struct Replaced {
+    /// the hashes of the torrents the delta added or changed.
+    pub(crate) changed: Vec<String>,
+    /// the previous version of the torrents the delta changed or removed (all of them, for a full update), keyed by hash.
+    pub(crate) torrents: HashMap<String, TorrentInfo>,
+    /// the previous server state, if the delta changed it.
+    pub(crate) server_state: Option<ServerState>,
+}
+
+/// overlays the changed fields of a partial update on top of the current typed value (or the default one, if there's none yet).
+pub(crate) fn merge<T: Serialize + DeserializeOwned + Default>(current: Option<&T>, changes: Map<String, Value>) -> Result<T, Error> {
+    let to_err = |e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None);
+
+    let mut value = match current {
+        Some(current) => serde_json::to_value(current).map_err(to_err)?,
+        None => serde_json::to_value(T::default()).map_err(to_err)?,
+    };
+
+    if let Value::Object(map) = &mut value {
+        map.extend(changes);
+    }
+
+    serde_json::from_value(value).map_err(to_err)
+}
+
+/// ## Info
+/// keeps a [`SyncState`] up to date by polling `/sync/maindata`, sending back the last received `rid` so that qbittorrent only sends what changed.
+///
+/// ## Example
+/// ```no_run
+/// # use qbittorrent_rust::{core::{api::QbitApi, creds::Credentials}, MainDataSync};
+/// # async fn example() -> Result<(), qbittorrent_rust::Error> {
+/// let api = QbitApi::new("http://localhost:6011/", Credentials::new("user_name", "password")).await?;
+/// let mut sync = MainDataSync::new(api);
+///
+/// let state = sync.update().await?;
+/// println!("{} torrents", state.torrents().len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MainDataSync {
+    api: QbitApi,
+    state: SyncState,
+}
+impl MainDataSync {
+    /// ## Usage
+    /// creates a new [`MainDataSync`] with an empty [`SyncState`]. the first call to `update` will get a full snapshot.
+    pub fn new(api: QbitApi) -> Self {
+        Self { api, state: SyncState::new() }
+    }
+
+    /// ## Usage
+    /// requests the changes since the last update and applies them, returning the updated [`SyncState`].
+    pub async fn update(&mut self) -> Result<&SyncState, Error> {
+        self.apply_next_delta().await?;
+        Ok(&self.state)
+    }
+
+    /// requests the changes since the last update and applies them, returning what they replaced.
+    pub(crate) async fn apply_next_delta(&mut self) -> Result<Replaced, Error> {
+        let delta = self.api.sync_get_main_data_delta(self.state.rid()).await?;
+        self.state.apply_replacing(delta)
+    }
+
+    /// ## Usage
+    /// returns the current [`SyncState`], without doing any request.
+    pub fn state(&self) -> &SyncState {
+        &self.state
+    }
+
+    /// ## Usage
+    /// discards the current [`SyncState`], so that the next call to `update` will get a full snapshot.
+    pub fn reset(&mut self) {
+        self.state = SyncState::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::api_fns::torrents::info::TorrentState;
+
+    const DEBIAN: &str = "8c212779b4abde7c6bc608063a0d008b7e40ce32";
+    const ARCH: &str = "c4b2b8a7e1d9f3a6b5c4d3e2f1a0b9c8d7e6f5a4";
+
+    fn delta(value: Value) -> MainDataDelta {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn snapshot() -> SyncState {
+        let mut state = SyncState::new();
+        state
+            .apply(delta(json!({
+                "rid": 1,
+                "full_update": true,
+                "torrents": {
+                    DEBIAN: { "name": "debian.iso", "state": "downloading", "progress": 0.5, "category": "linux", "tags": "iso" },
+                    ARCH: { "name": "arch.iso", "state": "pausedUP", "progress": 1.0 },
+                },
+                "categories": { "linux": { "name": "linux", "savePath": "/downloads/linux" }, "iso": { "name": "iso" } },
+                "tags": ["iso", "new"],
+                "trackers": { "udp://tracker.opentrackr.org:1337/announce": [DEBIAN] },
+                "server_state": { "connection_status": "connected", "dl_info_speed": 1024, "dht_nodes": 312 },
+            })))
+            .unwrap();
+        state
+    }
+
+    #[test]
+    fn full_updates_replace_everything() {
+        let mut state = snapshot();
+        assert_eq!((state.rid(), state.torrents().len(), state.categories().len()), (1, 2, 2));
+        assert_eq!(state.torrent(DEBIAN).unwrap().hash, DEBIAN);
+        assert_eq!(state.categories()["linux"].save_path, "/downloads/linux");
+
+        state
+            .apply(delta(json!({
+                "rid": 7,
+                "full_update": true,
+                "torrents": { ARCH: { "name": "arch.iso" } },
+                "server_state": { "connection_status": "firewalled" },
+            })))
+            .unwrap();
+
+        assert_eq!(state.rid(), 7);
+        assert_eq!(state.torrents().keys().collect::<Vec<_>>(), [ARCH]);
+        assert_eq!(state.torrent(ARCH).unwrap().state, TorrentState::default());
+        assert!(state.categories().is_empty() && state.tags().is_empty() && state.trackers().is_empty());
+        assert_eq!(state.server_state().dl_info_speed, 0);
+    }
+
+    #[test]
+    fn removals_are_applied() {
+        let mut state = snapshot();
+
+        state
+            .apply(delta(json!({
+                "rid": 2,
+                "torrents_removed": [DEBIAN, "0000000000000000000000000000000000000000"],
+                "categories_removed": ["iso"],
+                "tags_removed": ["new"],
+                "trackers_removed": ["udp://tracker.opentrackr.org:1337/announce"],
+            })))
+            .unwrap();
+
+        assert_eq!(state.torrents().keys().collect::<Vec<_>>(), [ARCH]);
+        assert_eq!(state.categories().keys().collect::<Vec<_>>(), ["linux"]);
+        assert_eq!(state.tags(), &["iso"]);
+        assert!(state.trackers().is_empty());
+    }
+
+    #[test]
+    fn partial_updates_only_change_the_fields_they_contain() {
+        let mut state = snapshot();
+
+        state
+            .apply(delta(json!({
+                "rid": 2,
+                "torrents": { DEBIAN: { "progress": 1.0, "state": "uploading" } },
+                "categories": { "linux": { "savePath": "/data/linux" } },
+                "tags": ["new", "linux"],
+                "server_state": { "dl_info_speed": 0 },
+            })))
+            .unwrap();
+
+        let debian = state.torrent(DEBIAN).unwrap();
+        assert_eq!((debian.name.as_str(), debian.category.as_str(), debian.tags.as_slice()), ("debian.iso", "linux", &["iso".to_string()][..]));
+        assert_eq!((debian.progress, debian.state), (1.0, TorrentState::Uploading));
+        assert_eq!(state.torrent(ARCH).unwrap().state, TorrentState::PausedUploading);
+
+        assert_eq!(state.categories()["linux"], CategoryInfo { name: "linux".to_string(), save_path: "/data/linux".to_string() });
+        assert_eq!(state.tags(), &["iso", "new", "linux"]);
+
+        assert_eq!(state.server_state().connection_status, "connected");
+        assert_eq!((state.server_state().dl_info_speed, state.server_state().dht_nodes), (0, 312));
+    }
+
+    #[test]
+    fn invalid_updates_leave_the_state_untouched() {
+        let mut state = snapshot();
+
+        let err = state
+            .apply(delta(json!({
+                "rid": 2,
+                "torrents": { ARCH: { "progress": "done" } },
+                "torrents_removed": [DEBIAN],
+                "categories": { "arch": {} },
+            })))
+            .unwrap_err();
+
+        assert!(matches!(err.err_type, ErrorType::JsonSerdeError(_)));
+        assert_eq!((state.rid(), state.torrents().len()), (1, 2));
+        assert_eq!(state.categories().len(), 2);
+    }
+}
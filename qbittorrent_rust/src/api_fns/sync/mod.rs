@@ -1 +1,5 @@
-pub mod sync;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod sync;
+pub mod main_data;
+pub mod events;
+pub mod peers;
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::{borrow::Borrow, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{api_fns::torrents::info::TorrentHash, core::api::QbitApi, Error};
+
+use super::main_data::merge;
+
+/// ## Info
+/// represents a peer of a torrent, as reported by `/sync/torrentPeers`.
+///
+/// every field falls back to its default value if qbittorrent doesn't send it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PeerInfo {
+    /// address of the peer, formatted as `host:port`. this is the format expected by [`QbitApi::transfer_ban_peers()`].
+    pub address: String,
+    /// ip of the peer.
+    pub ip: String,
+    /// port of the peer.
+    pub port: u16,
+    /// client used by the peer.
+    pub client: String,
+    /// client of the peer, as decoded from its peer id.
+    pub peer_id_client: String,
+    /// country of the peer.
+    pub country: String,
+    /// ISO 3166-1 alpha-2 code of the country of the peer.
+    pub country_code: String,
+    /// connection flags (eg: `"D X E P"`).
+    pub flags: String,
+    /// description of each connection flag.
+    pub flags_desc: String,
+    /// the peer's progress (percentage/100).
+    pub progress: f64,
+    /// download speed from the peer (bytes/s).
+    pub dl_speed: i64,
+    /// upload speed to the peer (bytes/s).
+    pub up_speed: i64,
+    /// data downloaded from the peer (bytes).
+    pub downloaded: i64,
+    /// data uploaded to the peer (bytes).
+    pub uploaded: i64,
+    /// connection type (eg: `"BT"`, `"μTP"`, `"Web"`).
+    pub connection: String,
+    /// relevance of the peer's pieces for the torrent (percentage/100).
+    pub relevance: f64,
+    /// files the peer is currently transferring.
+    pub files: String,
+}
+
+/// ## Info
+/// represents a single response of `/sync/torrentPeers`: either a full list of peers or a partial update (delta) relative to a previous `rid`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TorrentPeersDelta {
+    /// response id, to be sent back on the next request.
+    pub rid: u64,
+    /// whether the response contains all the peers, instead of only what changed.
+    pub full_update: bool,
+    /// whether the peer flags should be shown.
+    pub show_flags: Option<bool>,
+    /// changed peers, keyed by `host:port`.
+    pub peers: Option<HashMap<String, Map<String, Value>>>,
+    /// addresses of the removed peers.
+    pub peers_removed: Option<Vec<String>>,
+}
+
+/// ## Info
+/// describes a change in the peers of a torrent, as reported by [`PeerSync::update()`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PeerEvent {
+    /// a new peer connected.
+    Joined(PeerInfo),
+    /// a peer disconnected; contains its last known information.
+    Left(PeerInfo),
+}
+
+/// ## Info
+/// keeps a typed map of the peers of a torrent up to date by polling `/sync/torrentPeers`, sending back the last received `rid` so that qbittorrent only sends what changed.
+///
+/// ## Example
+/// ```no_run
+/// # use qbittorrent_rust::{core::{api::QbitApi, creds::Credentials}, PeerSync, TorrentHash};
+/// # async fn example(hash: TorrentHash) -> Result<(), qbittorrent_rust::Error> {
+/// let api = QbitApi::new("http://localhost:6011/", Credentials::new("user_name", "password")).await?;
+/// let mut peers = PeerSync::new(api.clone(), hash);
+/// peers.update().await?;
+///
+/// let leechers = peers.peers().values().filter(|p| p.uploaded > 0 && p.progress == 0.0).map(|p| p.address.clone()).collect::<Vec<String>>();
+/// api.transfer_ban_peers(leechers).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PeerSync {
+    api: QbitApi,
+    hash: TorrentHash,
+    rid: u64,
+    peers: HashMap<String, PeerInfo>,
+}
+impl PeerSync {
+    /// ## Usage
+    /// creates a new [`PeerSync`] for the specified torrent, with no known peers. the first call to `update` will get the full list.
+    pub fn new(api: QbitApi, hash: impl Borrow<TorrentHash>) -> Self {
+        Self {
+            api,
+            hash: hash.borrow().clone(),
+            rid: 0,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// ## Usage
+    /// returns the hash of the torrent the peers belong to.
+    pub fn hash(&self) -> &TorrentHash {
+        &self.hash
+    }
+
+    /// ## Usage
+    /// returns the `rid` of the last applied update.
+    pub fn rid(&self) -> u64 {
+        self.rid
+    }
+
+    /// ## Usage
+    /// returns all the known peers, keyed by `host:port`.
+    pub fn peers(&self) -> &HashMap<String, PeerInfo> {
+        &self.peers
+    }
+
+    /// ## Usage
+    /// requests the changes since the last update and applies them, returning which peers joined or left.
+    pub async fn update(&mut self) -> Result<Vec<PeerEvent>, Error> {
+        let delta = self.api.sync_get_torrent_peers_delta(&self.hash, self.rid).await?;
+        self.apply(delta)
+    }
+
+    /// ## Usage
+    /// applies a [`TorrentPeersDelta`] to the known peers, returning which peers joined or left.
+    /// if the delta is a full update, peers not present in it are considered as having left.
+    ///
+    /// ## Errors
+    /// - returns an [`Error`] with error type [`crate::error_handling::error_type::ErrorType::JsonSerdeError`] if a changed peer doesn't match its typed representation.
+    ///   in that case, the known peers are left untouched.
+    pub fn apply(&mut self, delta: TorrentPeersDelta) -> Result<Vec<PeerEvent>, Error> {
+        let mut next = if delta.full_update { HashMap::new() } else { self.peers.clone() };
+
+        if let Some(peers) = delta.peers {
+            for (address, changes) in peers {
+                let mut peer: PeerInfo = merge(next.get(&address), changes)?;
+                peer.address = address.clone();
+                next.insert(address, peer);
+            }
+        }
+
+        for address in delta.peers_removed.unwrap_or_default() {
+            next.remove(&address);
+        }
+
+        let mut events = vec![];
+
+        for (address, peer) in next.iter() {
+            if !self.peers.contains_key(address) {
+                events.push(PeerEvent::Joined(peer.clone()));
+            }
+        }
+
+        for (address, peer) in self.peers.iter() {
+            if !next.contains_key(address) {
+                events.push(PeerEvent::Left(peer.clone()));
+            }
+        }
+
+        self.rid = delta.rid;
+        self.peers = next;
+        Ok(events)
+    }
+}
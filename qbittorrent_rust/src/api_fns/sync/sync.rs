@@ -1,29 +1,47 @@
+use std::borrow::Borrow;
+
 use serde_json::Value;
 
-use crate::{core::api::QbitApi, error_handling::error_type::ErrorType, Error};
+use crate::{api_fns::torrents::info::TorrentHash, core::api::QbitApi, error_handling::errors::parse_json, Error};
+
+use super::{main_data::MainDataDelta, peers::TorrentPeersDelta};
 
 impl QbitApi {
     /// ## Usage
     /// Gets the main sync data as a [`String`].
-    pub async fn sync_get_main_data_raw(&mut self, rid: u64) -> Result<String, Error> {
-        Self::make_request(self, format!("/sync/maindata?rid={}", rid), "sync_get_main_data".to_string()).await
+    pub async fn sync_get_main_data_raw(&self, rid: u64) -> Result<String, Error> {
+        Self::make_request(self, "sync_get_main_data_raw", format!("/sync/maindata?rid={}", rid)).await
     }
 
     /// ## Usage
     /// Gets the main sync data as a json [`Value`].
-    pub async fn sync_get_main_data(&mut self, rid: u64) -> Result<Value, Error> {
-        serde_json::from_str(Self::sync_get_main_data_raw(self, rid).await?.as_str()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn sync_get_main_data(&self, rid: u64) -> Result<Value, Error> {
+        parse_json(&Self::sync_get_main_data_raw(self, rid).await?, "sync_get_main_data", "/sync/maindata")
+    }
+
+    /// ## Usage
+    /// Gets the main sync data as a [`MainDataDelta`].
+    /// To keep a merged snapshot of the main data, look at [`crate::MainDataSync`].
+    pub async fn sync_get_main_data_delta(&self, rid: u64) -> Result<MainDataDelta, Error> {
+        parse_json(&Self::sync_get_main_data_raw(self, rid).await?, "sync_get_main_data_delta", "/sync/maindata")
     }
 
     /// ## Usage
     /// Gets the torrents peer's sync data as a [`String`].
-    pub async fn sync_get_torrent_peers_data_raw(&mut self, hash: impl AsRef<String>, rid: u64) -> Result<String, Error> {
-        Self::make_request(self, format!("/sync/torrentPeers?hash={}&rid={}",hash.as_ref(), rid), "sync_get_torrent_peers_data".to_string()).await
+    pub async fn sync_get_torrent_peers_data_raw(&self, hash: impl AsRef<str>, rid: u64) -> Result<String, Error> {
+        Self::make_request(self, "sync_get_torrent_peers_data_raw", format!("/sync/torrentPeers?hash={}&rid={}",hash.as_ref(), rid)).await
     }
 
     /// ## Usage
     /// Gets the torrents peer's sync data as a json [`Value`].
-    pub async fn sync_get_torrent_peers_data(&mut self, hash: impl AsRef<String>, rid: u64) -> Result<Value, Error> {
-        serde_json::from_str(Self::sync_get_torrent_peers_data_raw(self, hash, rid).await?.as_str()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn sync_get_torrent_peers_data(&self, hash: impl AsRef<str>, rid: u64) -> Result<Value, Error> {
+        parse_json(&Self::sync_get_torrent_peers_data_raw(self, hash, rid).await?, "sync_get_torrent_peers_data", "/sync/torrentPeers")
+    }
+
+    /// ## Usage
+    /// Gets the torrents peer's sync data as a [`TorrentPeersDelta`].
+    /// To keep a merged map of the peers, look at [`crate::PeerSync`].
+    pub async fn sync_get_torrent_peers_delta(&self, hash: impl Borrow<TorrentHash>, rid: u64) -> Result<TorrentPeersDelta, Error> {
+        parse_json(&Self::sync_get_torrent_peers_data_raw(self, &hash.borrow().hash, rid).await?, "sync_get_torrent_peers_delta", "/sync/torrentPeers")
     }
 }
\ No newline at end of file
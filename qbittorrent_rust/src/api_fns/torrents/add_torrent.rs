@@ -1,12 +1,9 @@
-use core::panic;
 use std::borrow::Borrow;
 
 use proc_macros_qbittorrent_rust::Builder;
-use reqwest::header;
-use tokio::{fs::File, io::AsyncReadExt};
 
 use crate::{
-    code, core::api::QbitApi, error_handling::{error_type::ErrorType, errors::Error}, misc::sep_vec::SepVec
+    core::{api::QbitApi, transport::{MultipartField, MultipartValue, RequestBody, TransportRequest}}, error_handling::{error_type::ErrorType, errors::Error}, misc::sep_vec::SepVec
 };
 
 use super::torrents::Torrent;
@@ -22,6 +19,9 @@ pub struct TorrentAddDescriptor {
 
     paths: Vec<String>,
 
+    /// contents of .torrent files given in memory.
+    bytes: Vec<Vec<u8>>,
+
     /// Download folder path
     savepath: Option<String>,
 
@@ -37,7 +37,7 @@ pub struct TorrentAddDescriptor {
     /// Skip hash checking (true, false)
     skip_checking: Option<bool>,
 
-    /// Add torrents in a paused state (true, false)
+    /// Add torrents in a paused state (true, false); sent as `stopped` to qbittorrent 5.
     paused: Option<bool>,
 
     /// Create the root folder (true, false, or unset)
@@ -106,7 +106,7 @@ impl TorrentAddDescriptor {
 /// | `category`       |  `String`  | Category for the torrent.                                                   |
 /// | `tags`           |  `Vec<String>`  | Tags for the torrent.                                         |
 /// | `skip_checking`  |  `Bool`  | Whether to skip hash checking. (default: false)|
-/// | `paused`         |  `Bool`  | Add torrents in the paused state. (default: false)|
+/// | `paused`         |  `Bool`  | Add torrents in the paused state, sent as `stopped` to qbittorrent 5. (default: false)|
 /// | `root_folder`    |  `Bool`  | Whether to create the root folder.|
 /// | `rename`         |  `String`  | Rename the torrents.                                                             |
 /// | `upLimit`        | `Integer` | Set torrent upload speed limit. Unit in bytes/second                       |
@@ -136,7 +136,7 @@ pub struct TorrentAddDescriptorBuilder {
     /// Skip hash checking (true, false)
     skip_checking: Option<bool>,
 
-    /// Add torrents in a paused state (true, false)
+    /// Add torrents in a paused state (true, false); sent as `stopped` to qbittorrent 5.
     paused: Option<bool>,
 
     /// Create the root folder (true, false, or unset)
@@ -200,13 +200,14 @@ impl TorrentAddDescriptorBuilder {
     /// ## Errors
     /// - if the `torrent`s vector was set as empty, it will return an [`Error`] with error type [`ErrorType::TorrentsNotSet`].
     pub fn build(self) -> Result<TorrentAddDescriptor, Error> {
-        let (urls, paths) = match self.torrents {
+        let (urls, paths, bytes) = match self.torrents {
             Some(t) => {
                 if t.is_empty() {
                     return Err(Error::build(ErrorType::TorrentsNotSet, None));
                 } else {
                     let mut vec_urls = vec![];
                     let mut vec_paths = vec![];
+                    let mut vec_bytes = vec![];
 
                     for item in t.iter().map(|l| l.get_inner()) {
                         match item {
@@ -216,10 +217,13 @@ impl TorrentAddDescriptorBuilder {
                             crate::api_fns::torrents::torrents::TorrentInner::RawTorrent(path) => {
                                 vec_paths.push(path)
                             }
+                            crate::api_fns::torrents::torrents::TorrentInner::Bytes(bytes) => {
+                                vec_bytes.push(bytes)
+                            }
                         }
                     }
 
-                    (SepVec::new(vec_urls, "".to_string()), vec_paths)
+                    (SepVec::new(vec_urls, "\n".to_string()), vec_paths, vec_bytes)
                 }
             }
             None => {
@@ -227,7 +231,7 @@ impl TorrentAddDescriptorBuilder {
             }
         };
 
-        let tags = self.tags.and_then(|v| Some(SepVec::new(v, ',')));
+        let tags = self.tags.map(|v| SepVec::new(v, ','));
 
         let root_folder = match self.root_folder {
             None => String::from("unset"),
@@ -238,10 +242,11 @@ impl TorrentAddDescriptorBuilder {
         Ok(TorrentAddDescriptor {
             urls,
             paths,
+            bytes,
             savepath: self.savepath,
             cookie: self.cookie,
             category: self.category,
-            tags: tags,
+            tags,
             skip_checking: self.skip_checking,
             paused: self.paused,
             root_folder: Some(root_folder),
@@ -260,210 +265,165 @@ impl TorrentAddDescriptorBuilder {
 impl QbitApi {
     ///## Usage
     /// adds one (or more) torrents.
-    pub async fn torrents_add_torrent(&mut self, descriptor: impl Borrow<TorrentAddDescriptor>) -> Result<(), Error> {
-        let descriptor = descriptor.borrow();
+    pub async fn torrents_add_torrent(&self, descriptor: impl Borrow<TorrentAddDescriptor>) -> Result<(), Error> {
+        let method = "torrents_add_torrent";
+        crate::core::instrument::call(method, self.add_torrent(descriptor.borrow())).await.map_err(|e| e.with_method(method))
+    }
+
+    async fn add_torrent(&self, descriptor: &TorrentAddDescriptor) -> Result<(), Error> {
+        let request = TransportRequest::post(&self.api_url, "/torrents/add")?;
+
+        // qbittorrent 5 renamed the `paused` parameter to `stopped`.
+        let paused_param = match descriptor.paused.is_some() && self.is_qbittorrent_5().await {
+            true => "stopped",
+            false => "paused",
+        };
 
         match (
-            descriptor.paths.is_empty(),
+            descriptor.paths.is_empty() && descriptor.bytes.is_empty(),
             descriptor.urls.inner_vec().is_empty(),
         ) {
-            (true, true) => panic!(),
+            (true, true) => Err(Error::build(ErrorType::TorrentsNotSet, None)),
             (true, false) => {
-                let mut form_urls = reqwest::multipart::Form::new();
-
-                form_urls = form_urls.text("urls", descriptor.urls.to_string());
-
-                form_urls = thing(form_urls, descriptor.clone());
+                let response_urls = self.send_authenticated(request.body(urls_part(descriptor, paused_param))).await?;
 
-                let response_urls = self
-                    .reqwest_client
-                    .post(format!("{}/api/v2/torrents/add", self.authority))
-                    .multipart(form_urls)
-                    .header(header::COOKIE, format!("SID={}", self.get_cookie().await?))
-                    .send()
-                    .await
-                    .map_err(|e| {
-                        Error::build(ErrorType::ReqwestError(Box::new(e)), None)
-                    })?;
-
-                if response_urls.status().is_success() {
-                    return Ok(());
-                } else {
-                    return Err(Error::build(ErrorType::MiscNetError(code!(response_urls).unwrap()), code!(response_urls)));
-                }
+                Self::read_response("/torrents/add", response_urls)?;
+                Ok(())
             }
             (false, true) => {
-                let form = torrents_part(&descriptor).await?;
-
-                let response_torrents = self
-                    .reqwest_client
-                    .post(format!("{}/api/v2/torrents/add", self.authority))
-                    .multipart(form)
-                    .header(header::COOKIE, format!("SID={}", self.get_cookie().await?))
-                    .send()
-                    .await
-                    .map_err(|e| {
-                        Error::build(ErrorType::ReqwestError(Box::new(e)), None)
-                    })?;
-
-                if response_torrents.status().is_success() {
-                    return Ok(());
-                } else {
-                    return Err(Error::build(ErrorType::MiscNetError(code!(response_torrents).unwrap()), code!(response_torrents)));
-                }
-            }
-
-            (false, false) => {
-                // ---------- TORRENT FILES ----------
-                let form_torrents = torrents_part(&descriptor).await?;
-
-                let built_torrents = self
-                    .reqwest_client
-                    .post(format!("{}/api/v2/torrents/add", self.authority))
-                    .multipart(form_torrents)
-                    .header(header::COOKIE, format!("SID={}", self.get_cookie().await?));
-
-                // ---------- TORRENT FILES ----------
+                let files = self.read_torrent_files(descriptor).await?;
 
-                // ---------- URLS ----------
-                let mut form_urls = reqwest::multipart::Form::new();
+                let response_torrents = self.send_authenticated(request.body(torrents_part(files, descriptor, paused_param))).await?;
 
-                form_urls = form_urls.text("urls", descriptor.urls.to_string());
-                form_urls = thing(form_urls, descriptor.clone());
-
-                let built_urls = self
-                    .reqwest_client
-                    .post(format!("{}/api/v2/torrents/add", self.authority))
-                    .multipart(form_urls)
-                    .header(header::COOKIE, format!("SID={}", self.get_cookie().await?));
+                Self::read_response("/torrents/add", response_torrents)?;
+                Ok(())
+            }
 
-                // ---------- URLS ----------
+            (false, false) => {
+                let files = self.read_torrent_files(descriptor).await?;
 
-                let (response_torrents, response_urls) =
-                    tokio::join!(built_torrents.send(), built_urls.send());
+                let (response_torrents, response_urls) = futures_util::join!(
+                    self.send_authenticated(request.clone().body(torrents_part(files, descriptor, paused_param))),
+                    self.send_authenticated(request.body(urls_part(descriptor, paused_param)))
+                );
 
-                let mut thing = (false, false);
+                Self::read_response("/torrents/add", response_torrents?)?;
+                Self::read_response("/torrents/add", response_urls?)?;
+                Ok(())
+            }
+        }
+    }
+}
 
-                if response_torrents
-                    .map_err(|e| {
-                        Error::build(ErrorType::ReqwestError(Box::new(e)), None)
-                    })?
-                    .status()
-                    .is_success()
-                {
-                    thing.0 = true;
-                }
+fn urls_part(descriptor: &TorrentAddDescriptor, paused_param: &str) -> RequestBody {
+    let form_urls = vec![text_field("urls", descriptor.urls.to_string())];
 
-                if response_urls
-                    .map_err(|e| {
-                        Error::build(ErrorType::ReqwestError(Box::new(e)), None)
-                    })?
-                    .status()
-                    .is_success()
-                {
-                    thing.1 = true
-                }
+    RequestBody::Multipart(thing(form_urls, descriptor.clone(), paused_param))
+}
 
-                match thing {
-                        (true, true) => return Ok(()),
-                        (true, false) => return Err(Error::build(ErrorType::MiscError("something went wrong while adding urls.".to_string()), None)),
-                        (false, true) => return Err(Error::build(ErrorType::MiscError("something went wrong while adding torrent files.".to_string()), None)),
-                        (false, false) => return Err(Error::build(ErrorType::MiscError("wow, you really messed up. both torrents and urls failed.".to_string()), None)),
-                    }
-            }
-        };
-    }
+fn text_field(name: &str, value: String) -> MultipartField {
+    MultipartField { name: name.to_string(), value: MultipartValue::Text(value) }
 }
 
 fn thing(
-    mut form: reqwest::multipart::Form,
+    mut form: Vec<MultipartField>,
     descriptor: TorrentAddDescriptor,
-) -> reqwest::multipart::Form {
+    paused_param: &str,
+) -> Vec<MultipartField> {
     if let Some(savepath) = descriptor.savepath {
-        form = form.text("savepath", savepath);
+        form.push(text_field("savepath", savepath));
     }
 
     if let Some(cookie) = descriptor.cookie {
-        form = form.text("cookie", cookie);
+        form.push(text_field("cookie", cookie));
     }
 
     if let Some(category) = descriptor.category {
-        form = form.text("category", category);
+        form.push(text_field("category", category));
     }
 
     if let Some(tags) = descriptor.tags {
-        form = form.text("tags", tags.to_string());
+        form.push(text_field("tags", tags.to_string()));
     }
 
     if let Some(skip_checking) = descriptor.skip_checking {
-        form = form.text("skip_checking", skip_checking.to_string());
+        form.push(text_field("skip_checking", skip_checking.to_string()));
     }
 
     if let Some(paused) = descriptor.paused {
-        form = form.text("paused", paused.to_string());
+        form.push(text_field(paused_param, paused.to_string()));
     }
 
     if let Some(root_folder) = descriptor.root_folder {
-        form = form.text("root_folder", root_folder);
+        form.push(text_field("root_folder", root_folder));
     }
 
     if let Some(rename) = descriptor.rename {
-        form = form.text("rename", rename);
+        form.push(text_field("rename", rename));
     }
 
     if let Some(up_limit) = descriptor.up_limit {
-        form = form.text("upLimit", up_limit.to_string());
+        form.push(text_field("upLimit", up_limit.to_string()));
     }
 
     if let Some(dl_limit) = descriptor.dl_limit {
-        form = form.text("dlLimit", dl_limit.to_string());
+        form.push(text_field("dlLimit", dl_limit.to_string()));
     }
 
     if let Some(ratio_limit) = descriptor.ratio_limit {
-        form = form.text("ratioLimit", ratio_limit.to_string());
+        form.push(text_field("ratioLimit", ratio_limit.to_string()));
     }
 
     if let Some(seeding_time_limit) = descriptor.seeding_time_limit {
-        form = form.text("seedingTimeLimit", seeding_time_limit.to_string());
+        form.push(text_field("seedingTimeLimit", seeding_time_limit.to_string()));
     }
 
     if let Some(auto_tmm) = descriptor.auto_tmm {
-        form = form.text("autoTMM", auto_tmm.to_string());
+        form.push(text_field("autoTMM", auto_tmm.to_string()));
     }
 
     if let Some(sequential_download) = descriptor.sequential_download {
-        form = form.text("sequentialDownload", sequential_download.to_string());
+        form.push(text_field("sequentialDownload", sequential_download.to_string()));
     }
 
     if let Some(first_last_piece_prio) = descriptor.first_last_piece_prio {
-        form = form.text("firstLastPiecePrio", first_last_piece_prio.to_string());
+        form.push(text_field("firstLastPiecePrio", first_last_piece_prio.to_string()));
     }
 
     form
 }
 
-async fn torrents_part(
-    descriptor: &TorrentAddDescriptor,
-) -> Result<reqwest::multipart::Form, Error> {
-    let mut form_torrents = reqwest::multipart::Form::new();
-    for path in descriptor.paths.clone() {
-        let mut file = File::open(path)
-            .await
-            .map_err(|_| Error::build(ErrorType::TorrentFilePathError, None))?;
-
-        let mut buffer = Vec::new();
+impl QbitApi {
+    /// returns the contents of every .torrent file of the descriptor: first the ones read with the [`crate::core::file_reader::FileReader`], then the in-memory ones.
+    async fn read_torrent_files(&self, descriptor: &TorrentAddDescriptor) -> Result<Vec<Vec<u8>>, Error> {
+        let mut files = vec![];
+        for path in &descriptor.paths {
+            let buffer = self
+                .file_reader
+                .read(path)
+                .await
+                .map_err(|_| Error::build(ErrorType::TorrentFilePathError, None))?;
+
+            files.push(buffer);
+        }
 
-        file.read_to_end(&mut buffer)
-            .await
-            .map_err(|_| Error::build(ErrorType::TorrentFilePathError, None))?;
+        files.extend(descriptor.bytes.iter().cloned());
+        Ok(files)
+    }
+}
 
-        // part 4 the multipart form
-        let file_part = reqwest::multipart::Part::bytes(buffer)
-            .file_name("torrent_file.torrent")
-            .mime_str("application/x-bittorrent")
-            .unwrap();
+fn torrents_part(files: Vec<Vec<u8>>, descriptor: &TorrentAddDescriptor, paused_param: &str) -> RequestBody {
+    let fields = files
+        .into_iter()
+        .map(|bytes| MultipartField {
+            name: "torrents".to_string(),
+            value: MultipartValue::File {
+                file_name: "torrent_file.torrent".to_string(),
+                content_type: "application/x-bittorrent".to_string(),
+                bytes,
+            },
+        })
+        .collect();
 
-        form_torrents = form_torrents.part("torrents", file_part);
-    }
-    Ok(form_torrents)
-}
\ No newline at end of file
+    RequestBody::Multipart(thing(fields, descriptor.clone(), paused_param))
+}
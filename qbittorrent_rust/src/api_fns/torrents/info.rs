@@ -1,7 +1,8 @@
 use std::borrow::Borrow;
 
-use crate::{core::api::QbitApi, error_handling::error_type::ErrorType, misc::sep_vec::SepVec, Error};
+use crate::{core::api::QbitApi, error_handling::{error_type::ErrorType, errors::parse_json}, misc::sep_vec::SepVec, Error};
 use proc_macros_qbittorrent_rust::Builder;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// ## Info
@@ -19,22 +20,6 @@ impl TorrentHash {
     pub fn new<S:Into<String>, T:Into<String>>(name: S, hash: T) -> Self {
         TorrentHash { name: name.into(), hash: hash.into() }
     }
-
-    pub(crate) fn new_multiple<S, T, U, V>(names: U, hashes: V) -> Vec<Self> 
-    where
-        T: Into<String>,
-        U: IntoIterator<Item = T>, 
-        S: Into<String>,
-        V: IntoIterator<Item = S>
-    {
-        let binding = names.into_iter().map(|x| Into::<String>::into(x)).collect::<Vec<String>>();
-        let iter_names = binding.iter();
-        let binding = hashes.into_iter().map(|x| Into::<String>::into(x)).collect::<Vec<String>>();
-        let iter_hashes = binding.iter();
-
-        let torrent_hashes = iter_names.zip(iter_hashes).map(|(l, d)| Self::new(l, d)).collect::<Vec<Self>>();
-        torrent_hashes
-    }
 }
 
 /// ## Info
@@ -59,15 +44,18 @@ impl Category {
     pub fn get_category_from_str<S:Into<String>>(string: S) -> Category {
         let string: String = string.into();
         if string.is_empty() {
-            return Category::NoCategory;
+            Category::NoCategory
         } else {
-            return Category::Custom(string);
-        };
+            Category::Custom(string)
+        }
     }
 }
 
 /// ## Info
 /// represents the state of a torrent.
+///
+/// qbittorrent 5 renamed the `paused` and `resumed` filters to `stopped` and `running`: either name can be used with any version,
+/// [`QbitApi::torrents_get_torrent_list()`] sends the one the connected qbittorrent understands.
 #[derive(Debug)]
 pub enum State {
     All,
@@ -75,9 +63,11 @@ pub enum State {
     Seeding,
     Completed,
     Paused,
+    Stopped,
     Active,
     Inactive,
     Resumed,
+    Running,
     Stalled,
     StalledUploading,
     StalledDownloading,
@@ -91,9 +81,11 @@ impl State {
             State::Seeding => String::from("seeding"),
             State::Completed => String::from("completed"),
             State::Paused => String::from("paused"),
+            State::Stopped => String::from("stopped"),
             State::Active => String::from("active"),
             State::Inactive => String::from("inactive"),
             State::Resumed => String::from("resumed"),
+            State::Running => String::from("running"),
             State::Stalled => String::from("stalled"),
             State::StalledUploading => String::from("stalled_uploading"),
             State::StalledDownloading => String::from("stalled_downloading"),
@@ -110,29 +102,33 @@ impl State {
     pub fn get_state_from_str<S:Into<String>>(string: S) -> Result<State, Error> {
         let string = string.into();
 
-        if string == "all".to_string() {
-            return Ok(State::All);
-        } else if string == "downloading".to_string() {
-            return Ok(Self::Downloading);
-        } else if string == "seeding".to_string() {
+        if string == "all" {
+            Ok(State::All)
+        } else if string == "downloading" {
+            Ok(Self::Downloading)
+        } else if string == "seeding" {
             Ok(State::Seeding)
-        } else if string == "completed".to_string() {
+        } else if string == "completed" {
             Ok(State::Completed)
-        } else if string == "paused".to_string() {
+        } else if string == "paused" {
             Ok(State::Paused)
-        } else if string == "active".to_string() {
+        } else if string == "stopped" {
+            Ok(State::Stopped)
+        } else if string == "active" {
             Ok(State::Active)
-        } else if string == "inactive".to_string() {
+        } else if string == "inactive" {
             Ok(State::Inactive)
-        } else if string == "resumed".to_string() {
+        } else if string == "resumed" {
             Ok(State::Resumed)
-        } else if string == "stalled".to_string() {
+        } else if string == "running" {
+            Ok(State::Running)
+        } else if string == "stalled" {
             Ok(State::Stalled)
-        } else if string == "stalled_uploading".to_string() {
+        } else if string == "stalled_uploading" {
             Ok(State::StalledUploading)
-        } else if string == "stalled_downloading".to_string() {
+        } else if string == "stalled_downloading" {
             Ok(State::StalledDownloading)
-        } else if string == "errored".to_string() {
+        } else if string == "errored" {
             Ok(State::Errored)
         } else {
             Err(Error::build(ErrorType::ParameterNotExpected, None))            
@@ -168,6 +164,12 @@ impl TorrentListGetConfig {
     }
 }
 
+impl Default for TorrentListGetConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// ## Info
 /// builder struct for [`TorrentListGetConfig`].
 /// 
@@ -212,7 +214,7 @@ impl TorrentListGetConfigBuilder {
     /// ## Info
     /// builds a [`TorrentListGetConfig`] from a [`TorrentListGetConfigBuilder`].
     pub fn build(self) -> TorrentListGetConfig {
-        let filter = self.filter.and_then(|x| Some(x.get_str_state()));
+        let filter = self.filter.map(|x| x.get_str_state());
         let category = match self.category {
             Some(x) => x.get_str_category(),
             None => None,
@@ -226,38 +228,325 @@ impl TorrentListGetConfigBuilder {
             reverse: self.reverse,
             limit: self.limit,
             offset: self.offset,
-            hashes: self.hashes.and_then(|s| Some(SepVec::new(s, String::from("|")))),
+            hashes: self.hashes.map(|s| SepVec::new(s, String::from("|"))),
         }
     }
 }
 
+impl Default for TorrentListGetConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ## Info
+/// represents the state a torrent is currently in, as reported by qbittorrent.
+///
+/// unlike [`State`], which is only used to filter the torrent list, this enum describes the actual state of a single torrent.
+/// any state not known by the library is deserialized as [`TorrentState::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum TorrentState {
+    /// some error occurred, applies to paused torrents.
+    #[serde(rename = "error")]
+    Error,
+    /// torrent data files are missing.
+    #[serde(rename = "missingFiles")]
+    MissingFiles,
+    /// torrent is being seeded and data is being transferred.
+    #[serde(rename = "uploading")]
+    Uploading,
+    /// torrent is paused and has finished downloading.
+    #[serde(rename = "pausedUP")]
+    PausedUploading,
+    /// torrent is stopped and has finished downloading (qbittorrent 5.x).
+    #[serde(rename = "stoppedUP")]
+    StoppedUploading,
+    /// queuing is enabled and torrent is queued for upload.
+    #[serde(rename = "queuedUP")]
+    QueuedUploading,
+    /// torrent is being seeded, but no connection were made.
+    #[serde(rename = "stalledUP")]
+    StalledUploading,
+    /// torrent has finished downloading and is being checked.
+    #[serde(rename = "checkingUP")]
+    CheckingUploading,
+    /// torrent is forced to uploading and ignore queue limit.
+    #[serde(rename = "forcedUP")]
+    ForcedUploading,
+    /// torrent is allocating disk space for download.
+    #[serde(rename = "allocating")]
+    Allocating,
+    /// torrent is being downloaded and data is being transferred.
+    #[serde(rename = "downloading")]
+    Downloading,
+    /// torrent has just started downloading and is fetching metadata.
+    #[serde(rename = "metaDL")]
+    MetadataDownloading,
+    /// torrent is forced to fetch metadata and ignore queue limit.
+    #[serde(rename = "forcedMetaDL")]
+    ForcedMetadataDownloading,
+    /// torrent is paused and has NOT finished downloading.
+    #[serde(rename = "pausedDL")]
+    PausedDownloading,
+    /// torrent is stopped and has NOT finished downloading (qbittorrent 5.x).
+    #[serde(rename = "stoppedDL")]
+    StoppedDownloading,
+    /// queuing is enabled and torrent is queued for download.
+    #[serde(rename = "queuedDL")]
+    QueuedDownloading,
+    /// torrent is being downloaded, but no connection were made.
+    #[serde(rename = "stalledDL")]
+    StalledDownloading,
+    /// same as `CheckingUploading`, but torrent has NOT finished downloading.
+    #[serde(rename = "checkingDL")]
+    CheckingDownloading,
+    /// torrent is forced to downloading to ignore queue limit.
+    #[serde(rename = "forcedDL")]
+    ForcedDownloading,
+    /// checking resume data on qbittorrent startup.
+    #[serde(rename = "checkingResumeData")]
+    CheckingResumeData,
+    /// torrent is moving to another location.
+    #[serde(rename = "moving")]
+    Moving,
+    /// unknown status.
+    #[default]
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+impl TorrentState {
+    /// every state, in the order they're declared.
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    pub(crate) const ALL: [TorrentState; 22] = [
+        TorrentState::Error,
+        TorrentState::MissingFiles,
+        TorrentState::Uploading,
+        TorrentState::PausedUploading,
+        TorrentState::StoppedUploading,
+        TorrentState::QueuedUploading,
+        TorrentState::StalledUploading,
+        TorrentState::CheckingUploading,
+        TorrentState::ForcedUploading,
+        TorrentState::Allocating,
+        TorrentState::Downloading,
+        TorrentState::MetadataDownloading,
+        TorrentState::ForcedMetadataDownloading,
+        TorrentState::PausedDownloading,
+        TorrentState::StoppedDownloading,
+        TorrentState::QueuedDownloading,
+        TorrentState::StalledDownloading,
+        TorrentState::CheckingDownloading,
+        TorrentState::ForcedDownloading,
+        TorrentState::CheckingResumeData,
+        TorrentState::Moving,
+        TorrentState::Unknown,
+    ];
+
+    /// ## Usage
+    /// returns `true` if the torrent has finished downloading, whether it is seeding, paused, queued or being checked.
+    pub fn is_completed(&self) -> bool {
+        matches!(
+            self,
+            TorrentState::Uploading
+                | TorrentState::PausedUploading
+                | TorrentState::StoppedUploading
+                | TorrentState::QueuedUploading
+                | TorrentState::StalledUploading
+                | TorrentState::CheckingUploading
+                | TorrentState::ForcedUploading
+        )
+    }
+
+    /// ## Usage
+    /// returns `true` if the torrent is paused (or stopped, in qbittorrent 5.x).
+    pub fn is_paused(&self) -> bool {
+        matches!(
+            self,
+            TorrentState::PausedUploading
+                | TorrentState::PausedDownloading
+                | TorrentState::StoppedUploading
+                | TorrentState::StoppedDownloading
+        )
+    }
+
+    /// ## Usage
+    /// returns `true` if the torrent is in an errored state.
+    pub fn is_errored(&self) -> bool {
+        matches!(self, TorrentState::Error | TorrentState::MissingFiles)
+    }
+}
+
+/// ## Info
+/// represents the information about a single torrent, as returned by [`QbitApi::torrents_get_torrent_list_typed()`].
+///
+/// every field falls back to its default value if qbittorrent doesn't send it, so that older and newer versions of the WebUI API can be read alike.
+/// explanation of each field: <https://github.com/qbittorrent/qBittorrent/wiki/WebUI-API-(qBittorrent-4.1)#get-torrent-list>
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TorrentInfo {
+    /// torrent hash.
+    pub hash: String,
+    /// torrent name.
+    pub name: String,
+    /// torrent state.
+    pub state: TorrentState,
+    /// torrent progress (percentage/100).
+    pub progress: f64,
+    /// torrent share ratio.
+    pub ratio: f64,
+    /// maximum share ratio until the torrent is stopped from seeding/uploading.
+    pub max_ratio: f64,
+    /// share ratio limit set for the torrent.
+    pub ratio_limit: f64,
+    /// total size (bytes) of files selected for download.
+    pub size: i64,
+    /// total size (bytes) of all files in the torrent, including unselected ones.
+    pub total_size: i64,
+    /// amount of data left to download (bytes).
+    pub amount_left: i64,
+    /// amount of transfer data completed (bytes).
+    pub completed: i64,
+    /// amount of data downloaded (bytes).
+    pub downloaded: i64,
+    /// amount of data downloaded this session (bytes).
+    pub downloaded_session: i64,
+    /// amount of data uploaded (bytes).
+    pub uploaded: i64,
+    /// amount of data uploaded this session (bytes).
+    pub uploaded_session: i64,
+    /// torrent download speed (bytes/s).
+    pub dlspeed: i64,
+    /// torrent upload speed (bytes/s).
+    pub upspeed: i64,
+    /// torrent download speed limit (bytes/s), `-1` if unlimited.
+    pub dl_limit: i64,
+    /// torrent upload speed limit (bytes/s), `-1` if unlimited.
+    pub up_limit: i64,
+    /// torrent ETA (seconds).
+    pub eta: i64,
+    /// time (unix epoch) when the torrent was added to the client.
+    pub added_on: i64,
+    /// time (unix epoch) when the torrent completed.
+    pub completion_on: i64,
+    /// last time (unix epoch) when a chunk was downloaded/uploaded.
+    pub last_activity: i64,
+    /// time (unix epoch) when the torrent was last seen complete.
+    pub seen_complete: i64,
+    /// total active time (seconds).
+    pub time_active: i64,
+    /// total seeding time (seconds).
+    pub seeding_time: i64,
+    /// maximum seeding time (seconds) until the torrent is stopped from seeding.
+    pub max_seeding_time: i64,
+    /// seeding time limit set for the torrent (minutes).
+    pub seeding_time_limit: i64,
+    /// category of the torrent, empty if it has none.
+    pub category: String,
+    /// tags of the torrent.
+    #[serde(with = "comma_separated")]
+    pub tags: Vec<String>,
+    /// the first tracker with working status, empty if there's none.
+    pub tracker: String,
+    /// number of trackers of the torrent.
+    pub trackers_count: i64,
+    /// path where this torrent's data is stored.
+    pub save_path: String,
+    /// absolute path of the torrent content (root path for multifile torrents, absolute file path for singlefile torrents).
+    pub content_path: String,
+    /// path where the incomplete data of the torrent is stored.
+    pub download_path: String,
+    /// magnet URI corresponding to this torrent.
+    pub magnet_uri: String,
+    /// torrent queue position, `-1` if torrent is seeding or queueing is disabled.
+    pub priority: i64,
+    /// number of seeds connected to.
+    pub num_seeds: i64,
+    /// number of seeds in the swarm.
+    pub num_complete: i64,
+    /// number of leechers connected to.
+    pub num_leechs: i64,
+    /// number of leechers in the swarm.
+    pub num_incomplete: i64,
+    /// percentage of file pieces currently available.
+    pub availability: f64,
+    /// whether the torrent is managed by Automatic Torrent Management.
+    pub auto_tmm: bool,
+    /// whether the first and last piece are prioritized.
+    pub f_l_piece_prio: bool,
+    /// whether force start is enabled for this torrent.
+    pub force_start: bool,
+    /// whether sequential download is enabled.
+    pub seq_dl: bool,
+    /// whether super seeding is enabled.
+    pub super_seeding: bool,
+}
+
+/// (de)serializes qbittorrent's comma separated tag lists (`"tag1,tag2"`) as a [`Vec`] of [`String`]s.
+mod comma_separated {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(vec: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&vec.join(","))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+        let string = String::deserialize(deserializer)?;
+
+        Ok(string
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect())
+    }
+}
+
 impl QbitApi {
     /// ## Usage
     /// returns a [`Vec`] containing multiple [`TorrentHash`]es, each corresponding to a torrent.
-    pub async fn torrents_get_hashes(&mut self) -> Result<Vec<TorrentHash>, Error> {
-        let jsons = self.torrents_get_torrent_list(TorrentListGetConfig::new()).await?;
+    pub async fn torrents_get_hashes(&self) -> Result<Vec<TorrentHash>, Error> {
+        let torrents = self.torrents_get_torrent_list_typed(TorrentListGetConfig::new()).await?;
 
-        let names: Vec<String> = jsons.as_array().unwrap().iter().map(|k| k.clone()["name"].take().as_str().unwrap().to_string()).collect();
-        let hashes: Vec<String> = jsons.as_array().unwrap().iter().map(|k| k.clone()["hash"].take().as_str().unwrap().to_string()).collect();
-        Ok(TorrentHash::new_multiple(names, hashes))
+        Ok(torrents.into_iter().map(|t| TorrentHash::new(t.name, t.hash)).collect())
+    }
+
+    /// ## Usage
+    /// gets the torrent list as a [`Vec`] of [`TorrentInfo`]s.
+    pub async fn torrents_get_torrent_list_typed(&self, config: impl Borrow<TorrentListGetConfig>) -> Result<Vec<TorrentInfo>, Error> {
+        parse_json(&Self::torrents_get_torrent_list_raw(self, config).await?, "torrents_get_torrent_list_typed", "/torrents/info")
     }
 
     /// ## Usage
     /// gets the torrent list as a [`serde_json::value::Value`]
-    pub async fn torrents_get_torrent_list(&mut self, config: impl Borrow<TorrentListGetConfig>) -> Result<Value, Error> {
-        serde_json::from_str(Self::torrents_get_torrent_list_raw(self, config).await?.as_str()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn torrents_get_torrent_list(&self, config: impl Borrow<TorrentListGetConfig>) -> Result<Value, Error> {
+        parse_json(&Self::torrents_get_torrent_list_raw(self, config).await?, "torrents_get_torrent_list", "/torrents/info")
     }
 
     /// ## Usage
     /// gets the list of torrents as a [`String`]. 
-    pub async fn torrents_get_torrent_list_raw(&mut self, config: impl Borrow<TorrentListGetConfig>) -> Result<String, Error> {
+    pub async fn torrents_get_torrent_list_raw(&self, config: impl Borrow<TorrentListGetConfig>) -> Result<String, Error> {
         let config: TorrentListGetConfig = config.borrow().clone();
 
-        let vec = config.hashes.and_then(|x| Some(x.to_string()));
+        let vec = config.hashes.map(|x| x.to_string());
+
+        // the version is only needed for the filters that qbittorrent 5 renamed.
+        let filter = match config.filter.as_deref() {
+            Some(filter @ ("paused" | "resumed" | "stopped" | "running")) => {
+                let renamed = match (self.is_qbittorrent_5().await, filter) {
+                    (true, "paused") => "stopped",
+                    (true, "resumed") => "running",
+                    (false, "stopped") => "paused",
+                    (false, "running") => "resumed",
+                    (_, filter) => filter,
+                };
+                Some(renamed.to_string())
+            }
+            _ => config.filter,
+        };
         
         let url = crate::url!(
             "/torrents/info",
-            ("filter", config.filter),
+            ("filter", filter),
             ("category", config.category),
             ("tag", config.tag),
             ("sort", config.sort),
@@ -267,6 +556,6 @@ impl QbitApi {
             ("hashes", vec)
         );
 
-        Self::make_request(self, url, "get_torrent_list").await
+        Self::make_request(self, "torrents_get_torrent_list_raw", url).await
     }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 pub mod add_torrent;
+#[allow(clippy::module_inception)]
 pub mod torrents;
 pub mod info;
 pub mod torrent_managing_misc;
\ No newline at end of file
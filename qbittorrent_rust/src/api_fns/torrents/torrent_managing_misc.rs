@@ -4,7 +4,7 @@ use proc_macros_qbittorrent_rust::{requires_hash, requires_mult_hashes};
 use serde_json::Value;
 
 use crate::{
-    core::api::QbitApi, error_handling::error_type::ErrorType, fn_hash_value_pair,
+    core::{api::QbitApi, version::ApiVersion}, error_handling::{error_type::ErrorType, errors::parse_json}, fn_hash_value_pair,
     fn_value_from_string, misc::sep_vec::SepVec, post_request_hash, request_error_focus,
     torrents_fn_mult_hashes, torrents_fn_mult_hashes_prios, url, Error,
 };
@@ -47,7 +47,7 @@ impl QbitApi {
     /// ## Usage
     /// Gets the properties of a specified torrent as a [`String`].
     pub async fn torrents_get_torrent_generic_properties_raw(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
     ) -> Result<String, Error> {
         Ok(self
@@ -60,7 +60,7 @@ impl QbitApi {
     /// ## Usage
     /// Gets the properties of a specified torrent as a [`serde_json::value::Value`]
     pub async fn torrents_get_torrent_generic_properties(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
     ) -> Result<Value, Error> {
         let mid: &TorrentHash = hash.borrow();
@@ -69,16 +69,8 @@ impl QbitApi {
 
         hashmap.insert("hash", mid.hash.as_str());
 
-        serde_json::from_str(
-            self.make_request_with_form_hash(
-                "/torrents/properties",
-                "get_torrent_generic_properties",
-                hashmap,
-            )
-            .await?
-            .as_str(),
-        )
-        .map_err(|k: serde_json::Error| Error::build(ErrorType::JsonSerdeError(Box::new(k)), None))
+        let properties = self.make_request_with_form_hash("torrents_get_torrent_generic_properties", "/torrents/properties", hashmap).await?;
+        parse_json(&properties, "torrents_get_torrent_generic_properties", "/torrents/properties")
     }
 
     fn_hash_value_pair!(
@@ -109,7 +101,7 @@ impl QbitApi {
     /// ## Usage
     /// Gets all the contents (files) of a torrent as a [`String`].
     pub async fn torrents_get_torrent_contents_raw(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
         indexes: impl Borrow<Option<Vec<String>>>,
     ) -> Result<String, Error> {
@@ -120,10 +112,10 @@ impl QbitApi {
         if let Some(vec) = indexes.borrow() {
             let sep_vec = SepVec::new(vec, '|').to_string();
             hashmap.insert("indexes", sep_vec);
-            self.make_request_with_form_hash("/torrents/files", "get_torrents_contents", hashmap)
+            self.make_request_with_form_hash("torrents_get_torrent_contents_raw", "/torrents/files", hashmap)
                 .await
         } else {
-            self.make_request_with_form_hash("/torrents/files", "get_torrents_contents", hashmap)
+            self.make_request_with_form_hash("torrents_get_torrent_contents_raw", "/torrents/files", hashmap)
                 .await
         }
     }
@@ -132,51 +124,45 @@ impl QbitApi {
     /// ## Usage
     /// Gets all the contents (files) of a torrent as a [`serde_json::value::Value`].
     pub async fn torrents_get_torrent_contents(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
         indexes: impl Borrow<Option<Vec<String>>>,
     ) -> Result<Value, Error> {
-        Ok(serde_json::from_str(
-            self.torrents_get_torrent_contents_raw(hash, indexes)
-                .await?
-                .as_str(),
-        )
-        .map_err(|l| Error::build(ErrorType::JsonSerdeError(Box::new(l)), None))?)
+        parse_json(&self.torrents_get_torrent_contents_raw(hash, indexes).await?, "torrents_get_torrent_contents", "/torrents/files")
     }
 
     #[requires_hash]
     /// ## Usage
     /// Gets all the contents (files) of a torrent as a [`Vec`] of [`TorrentContent`]s.
+    ///
+    /// ## Errors
+    /// - returns an [`Error`] with error type [`ErrorType::UnexpectedResponse`] if qbittorrent doesn't answer with a list of files, or a file has no name.
     pub async fn torrents_get_files_ids(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
-    ) -> Vec<TorrentContent> {
-        let x = self
-            .torrents_get_torrent_contents(hash, &None)
-            .await
-            .unwrap();
-
-        let mut res = vec![];
-
-        let len = x.as_array().iter().len();
-
-        for i in x.as_array().iter().zip(0..len) {
-            let name =
-                i.0.into_iter()
-                    .filter_map(|item| {
-                        item.get("name")
-                            .and_then(|name| name.as_str().map(|s| s.to_string()))
-                    })
-                    .collect();
-            let indx = i.1;
-
-            res.push(TorrentContent {
-                id: indx as u64,
-                name,
-            });
-        }
-
-        res
+    ) -> Result<Vec<TorrentContent>, Error> {
+        let contents = self.torrents_get_torrent_contents(hash, &None).await?;
+        let unexpected = |reason: &str| Error::build(ErrorType::UnexpectedResponse(reason.to_string()), None)
+            .with_method("torrents_get_files_ids")
+            .with_endpoint("/torrents/files");
+
+        let files = contents.as_array().ok_or_else(|| unexpected("expected a list of files."))?;
+
+        files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| {
+                let name = file
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| unexpected("a file has no name."))?;
+
+                // `index` is only sent since qbittorrent v4.4.0 (WebAPI v2.8.2); before that, files are listed in order.
+                let id = file.get("index").and_then(Value::as_u64).unwrap_or(i as u64);
+
+                Ok(TorrentContent { id, name: name.to_string() })
+            })
+            .collect()
     }
 
     fn_hash_value_pair!(
@@ -210,13 +196,13 @@ impl QbitApi {
         /// 1 -> Now downloading;
         /// 2 -> Already downloaded;
     pub async fn torrents_get_torrent_pieces_states_vec(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
     ) -> Result<Vec<u8>, Error> {
         let strr = self.torrents_get_torrent_pieces_states_raw(hash).await?;
         Ok(strr
             .chars()
-            .filter(|c| (*c == '0' || *c == '1' || *c == '2'))
+            .filter(|c| *c == '0' || *c == '1' || *c == '2' )
             .map(|c| (c as u8) - 48)
             .collect::<Vec<u8>>())
     }
@@ -237,7 +223,7 @@ impl QbitApi {
     /// ## Usage
     /// Gets all the hashes of the pieces of a specified torrent as a [`Vec`] of [`String`]s
     pub async fn torrents_get_torrent_pieces_hashes_vec(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
     ) -> Result<Vec<String>, Error> {
         let strr = self.torrents_get_torrent_pieces_hashes_raw(hash).await?;
@@ -252,21 +238,57 @@ impl QbitApi {
     }
 
 
-    torrents_fn_mult_hashes!(
-        #[requires_mult_hashes]
-        /// ## Usage
-        /// Pauses the torrents specified.
-        torrents_pause_torrents,
-        "/torrents/pause"
-    );
+    #[requires_mult_hashes]
+    /// ## Usage
+    /// Stops (pauses) the torrents specified.
+    ///
+    /// qbittorrent 5 renamed `/torrents/pause` to `/torrents/stop`: the right one is picked from the detected WebUI API version
+    /// (see [`ApiVersion::QBITTORRENT_5`]), falling back to `/torrents/pause` if it can't be detected.
+    pub async fn torrents_stop(&self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error> {
+        let path = match self.is_qbittorrent_5().await {
+            true => "/torrents/stop",
+            false => "/torrents/pause",
+        };
+
+        let url = url!(path, ("hashes", Some(hashes.borrow().get_string("|"))));
+        self.make_request("torrents_stop", url).await?;
 
-    torrents_fn_mult_hashes!(
-        #[requires_mult_hashes]
-        /// ## Usage
-        /// Resumes the torrents specified.
-        torrents_resume_torrents, 
-        "/torrents/resume"
-    );
+        Ok(())
+    }
+
+    #[requires_mult_hashes]
+    /// ## Usage
+    /// Starts (resumes) the torrents specified.
+    ///
+    /// qbittorrent 5 renamed `/torrents/resume` to `/torrents/start`: the right one is picked from the detected WebUI API version
+    /// (see [`ApiVersion::QBITTORRENT_5`]), falling back to `/torrents/resume` if it can't be detected.
+    pub async fn torrents_start(&self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error> {
+        let path = match self.is_qbittorrent_5().await {
+            true => "/torrents/start",
+            false => "/torrents/resume",
+        };
+
+        let url = url!(path, ("hashes", Some(hashes.borrow().get_string("|"))));
+        self.make_request("torrents_start", url).await?;
+
+        Ok(())
+    }
+
+    #[requires_mult_hashes]
+    /// ## Usage
+    /// Pauses the torrents specified.
+    /// alias of [`QbitApi::torrents_stop()`], which also works with qbittorrent 5.
+    pub async fn torrents_pause_torrents(&self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error> {
+        self.torrents_stop(hashes).await
+    }
+
+    #[requires_mult_hashes]
+    /// ## Usage
+    /// Resumes the torrents specified.
+    /// alias of [`QbitApi::torrents_start()`], which also works with qbittorrent 5.
+    pub async fn torrents_resume_torrents(&self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error> {
+        self.torrents_start(hashes).await
+    }
 
     #[requires_mult_hashes]
     /// ## Usage
@@ -275,7 +297,7 @@ impl QbitApi {
     /// ## Arguments
     /// - `delete_files`: if set to true, the downloaded data will also be deleted.
     pub async fn torrents_delete_torrents(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
         delete_files: bool,
     ) -> Result<(), Error> {
@@ -288,9 +310,7 @@ impl QbitApi {
             ("deleteFiles", Some(delete_files))
         );
 
-        self.make_request(url, stringify!($func_name))
-            .await
-            .map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
+        self.make_request("torrents_delete_torrents", url).await?;
 
         Ok(())
     }
@@ -351,17 +371,17 @@ impl QbitApi {
     /// ## Arguments
     /// - trackers: a [`Vec`] of urls for the trackers.
     pub async fn torrents_add_trackers_to_torrent<U: Into<String> + Clone>(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
         trackers: impl Borrow<Vec<U>>,
     ) -> Result<(), Error> {
         let vec = trackers
             .borrow()
-            .into_iter()
-            .map(|s| Into::<String>::into(s.clone()).replace("&", "%26"))
+            .iter()
+            .map(|s| Into::<String>::into(s.clone()))
             .collect::<Vec<String>>();
 
-        let sep_vec = SepVec::new(vec, "%0A").to_string();
+        let sep_vec = SepVec::new(vec, "\n").to_string();
 
         let mut hashmap = HashMap::new();
 
@@ -369,8 +389,8 @@ impl QbitApi {
         hashmap.insert("urls", sep_vec);
 
         self.make_request_with_form_hash(
-            "/torrents/addTrackers",
             "torrents_add_trackers_to_torrent",
+            "/torrents/addTrackers",
             hashmap,
         )
         .await?;
@@ -386,13 +406,13 @@ impl QbitApi {
     /// - orig_url: the original url of the tracker.
     /// - new_url: the new url of the tracker.
     pub async fn torrents_edit_trackers(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
-        orig_url: impl AsRef<String>,
-        new_url: impl AsRef<String>,
+        orig_url: impl Into<String>,
+        new_url: impl Into<String>,
     ) -> Result<(), Error> {
-        let orig_url: String = orig_url.as_ref().clone();
-        let new_url: String = new_url.as_ref().clone();
+        let orig_url: String = orig_url.into();
+        let new_url: String = new_url.into();
 
         let mut hashmap = HashMap::new();
 
@@ -407,12 +427,12 @@ impl QbitApi {
             hashmap,
             (
                 400,
-                ErrorType::MiscError("new_url is not a valid URL".to_string())
+                ErrorType::BadParameters("new_url is not a valid URL".to_string())
             ),
             (404, ErrorType::TorrentHashNotFound),
             (
                 409,
-                ErrorType::MiscError(
+                ErrorType::Conflict(
                     "new_url already exists for the torrent or orig_url couldn't be found."
                         .to_string()
                 )
@@ -428,7 +448,7 @@ impl QbitApi {
     /// ## Arguments
     /// - urls: a [`Vec`] of the urls of the trackers.
     pub async fn torrents_remove_trackers<S: Into<String> + Clone>(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
         urls: impl Borrow<Vec<S>>,
     ) -> Result<(), Error> {
@@ -452,7 +472,7 @@ impl QbitApi {
             (404, ErrorType::TorrentHashNotFound),
             (
                 409,
-                ErrorType::MiscError("all urls were not found.".to_string())
+                ErrorType::Conflict("all urls were not found.".to_string())
             )
         )?;
         Ok(())
@@ -466,7 +486,7 @@ impl QbitApi {
     /// - hashes: simply, a [`Vec`] of all the hashes of the torrents interested. 
     /// - peers: a [`Vec`] of peers, represented as [`String`]s; each peer must follow the format `host:port`
     pub async fn torrents_add_peers<S: Into<String> + Clone>(
-        &mut self,
+        &self,
         hashes: impl Borrow<Vec<TorrentHash>>,
         peers: impl Borrow<Vec<S>>,
     ) -> Result<(), Error> {
@@ -482,17 +502,17 @@ impl QbitApi {
 
         let mut hashmap = HashMap::new();
 
-        hashmap.insert("hash", hashes.to_string());
-        hashmap.insert("urls", peers.to_string());
+        hashmap.insert("hashes", hashes.to_string());
+        hashmap.insert("peers", peers.to_string());
 
         request_error_focus!(
             self,
-            torrents_remove_trackers,
+            torrents_add_peers,
             "/torrents/addPeers",
             hashmap,
             (
                 400,
-                ErrorType::MiscError("none of the supplied peers are valid".to_string())
+                ErrorType::BadParameters("none of the supplied peers are valid".to_string())
             )
         )?;
         Ok(())
@@ -504,7 +524,7 @@ impl QbitApi {
     /// ## Arguments
     /// - ids: a [`Vec`] of [`TorrentContent`]s. You can get the ids you need by using `QbitApi::torrents_get_files_ids`.
     pub async fn torrents_set_file_priority(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
         ids: impl Borrow<Vec<TorrentContent>>,
     ) -> Result<(), Error> {
@@ -516,7 +536,7 @@ impl QbitApi {
         hashmap.insert("hash", hash);
         hashmap.insert("id", ids.to_string());
 
-        request_error_focus!(self, torrents_set_file_priority, "/torrents/filePrio", hashmap, (400, ErrorType::MiscError("the priority is invalid or at least one file id is not a valid integer".to_string())), (404, ErrorType::TorrentHashNotFound), (409, ErrorType::MiscError("the torrent metadata hasn't downloaded yet or at least one file id was not found".to_string())))?;
+        request_error_focus!(self, torrents_set_file_priority, "/torrents/filePrio", hashmap, (400, ErrorType::BadParameters("the priority is invalid or at least one file id is not a valid integer".to_string())), (404, ErrorType::TorrentHashNotFound), (409, ErrorType::Conflict("the torrent metadata hasn't downloaded yet or at least one file id was not found".to_string())))?;
         Ok(())
     }
 
@@ -528,7 +548,7 @@ impl QbitApi {
     /// The method returns a [`String`] formatted in the following way: `{"torrent_hash":download_limit_in_bytes, "torrent_hash2":download_limit_in_bytes2, ...}`
     /// the value of the download limit will be `0` if there's no limit applied.
     pub async fn torrents_get_torrent_download_limit_raw(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
     ) -> Result<String, Error> {
         let hashes: TorrentHashesDesc = hashes.borrow().clone();
@@ -538,8 +558,8 @@ impl QbitApi {
         hashmap.insert("hashes", hashes.get_string("|"));
 
         self.make_request_with_form(
+            "torrents_get_torrent_download_limit_raw",
             "/torrents/downloadLimit",
-            "torrents_get_torrent_download_limit",
             hashmap,
         )
         .await
@@ -553,15 +573,10 @@ impl QbitApi {
     /// The method returns a json [`Value`] with the following structure: `{"torrent_hash_string":download_limit_in_bytes_int, "torrent_hash_string2":download_limit_in_bytes_int2, ...}`
     /// the value of the download limit will be `0` if there's no limit applied.
     pub async fn torrents_get_torrent_download_limit(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHashesDesc>,
     ) -> Result<Value, crate::Error> {
-        Ok(serde_json::from_str(
-            self.torrents_get_torrent_download_limit_raw(hash)
-                .await?
-                .as_str(),
-        )
-        .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?)
+        parse_json(&self.torrents_get_torrent_download_limit_raw(hash).await?, "torrents_get_torrent_download_limit", "/torrents/downloadLimit")
     }
 
     #[requires_mult_hashes]
@@ -571,7 +586,7 @@ impl QbitApi {
     /// ## Arguments
     /// - limit: download limit in bytes.
     pub async fn torrents_set_torrent_download_limit(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
         limit: u64,
     ) -> Result<(), Error> {
@@ -583,8 +598,8 @@ impl QbitApi {
         hashmap.insert("limit", limit.to_string());
 
         self.make_request_with_form(
+            "torrents_set_torrent_download_limit",
             "/torrents/setDownloadLimit",
-            "torrents_get_torrent_download_limit",
             hashmap,
         )
         .await?;
@@ -599,13 +614,21 @@ impl QbitApi {
     /// - ratio_limit: the maximum seeding ratio for the torrent. `-2` means the global limit should be used, `-1` means no limit.
     /// - seeding_time_limit: the maximum seeding time (minutes) for the torrent. `-2` means the global limit should be used, `-1` means no limit.
     /// - inactive_seeding_time_limit: the maximum amount of time (minutes) the torrent is allowed to seed while being inactive. `-2` means the global limit should be used, `-1` means no limit.
+    ///
+    /// ## Errors
+    /// returns an [`Error`] with error type [`ErrorType::Unsupported`] if `inactive_seeding_time_limit` isn't `-2`
+    /// and the WebUI API is older than 2.9.2 (qbittorrent 4.6), which would silently ignore it.
     pub async fn torrents_set_torrent_share_limit(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
         ratio_limit: f32,
         seeding_time_limit: i32,
         inactive_seeding_time_limit: i32,
     ) -> Result<(), Error> {
+        if inactive_seeding_time_limit != -2 {
+            self.require_api_version(ApiVersion::new(2, 9, 2)).await.map_err(|e| e.with_method("torrents_set_torrent_share_limit").with_endpoint("/torrents/setShareLimits"))?;
+        }
+
         let hashes: TorrentHashesDesc = hashes.borrow().clone();
 
         let mut hashmap = HashMap::new();
@@ -619,8 +642,8 @@ impl QbitApi {
         );
 
         self.make_request_with_form(
+            "torrents_set_torrent_share_limit",
             "/torrents/setShareLimits",
-            "torrents_get_torrent_download_limit",
             hashmap,
         )
         .await?;
@@ -637,7 +660,7 @@ impl QbitApi {
     /// The method returns a [`String`] formatted in the following way: `{"torrent_hash":upload_limit_in_bytes, "torrent_hash2":upload_limit_in_bytes2, ...}`
     /// the value of the upload limit will be `0` if there's no limit applied.
     pub async fn torrents_get_torrent_upload_limit_raw(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
     ) -> Result<String, Error> {
         let hashes: TorrentHashesDesc = hashes.borrow().clone();
@@ -647,8 +670,8 @@ impl QbitApi {
         hashmap.insert("hashes", hashes.get_string("|"));
 
         self.make_request_with_form(
+            "torrents_get_torrent_upload_limit_raw",
             "/torrents/uploadLimit",
-            "torrents_get_torrent_download_limit",
             hashmap,
         )
         .await
@@ -663,15 +686,10 @@ impl QbitApi {
     /// the method returns a json [`Value`] with the following structure: `{"torrent_hash_string":upload_limit_in_bytes_int, "torrent_hash_string2":upload_limit_in_bytes_int2, ...}`
     /// the value of the upload limit will be `0` if there's no limit applied.
     pub async fn torrents_get_torrent_upload_limit(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHashesDesc>,
     ) -> Result<Value, crate::Error> {
-        Ok(serde_json::from_str(
-            self.torrents_get_torrent_download_limit_raw(hash)
-                .await?
-                .as_str(),
-        )
-        .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?)
+        parse_json(&self.torrents_get_torrent_upload_limit_raw(hash).await?, "torrents_get_torrent_upload_limit", "/torrents/uploadLimit")
     }
 
     #[requires_mult_hashes]
@@ -681,7 +699,7 @@ impl QbitApi {
     /// ## Arguments
     /// - limit: upload limit in bytes.
     pub async fn torrents_set_torrent_upload_limit(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
         limit: u64,
     ) -> Result<(), Error> {
@@ -693,8 +711,8 @@ impl QbitApi {
         hashmap.insert("limit", limit.to_string());
 
         self.make_request_with_form(
+            "torrents_set_torrent_upload_limit",
             "/torrents/setUploadLimit",
-            "torrents_get_torrent_download_limit",
             hashmap,
         )
         .await?;
@@ -708,23 +726,23 @@ impl QbitApi {
     /// ## Arguments
     /// - location: the path where to download the torrents to.
     pub async fn torrents_set_torrent_download_location(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
-        location: impl AsRef<String>,
+        location: impl Into<String>,
     ) -> Result<(), Error> {
         let hashes: TorrentHashesDesc = hashes.borrow().clone();
 
         let mut hashmap = HashMap::new();
 
         hashmap.insert("hashes", hashes.get_string("|"));
-        hashmap.insert("location", location.as_ref().to_string());
+        hashmap.insert("location", location.into());
 
         request_error_focus!(
             self,
             torrents_set_torrent_download_location,
             "/torrents/setLocation",
             hashmap,
-            (400, ErrorType::MiscError("save path is empty".to_string())),
+            (400, ErrorType::BadParameters("save path is empty".to_string())),
             (404, ErrorType::TorrentHashNotFound),
             (
                 403,
@@ -732,7 +750,7 @@ impl QbitApi {
             ),
             (
                 409,
-                ErrorType::MiscError("unable to create save path directory".to_string())
+                ErrorType::Conflict("unable to create save path directory".to_string())
             )
         )?;
         Ok(())
@@ -745,7 +763,7 @@ impl QbitApi {
     /// ## Arguments
     /// - new_name: the new name for the torrent
     pub async fn torrents_set_torrent_name(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
         new_name: impl Into<String>,
     ) -> Result<(), Error> {
@@ -756,10 +774,7 @@ impl QbitApi {
         hashmap.insert("hash", hash.hash.to_string());
         hashmap.insert(
             "name",
-            new_name.into()
-                .replace(" ", "%20")
-                .replace("&", "%26")
-                .to_string(),
+            new_name.into(),
         );
 
         request_error_focus!(
@@ -770,7 +785,7 @@ impl QbitApi {
             (404, ErrorType::TorrentHashNotFound),
             (
                 409,
-                ErrorType::MiscError("torrent name is empty".to_string())
+                ErrorType::Conflict("torrent name is empty".to_string())
             )
         )?;
         Ok(())
@@ -783,7 +798,7 @@ impl QbitApi {
     /// ## Arguments
     /// - category_name: the name of the category.
     pub async fn torrents_set_torrent_category(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHashesDesc>,
         category_name: impl Into<String>,
     ) -> Result<(), Error> {
@@ -796,9 +811,7 @@ impl QbitApi {
             "category",
             category_name
                 .into()
-                .replace(" ", "")
-                .replace("&", "%26")
-                .to_string(),
+                .replace(" ", ""),
         );
 
         request_error_focus!(
@@ -808,7 +821,7 @@ impl QbitApi {
             hashmap,
             (
                 409,
-                ErrorType::MiscError("category name does not exist".to_string())
+                ErrorType::Conflict("category name does not exist".to_string())
             )
         )?;
         Ok(())
@@ -818,27 +831,20 @@ impl QbitApi {
     /// Gets all the categories available as a [`String`]. 
     /// 
     /// ## Example: 
+    /// ```json
     /// {
-    /// 
-    ///     "Video": {  
-    /// 
+    ///     "Video": {
     ///         "name": "Video",
-    /// 
     ///         "savePath": "/home/user/torrents/video/"
-    /// 
     ///     },
-    /// 
     ///     "eBooks": {
-    /// 
     ///         "name": "eBooks",
-    /// 
     ///         "savePath": "/home/user/torrents/eBooks/"
-    /// 
     ///     }
-    /// 
     /// }
-    pub async fn torrents_get_all_categories_raw(&mut self) -> Result<String, Error> {
-        self.make_request("/torrents/categories", "torrents_get_all_categories_raw")
+    /// ```
+    pub async fn torrents_get_all_categories_raw(&self) -> Result<String, Error> {
+        self.make_request("torrents_get_all_categories_raw", "/torrents/categories")
             .await
     }
 
@@ -846,30 +852,20 @@ impl QbitApi {
     /// Gets all the categories available as a json [`Value`].
     /// 
     /// ## Example: 
+    /// ```json
     /// {
-    /// 
-    ///     "Video": {  
-    /// 
+    ///     "Video": {
     ///         "name": "Video",
-    /// 
     ///         "savePath": "/home/user/torrents/video/"
-    /// 
     ///     },
-    /// 
     ///     "eBooks": {
-    /// 
     ///         "name": "eBooks",
-    /// 
     ///         "savePath": "/home/user/torrents/eBooks/"
-    /// 
     ///     }
-    /// 
     /// }
-    pub async fn torrents_get_all_categories(&mut self) -> Result<Value, crate::Error> {
-        Ok(
-            serde_json::from_str(self.torrents_get_all_categories_raw().await?.as_str())
-                .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?,
-        )
+    /// ```
+    pub async fn torrents_get_all_categories(&self) -> Result<Value, crate::Error> {
+        parse_json(&self.torrents_get_all_categories_raw().await?, "torrents_get_all_categories", "/torrents/categories")
     }
 
     ///## Usage
@@ -878,19 +874,23 @@ impl QbitApi {
     /// ## Arguments
     /// - category_name: the name of the category.
     /// - save_path: the location to which torrents with this category should be downloaded. If `None`, the default one is assumed.
+    ///
+    /// ## Errors
+    /// returns an [`Error`] with error type [`ErrorType::Unsupported`] if `save_path` is `Some`
+    /// and the WebUI API is older than 2.1.0, which would silently ignore it.
     pub async fn torrents_add_new_category(
-        &mut self,
+        &self,
         category_name: impl Into<String>,
         save_path: Option<impl Into<String>>,
     ) -> Result<(), Error> {
         let mut hashmap: HashMap<&str, String> = HashMap::new();
         let cat = category_name
             .into()
-            .replace(" ", "")
-            .replace("&", "%26")
-            .to_string();
+            .replace(" ", "");
         match save_path {
             Some(path) => {
+                self.require_api_version(ApiVersion::new(2, 1, 0)).await.map_err(|e| e.with_method("torrents_add_new_category").with_endpoint("/torrents/createCategory"))?;
+
                 hashmap.insert("category", cat);
                 hashmap.insert("savePath", path.into());
                 request_error_focus!(
@@ -900,14 +900,14 @@ impl QbitApi {
                     hashmap,
                     (
                         400,
-                        ErrorType::MiscError("category name is empty".to_string())
+                        ErrorType::BadParameters("category name is empty".to_string())
                     ),
                     (
                         409,
-                        ErrorType::MiscError("category name is invalid".to_string())
+                        ErrorType::Conflict("category name is invalid".to_string())
                     )
                 )?;
-                return Ok(());
+                Ok(())
             }
 
             None => {
@@ -919,14 +919,14 @@ impl QbitApi {
                     hashmap,
                     (
                         400,
-                        ErrorType::MiscError("category name is empty".to_string())
+                        ErrorType::BadParameters("category name is empty".to_string())
                     ),
                     (
                         409,
-                        ErrorType::MiscError("category name is invalid".to_string())
+                        ErrorType::Conflict("category name is invalid".to_string())
                     )
                 )?;
-                return Ok(());
+                Ok(())
             }
         }
     }
@@ -938,54 +938,52 @@ impl QbitApi {
     /// - category_name: the name of the category.
     /// - save_path: the new location to which torrents with this category should be downloaded. If `None`, the default one is assumed.
     pub async fn torrents_edit_category(
-        &mut self,
+        &self,
         category_name: impl Into<String>,
         save_path: Option<impl Into<String>>,
     ) -> Result<(), Error> {
         let mut hashmap: HashMap<&str, String> = HashMap::new();
         let cat = category_name
             .into()
-            .replace(" ", "")
-            .replace("&", "%26")
-            .to_string();
+            .replace(" ", "");
         match save_path {
             Some(path) => {
                 hashmap.insert("category", cat);
                 hashmap.insert("savePath", path.into());
                 request_error_focus!(
                     self,
-                    torrents_add_new_category,
+                    torrents_edit_category,
                     "/torrents/editCategory",
                     hashmap,
                     (
                         400,
-                        ErrorType::MiscError("category name is empty".to_string())
+                        ErrorType::BadParameters("category name is empty".to_string())
                     ),
                     (
                         409,
-                        ErrorType::MiscError("category editing failed".to_string())
+                        ErrorType::Conflict("category editing failed".to_string())
                     )
                 )?;
-                return Ok(());
+                Ok(())
             }
 
             None => {
                 hashmap.insert("category", cat);
                 request_error_focus!(
                     self,
-                    torrents_add_new_category,
+                    torrents_edit_category,
                     "/torrents/editCategory",
                     hashmap,
                     (
                         400,
-                        ErrorType::MiscError("category name is empty".to_string())
+                        ErrorType::BadParameters("category name is empty".to_string())
                     ),
                     (
                         409,
-                        ErrorType::MiscError("category editing failed".to_string())
+                        ErrorType::Conflict("category editing failed".to_string())
                     )
                 )?;
-                return Ok(());
+                Ok(())
             }
         }
     }
@@ -996,22 +994,22 @@ impl QbitApi {
     /// ## Arguments
     /// - categories_name: the names of the categories to be deleted.
     pub async fn torrents_remove_categories<S: Into<String> + Clone>(
-        &mut self,
+        &self,
         categories_name: impl Borrow<Vec<S>>,
     ) -> Result<(), Error> {
         let mut hashmap = HashMap::new();
         let sep_vec: SepVec<String, &str> = SepVec::new(
             categories_name
                 .borrow()
-                .into_iter()
+                .iter()
                 .map(|s| Into::<String>::into(s.clone())),
-            "%0A",
+            "\n",
         );
         hashmap.insert("categories", sep_vec.to_string());
 
         self.make_request_with_form(
-            "/torrents/removeCategories",
             "torrents_remove_categories",
+            "/torrents/removeCategories",
             hashmap,
         )
         .await?;
@@ -1025,8 +1023,8 @@ impl QbitApi {
     ///     "Tag 2",
     ///     ...
     /// ]
-    pub async fn torrents_get_all_tags_raw(&mut self) -> Result<String, Error> {
-        self.make_request("/torrents/tags", "torrents_get_all_tags_raw")
+    pub async fn torrents_get_all_tags_raw(&self) -> Result<String, Error> {
+        self.make_request("torrents_get_all_tags_raw", "/torrents/tags")
             .await
     }
 
@@ -1038,30 +1036,27 @@ impl QbitApi {
     ///     "Tag 2",
     ///     ...
     /// ]
-    pub async fn torrents_get_all_tags(&mut self) -> Result<Value, crate::Error> {
-        Ok(
-            serde_json::from_str(self.torrents_get_all_tags_raw().await?.as_str())
-                .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?,
-        )
+    pub async fn torrents_get_all_tags(&self) -> Result<Value, crate::Error> {
+        parse_json(&self.torrents_get_all_tags_raw().await?, "torrents_get_all_tags", "/torrents/tags")
     }
 
     /// ## Usage
     /// Adds new tags.
     pub async fn torrents_add_new_tags<S: Into<String> + Clone>(
-        &mut self,
+        &self,
         tags_name: impl Borrow<Vec<S>>,
     ) -> Result<(), Error> {
         let mut hashmap: HashMap<&str, String> = HashMap::new();
         let sep_vec: SepVec<String, &str> = SepVec::new(
             tags_name
                 .borrow()
-                .into_iter()
+                .iter()
                 .map(|k| Into::<String>::into(k.clone())),
             ",",
         );
         hashmap.insert("tags", sep_vec.to_string());
 
-        self.make_request_with_form("/torrents/createTags", "torrents_add_new_tags", hashmap)
+        self.make_request_with_form("torrents_add_new_tags", "/torrents/createTags", hashmap)
             .await?;
 
         Ok(())
@@ -1070,20 +1065,20 @@ impl QbitApi {
     /// ## Usage
     /// Removes existing tags.
     pub async fn torrents_remove_tags<S: Into<String> + Clone>(
-        &mut self,
+        &self,
         tags_name: impl Borrow<Vec<S>>,
     ) -> Result<(), Error> {
         let mut hashmap: HashMap<&str, String> = HashMap::new();
         let sep_vec: SepVec<String, &str> = SepVec::new(
             tags_name
                 .borrow()
-                .into_iter()
+                .iter()
                 .map(|k| Into::<String>::into(k.clone())),
             ",",
         );
         hashmap.insert("tags", sep_vec.to_string());
 
-        self.make_request_with_form("/torrents/deleteTags", "torrents_remove_tags", hashmap)
+        self.make_request_with_form("torrents_remove_tags", "/torrents/deleteTags", hashmap)
             .await?;
 
         Ok(())
@@ -1096,7 +1091,7 @@ impl QbitApi {
     /// ## Arguments
     /// - enabled: whether the automatic torrent management should be set as enabled or not.
     pub async fn torrents_set_automatic_torrents_management(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
         enabled: bool,
     ) -> Result<(), Error> {
@@ -1105,8 +1100,8 @@ impl QbitApi {
         hashmap.insert("enable", enabled.to_string());
 
         self.make_request_with_form(
+            "torrents_set_automatic_torrents_management",
             "/torrents/setAutoManagement",
-            "torrents_set_automatic_torrents_manaement",
             hashmap,
         )
         .await?;
@@ -1118,7 +1113,7 @@ impl QbitApi {
     /// ## Usage
     /// Toggles the sequential download for the specified torrents.
     pub async fn torrents_toggle_sequential_download(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
     ) -> Result<(), Error> {
         let url = url!(
@@ -1133,7 +1128,7 @@ impl QbitApi {
             )
         );
 
-        self.make_request(url, "torrents_toggle_sequential_download")
+        self.make_request("torrents_toggle_sequential_download", url)
             .await?;
         Ok(())
     }
@@ -1142,7 +1137,7 @@ impl QbitApi {
     /// ## Usage
     /// Toggles the  first/last piece priority for the specified torrents.
     pub async fn torrents_toggle_first_last_piece_priority(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
     ) -> Result<(), Error> {
         let url = url!(
@@ -1157,7 +1152,7 @@ impl QbitApi {
             )
         );
 
-        self.make_request(url, "set_first_last_piece_priority")
+        self.make_request("torrents_toggle_first_last_piece_priority", url)
             .await?;
         Ok(())
     }
@@ -1169,7 +1164,7 @@ impl QbitApi {
     /// ## Arguments
     /// - enabled: whether force start should be set as enabled or not.
     pub async fn torrents_set_force_start(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
         enabled: bool,
     ) -> Result<(), Error> {
@@ -1178,8 +1173,8 @@ impl QbitApi {
         hashmap.insert("value", enabled.to_string());
 
         self.make_request_with_form(
-            "/torrents/setForceStart",
             "torrents_set_force_start",
+            "/torrents/setForceStart",
             hashmap,
         )
         .await?;
@@ -1193,7 +1188,7 @@ impl QbitApi {
     /// ## Arguments
     /// - enabled: whether super seeding should be set as enabled or not.
     pub async fn torrents_set_super_seeding(
-        &mut self,
+        &self,
         hashes: impl Borrow<TorrentHashesDesc>,
         enabled: bool,
     ) -> Result<(), Error> {
@@ -1202,8 +1197,8 @@ impl QbitApi {
         hashmap.insert("value", enabled.to_string());
 
         self.make_request_with_form(
-            "/torrents/setSuperSeeding",
             "torrents_set_super_seeding",
+            "/torrents/setSuperSeeding",
             hashmap,
         )
         .await?;
@@ -1218,7 +1213,7 @@ impl QbitApi {
     /// - old_path: The old path of the torrent.
     /// - new_path: The new path to use for the file.
     pub async fn torrents_rename_file(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
         old_path: impl Into<String>,
         new_path: impl Into<String>,
@@ -1236,11 +1231,11 @@ impl QbitApi {
             hashmap,
             (
                 400,
-                ErrorType::MiscError("missing new_path parameter".to_string())
+                ErrorType::BadParameters("missing new_path parameter".to_string())
             ),
             (
                 409,
-                ErrorType::MiscError(
+                ErrorType::Conflict(
                     "invalid new_path or old_path, or newPath already in use".to_string()
                 )
             )
@@ -1257,7 +1252,7 @@ impl QbitApi {
     /// - old_path: The old path of the torrent.
     /// - new_path: The new path to use for the file.
     pub async fn torrents_rename_folder(
-        &mut self,
+        &self,
         hash: impl Borrow<TorrentHash>,
         old_path: impl Into<String>,
         new_path: impl Into<String>,
@@ -1275,11 +1270,11 @@ impl QbitApi {
             hashmap,
             (
                 400,
-                ErrorType::MiscError("missing new_path parameter".to_string())
+                ErrorType::BadParameters("missing new_path parameter".to_string())
             ),
             (
                 409,
-                ErrorType::MiscError(
+                ErrorType::Conflict(
                     "invalid new_path or old_path, or newPath already in use".to_string()
                 )
             )
@@ -3,6 +3,8 @@
 /// Describes the type of the torrent, either: 
 /// a URL, such as a magnet, but any URL is accepted;
 /// a torrent file (.torrent), it represents a file path.
+///
+/// to add a .torrent file that's already in memory, use [`Torrent::from_bytes()`].
 #[derive(Debug, Clone)]
 pub enum TorrentType<S: Clone + Into<String>> {
     Url(S),
@@ -13,6 +15,7 @@ pub enum TorrentType<S: Clone + Into<String>> {
 pub(crate) enum TorrentInner {
     Url(String),
     RawTorrent(String),
+    Bytes(Vec<u8>),
 }
 
 /// ## Info
@@ -35,6 +38,12 @@ impl Torrent {
         }
     }
 
+    /// ## Usage
+    /// creates a new [`Torrent`] from the contents of a .torrent file, without reading anything from the file system.
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self { inner: TorrentInner::Bytes(bytes.into()) }
+    }
+
     pub(crate) fn get_inner(&self) -> TorrentInner {
         self.inner.clone()
     }
@@ -1 +1,2 @@
+#[allow(clippy::module_inception)]
 pub mod transfer_info;
\ No newline at end of file
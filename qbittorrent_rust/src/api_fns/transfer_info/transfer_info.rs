@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use serde_json::Value;
 
 use crate::{
-    core::api::QbitApi, error_handling::error_type::ErrorType, misc::sep_vec::SepVec, Error,
+    core::api::QbitApi, error_handling::{error_type::ErrorType, errors::parse_json}, misc::sep_vec::SepVec, Error,
 };
 
 use crate::{post_request, post_request_no_return};
@@ -33,19 +33,22 @@ impl QbitApi {
 
     /// ## Usage
     /// Returns `1` if the alternative speed limits are enabled, `0` if they're not.
-    pub async fn transfer_get_alternative_speed_limits(&mut self) -> Result<u8, crate::Error> {
+    pub async fn transfer_get_alternative_speed_limits(&self) -> Result<u8, crate::Error> {
         let resp = Self::make_request(
             self,
+            "transfer_get_alternative_speed_limits",
             "/transfer/speedLimitsMode",
-            format!("{}", "transfer_get_alternative_speed_limits"),
         )
         .await?;
-        if resp.chars().next().unwrap() == '0' {
-            return Ok(0);
-        } else if resp.chars().next().unwrap() == '1' {
-            return Ok(1);
+        if resp.starts_with('0') {
+            Ok(0)
+        } else if resp.starts_with('1') {
+            Ok(1)
         } else {
-            return Err(Error::build(ErrorType::ParameterNotExpected, None));
+            Err(Error::build(ErrorType::UnexpectedResponse("expected either `0` or `1`.".to_string()), None)
+                .with_method("transfer_get_alternative_speed_limits")
+                .with_endpoint("/transfer/speedLimitsMode")
+                .with_body(resp))
         }
     }
 
@@ -58,30 +61,30 @@ impl QbitApi {
 
     /// ## Usage
     /// Gets the global download limit in bytes per second; this value will be 0 if there's no limit.
-    pub async fn transfer_get_global_download_limit(&mut self) -> Result<u64, crate::Error> {
+    pub async fn transfer_get_global_download_limit(&self) -> Result<u64, crate::Error> {
         let str = Self::make_request(
             self,
+            "transfer_get_global_download_limit",
             "/transfer/downloadLimit",
-            format!("{}", "transfer_get_global_download_limit"),
         )
         .await?;
-        Ok(str
+        str
             .parse::<u64>()
-            .map_err(|_| Error::build(ErrorType::ParameterNotExpected, None))?)
+            .map_err(|_| Error::build(ErrorType::ParameterNotExpected, None))
     }
 
     /// ## Usage
     /// Gets the global upload limit in bytes per second; this value will be 0 if there's no limit.
-    pub async fn transfer_get_global_upload_limit(&mut self) -> Result<u64, crate::Error> {
+    pub async fn transfer_get_global_upload_limit(&self) -> Result<u64, crate::Error> {
         let str = Self::make_request(
             self,
+            "transfer_get_global_upload_limit",
             "/transfer/uploadLimit",
-            format!("{}", "transfer_get_global_upload_limit"),
         )
         .await?;
-        Ok(str
+        str
             .parse::<u64>()
-            .map_err(|_| Error::build(ErrorType::ParameterNotExpected, None))?)
+            .map_err(|_| Error::build(ErrorType::ParameterNotExpected, None))
     }
 
     /// ## Usage
@@ -90,7 +93,7 @@ impl QbitApi {
     /// ## Arguments
     /// - peers: a [`Vec`] of strings, where each element is structured as `host:port`
     pub async fn transfer_ban_peers<S: Into<String>>(
-        &mut self,
+        &self,
         peers: Vec<S>,
     ) -> Result<(), Error> {
         let x = SepVec::new(
@@ -104,7 +107,7 @@ impl QbitApi {
 
         hashmap.insert("peers", y.as_str());
 
-        self.make_request_with_form("/transfer/banPeers", "ban_peers", hashmap)
+        self.make_request_with_form("transfer_ban_peers", "/transfer/banPeers", hashmap)
             .await?;
         Ok(())
     }
@@ -118,12 +121,7 @@ impl QbitApi {
 
     /// ## Usage
     /// Gets the global transfer info as a json [`Value`].
-    pub async fn transfer_get_global_transfer_info(&mut self) -> Result<Value, Error> {
-        serde_json::from_str(
-            Self::transfer_get_global_transfer_info_raw(self)
-                .await?
-                .as_str(),
-        )
-        .map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))
+    pub async fn transfer_get_global_transfer_info(&self) -> Result<Value, Error> {
+        parse_json(&Self::transfer_get_global_transfer_info_raw(self).await?, "transfer_get_global_transfer_info", "/transfer/info")
     }
 }
\ No newline at end of file
@@ -0,0 +1,295 @@
+//! a blocking (synchronous) facade over [`crate::core::api::QbitApi`], for scripts and codebases that don't use async.
+//!
+//! requires the `blocking` feature.
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+use std::{future::Future, sync::Arc};
+
+#[allow(unused_imports)]
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{
+    core::{api::QbitApi as AsyncQbitApi, builder::QbitApiBuilder, creds::AuthMode, version::ApiVersion},
+    error_handling::error_type::ErrorType,
+    Error,
+};
+#[cfg(feature = "torrents")]
+use crate::{TorrentAddDescriptor, TorrentContent, TorrentHash, TorrentHashesDesc, TorrentInfo, TorrentListGetConfig};
+#[cfg(feature = "app")]
+use crate::{BuildInfo, QBittorrentConfig};
+#[cfg(feature = "log")]
+use crate::GetLogConfig;
+#[cfg(feature = "sync")]
+use crate::{MainDataDelta, TorrentPeersDelta};
+#[cfg(feature = "rss")]
+use crate::RssAutoDownloadRule;
+#[cfg(feature = "search")]
+use crate::{Categories, SearchPlugin, SearchPluginsSpec};
+
+/// generates the blocking version of each listed method, which runs the async one to completion.
+macro_rules! blocking_fns {
+    ($(fn $name:ident $([$($generics:tt)*])? ($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            #[doc = concat!("blocking version of [`AsyncQbitApi::", stringify!($name), "()`].")]
+            pub fn $name $(<$($generics)*>)? (&self, $($arg: $ty),*) -> $ret {
+                self.block_on(self.inner.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+/// ## Info
+/// the blocking counterpart of [`AsyncQbitApi`]: it has the same methods, with the same arguments, but they wait for the response instead of returning a future.
+///
+/// it owns a small single-threaded tokio runtime, used to run the requests; clones share it, along with the session cookie and the request limits.
+/// when the last clone is dropped, the runtime is shut down in the background, so that can happen anywhere, even inside an async runtime.
+///
+/// ## Panics
+/// like every blocking client, its methods panic if called from inside an async runtime; use [`AsyncQbitApi`] there.
+///
+/// ## Example
+/// ```no_run
+/// # use qbittorrent_rust::{blocking::QbitApi, core::creds::Credentials, TorrentListGetConfig};
+/// # fn example() -> Result<(), qbittorrent_rust::Error> {
+/// let api = QbitApi::new("http://localhost:6011/", Credentials::new("user_name", "password"))?;
+///
+/// let torrents = api.torrents_get_torrent_list_typed(TorrentListGetConfig::default())?;
+/// println!("{} torrents", torrents.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct QbitApi {
+    inner: AsyncQbitApi,
+    runtime: Arc<BlockingRuntime>,
+}
+
+/// the runtime of the blocking client. dropping a tokio [`Runtime`] inside an async context panics, so it's shut down in the background instead.
+#[derive(Debug)]
+struct BlockingRuntime(Option<Runtime>);
+impl BlockingRuntime {
+    fn get(&self) -> &Runtime {
+        self.0.as_ref().expect("the runtime is only taken when it's dropped")
+    }
+}
+impl Drop for BlockingRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl QbitApi {
+    /// ## Usage
+    /// creates a new instance of [`QbitApi`], with a default http client.
+    /// blocking version of [`AsyncQbitApi::new()`]; to configure the client, use [`QbitApiBuilder::build_blocking()`].
+    pub fn new(authority: impl AsRef<str>, auth: impl Into<AuthMode>) -> Result<Self, Error> {
+        AsyncQbitApi::builder(authority, auth).build_blocking()
+    }
+
+    /// ## Usage
+    /// returns the async [`AsyncQbitApi`] used under the hood.
+    pub fn inner(&self) -> &AsyncQbitApi {
+        &self.inner
+    }
+
+    /// ## Usage
+    /// runs any future to completion on the runtime of this [`QbitApi`].
+    /// useful for the async parts of the library that have no blocking version, like [`crate::MainDataSync`]:
+    ///
+    /// ```no_run
+    /// # use qbittorrent_rust::{blocking::QbitApi, core::creds::Credentials, MainDataSync};
+    /// # fn example() -> Result<(), qbittorrent_rust::Error> {
+    /// let api = QbitApi::new("http://localhost:6011/", Credentials::new("user_name", "password"))?;
+    /// let mut sync = MainDataSync::new(api.inner().clone());
+    ///
+    /// let state = api.block_on(sync.update())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.get().block_on(future)
+    }
+
+    blocking_fns! {
+        fn logout() -> Result<(), Error>;
+        fn api_version() -> Result<ApiVersion, Error>;
+    }
+
+    /// ## Usage
+    /// blocking version of [`AsyncQbitApi::cached_api_version()`].
+    pub fn cached_api_version(&self) -> Option<ApiVersion> {
+        self.inner.cached_api_version()
+    }
+
+    #[cfg(feature = "torrents")]
+    blocking_fns! {
+        fn torrents_add_torrent(descriptor: impl Borrow<TorrentAddDescriptor>) -> Result<(), Error>;
+        fn torrents_get_hashes() -> Result<Vec<TorrentHash>, Error>;
+        fn torrents_get_torrent_list_typed(config: impl Borrow<TorrentListGetConfig>) -> Result<Vec<TorrentInfo>, Error>;
+        fn torrents_get_torrent_list(config: impl Borrow<TorrentListGetConfig>) -> Result<Value, Error>;
+        fn torrents_get_torrent_list_raw(config: impl Borrow<TorrentListGetConfig>) -> Result<String, Error>;
+        fn torrents_get_torrent_generic_properties_raw(hash: impl Borrow<TorrentHash>) -> Result<String, Error>;
+        fn torrents_get_torrent_generic_properties(hash: impl Borrow<TorrentHash>) -> Result<Value, Error>;
+        fn torrents_get_torrent_trackers_raw(hash: impl Borrow<TorrentHash>) -> Result<String, Error>;
+        fn torrents_get_torrent_trackers(hash: impl Borrow<TorrentHash>) -> Result<Value, Error>;
+        fn torrents_get_torrent_web_seeds_raw(hash: impl Borrow<TorrentHash>) -> Result<String, Error>;
+        fn torrents_get_torrent_web_seeds(hash: impl Borrow<TorrentHash>) -> Result<Value, Error>;
+        fn torrents_get_torrent_contents_raw(hash: impl Borrow<TorrentHash>, indexes: impl Borrow<Option<Vec<String>>>) -> Result<String, Error>;
+        fn torrents_get_torrent_contents(hash: impl Borrow<TorrentHash>, indexes: impl Borrow<Option<Vec<String>>>) -> Result<Value, Error>;
+        fn torrents_get_files_ids(hash: impl Borrow<TorrentHash>) -> Result<Vec<TorrentContent>, Error>;
+        fn torrents_get_torrent_pieces_states_raw(hash: impl Borrow<TorrentHash>) -> Result<String, Error>;
+        fn torrents_get_torrent_pieces_states(hash: impl Borrow<TorrentHash>) -> Result<Value, Error>;
+        fn torrents_get_torrent_pieces_states_vec(hash: impl Borrow<TorrentHash>) -> Result<Vec<u8>, Error>;
+        fn torrents_get_torrent_pieces_hashes_raw(hash: impl Borrow<TorrentHash>) -> Result<String, Error>;
+        fn torrents_get_torrent_pieces_hashes(hash: impl Borrow<TorrentHash>) -> Result<Value, Error>;
+        fn torrents_get_torrent_pieces_hashes_vec(hash: impl Borrow<TorrentHash>) -> Result<Vec<String>, Error>;
+        fn torrents_stop(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_start(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_pause_torrents(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_resume_torrents(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_delete_torrents(hashes: impl Borrow<TorrentHashesDesc>, delete_files: bool) -> Result<(), Error>;
+        fn torrents_recheck_torrents(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_reannounce_torrents(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_increase_priority_torrents(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_decrease_priority_torrents(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_set_top_priority_torrents(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_set_bottom_priority_torrents(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_add_trackers_to_torrent [U: Into<String> + Clone](hash: impl Borrow<TorrentHash>, trackers: impl Borrow<Vec<U>>) -> Result<(), Error>;
+        fn torrents_edit_trackers(hash: impl Borrow<TorrentHash>, orig_url: impl Into<String>, new_url: impl Into<String>) -> Result<(), Error>;
+        fn torrents_remove_trackers [S: Into<String> + Clone](hash: impl Borrow<TorrentHash>, urls: impl Borrow<Vec<S>>) -> Result<(), Error>;
+        fn torrents_add_peers [S: Into<String> + Clone](hashes: impl Borrow<Vec<TorrentHash>>, peers: impl Borrow<Vec<S>>) -> Result<(), Error>;
+        fn torrents_set_file_priority(hash: impl Borrow<TorrentHash>, ids: impl Borrow<Vec<TorrentContent>>) -> Result<(), Error>;
+        fn torrents_get_torrent_download_limit_raw(hashes: impl Borrow<TorrentHashesDesc>) -> Result<String, Error>;
+        fn torrents_get_torrent_download_limit(hash: impl Borrow<TorrentHashesDesc>) -> Result<Value, Error>;
+        fn torrents_set_torrent_download_limit(hashes: impl Borrow<TorrentHashesDesc>, limit: u64) -> Result<(), Error>;
+        fn torrents_set_torrent_share_limit(hashes: impl Borrow<TorrentHashesDesc>, ratio_limit: f32, seeding_time_limit: i32, inactive_seeding_time_limit: i32) -> Result<(), Error>;
+        fn torrents_get_torrent_upload_limit_raw(hashes: impl Borrow<TorrentHashesDesc>) -> Result<String, Error>;
+        fn torrents_get_torrent_upload_limit(hash: impl Borrow<TorrentHashesDesc>) -> Result<Value, Error>;
+        fn torrents_set_torrent_upload_limit(hashes: impl Borrow<TorrentHashesDesc>, limit: u64) -> Result<(), Error>;
+        fn torrents_set_torrent_download_location(hashes: impl Borrow<TorrentHashesDesc>, location: impl Into<String>) -> Result<(), Error>;
+        fn torrents_set_torrent_name(hash: impl Borrow<TorrentHash>, new_name: impl Into<String>) -> Result<(), Error>;
+        fn torrents_set_torrent_category(hash: impl Borrow<TorrentHashesDesc>, category_name: impl Into<String>) -> Result<(), Error>;
+        fn torrents_get_all_categories_raw() -> Result<String, Error>;
+        fn torrents_get_all_categories() -> Result<Value, Error>;
+        fn torrents_add_new_category(category_name: impl Into<String>, save_path: Option<impl Into<String>>) -> Result<(), Error>;
+        fn torrents_edit_category(category_name: impl Into<String>, save_path: Option<impl Into<String>>) -> Result<(), Error>;
+        fn torrents_remove_categories [S: Into<String> + Clone](categories_name: impl Borrow<Vec<S>>) -> Result<(), Error>;
+        fn torrents_get_all_tags_raw() -> Result<String, Error>;
+        fn torrents_get_all_tags() -> Result<Value, Error>;
+        fn torrents_add_new_tags [S: Into<String> + Clone](tags_name: impl Borrow<Vec<S>>) -> Result<(), Error>;
+        fn torrents_remove_tags [S: Into<String> + Clone](tags_name: impl Borrow<Vec<S>>) -> Result<(), Error>;
+        fn torrents_set_automatic_torrents_management(hashes: impl Borrow<TorrentHashesDesc>, enabled: bool) -> Result<(), Error>;
+        fn torrents_toggle_sequential_download(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_toggle_first_last_piece_priority(hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error>;
+        fn torrents_set_force_start(hashes: impl Borrow<TorrentHashesDesc>, enabled: bool) -> Result<(), Error>;
+        fn torrents_set_super_seeding(hashes: impl Borrow<TorrentHashesDesc>, enabled: bool) -> Result<(), Error>;
+        fn torrents_rename_file(hash: impl Borrow<TorrentHash>, old_path: impl Into<String>, new_path: impl Into<String>) -> Result<(), Error>;
+        fn torrents_rename_folder(hash: impl Borrow<TorrentHash>, old_path: impl Into<String>, new_path: impl Into<String>) -> Result<(), Error>;
+    }
+
+    #[cfg(feature = "app")]
+    blocking_fns! {
+        fn app_version() -> Result<String, Error>;
+        fn app_web_api_version() -> Result<String, Error>;
+        fn app_build_info_raw() -> Result<String, Error>;
+        fn app_build_info() -> Result<Value, Error>;
+        fn app_build_info_typed() -> Result<BuildInfo, Error>;
+        fn app_shutdown() -> Result<(), Error>;
+        fn app_get_default_save_path() -> Result<String, Error>;
+        fn app_get_preferences() -> Result<Value, Error>;
+        fn app_get_preferences_typed() -> Result<QBittorrentConfig, Error>;
+        fn get_preferences_raw() -> Result<String, Error>;
+        fn app_set_preferences(config: impl Borrow<QBittorrentConfig>) -> Result<(), Error>;
+    }
+
+    #[cfg(feature = "log")]
+    blocking_fns! {
+        fn log_get_log(config: impl Borrow<GetLogConfig>) -> Result<Value, Error>;
+        fn log_get_log_raw(config: impl Borrow<GetLogConfig>) -> Result<String, Error>;
+        fn log_get_peer_log(last_known_id: Option<i64>) -> Result<Value, Error>;
+        fn log_get_peer_log_raw(last_known_id: Option<i64>) -> Result<String, Error>;
+    }
+
+    #[cfg(feature = "sync")]
+    blocking_fns! {
+        fn sync_get_main_data_raw(rid: u64) -> Result<String, Error>;
+        fn sync_get_main_data(rid: u64) -> Result<Value, Error>;
+        fn sync_get_main_data_delta(rid: u64) -> Result<MainDataDelta, Error>;
+        fn sync_get_torrent_peers_data_raw(hash: impl AsRef<str>, rid: u64) -> Result<String, Error>;
+        fn sync_get_torrent_peers_data(hash: impl AsRef<str>, rid: u64) -> Result<Value, Error>;
+        fn sync_get_torrent_peers_delta(hash: impl Borrow<TorrentHash>, rid: u64) -> Result<TorrentPeersDelta, Error>;
+    }
+
+    #[cfg(feature = "transfer")]
+    blocking_fns! {
+        fn transfer_set_global_upload_limit(limit: u64) -> Result<(), Error>;
+        fn transfer_set_global_download_limit(limit: u64) -> Result<(), Error>;
+        fn transfer_get_alternative_speed_limits() -> Result<u8, Error>;
+        fn transfer_toggle_alternative_speed_limits() -> Result<(), Error>;
+        fn transfer_get_global_download_limit() -> Result<u64, Error>;
+        fn transfer_get_global_upload_limit() -> Result<u64, Error>;
+        fn transfer_ban_peers [S: Into<String>](peers: Vec<S>) -> Result<(), Error>;
+        fn transfer_get_global_transfer_info_raw() -> Result<String, Error>;
+        fn transfer_get_global_transfer_info() -> Result<Value, Error>;
+    }
+
+    #[cfg(feature = "rss")]
+    blocking_fns! {
+        fn rss_add_folder(path: impl Into<String>) -> Result<(), Error>;
+        fn rss_add_feed(url: impl Into<String>, path: Option<impl Into<String>>) -> Result<(), Error>;
+        fn rss_remove_item(path: impl Into<String>) -> Result<(), Error>;
+        fn rss_move_item(original_path: impl Into<String>, destination_path: impl Into<String>) -> Result<(), Error>;
+        fn rss_get_all_items_raw(with_data: Option<bool>) -> Result<String, Error>;
+        fn rss_get_all_items(with_data: Option<bool>) -> Result<Value, Error>;
+        fn rss_mark_as_read(item_path: impl Into<String>, article_id: Option<impl Into<String>>) -> Result<(), Error>;
+        fn rss_refresh_item(item_path: impl Into<String>) -> Result<(), Error>;
+        fn rss_set_auto_downloading_rule(rule_name: impl Into<String>, rule: impl Borrow<RssAutoDownloadRule>) -> Result<(), Error>;
+        fn rss_rename_auto_downloading_rule(original_name: impl Into<String>, new_name: impl Into<String>) -> Result<(), Error>;
+        fn rss_remove_auto_downloading_rule(rule_name: impl Into<String>) -> Result<(), Error>;
+        fn rss_get_all_auto_downloading_rules_raw() -> Result<String, Error>;
+        fn rss_get_all_auto_downloading_rules() -> Result<Value, Error>;
+        fn rss_get_all_articles_matching_a_rule_raw(rule_name: impl Into<String>) -> Result<String, Error>;
+        fn rss_get_all_articles_matching_a_rule(rule_name: impl Into<String>) -> Result<Value, Error>;
+    }
+
+    #[cfg(feature = "search")]
+    blocking_fns! {
+        fn search_get_search_plugins_raw() -> Result<String, Error>;
+        fn search_get_search_plugins_json() -> Result<Value, Error>;
+        fn search_get_search_plugins() -> Result<Vec<SearchPlugin>, Error>;
+        fn search_get_search_plugins_names() -> Result<Vec<String>, Error>;
+        fn search_start(pattern: impl Into<String>, plugins: impl Borrow<SearchPluginsSpec>, categories: impl Borrow<Categories>) -> Result<u64, Error>;
+        fn search_stop(id: u64) -> Result<(), Error>;
+        fn search_status_raw(id: Option<u64>) -> Result<String, Error>;
+        fn search_status(id: Option<u64>) -> Result<Value, Error>;
+        fn search_results_raw(id: u64, limit: Option<i64>, offset: Option<i64>) -> Result<String, Error>;
+        fn search_results(id: u64, limit: Option<i64>, offset: Option<i64>) -> Result<Value, Error>;
+        fn search_delete(id: u64) -> Result<(), Error>;
+        fn search_install_plugins [S: Into<String> + Clone](sources: impl Borrow<Vec<S>>) -> Result<(), Error>;
+        fn search_uninstall_plugins [S: Into<String> + Clone](names: impl Borrow<Vec<S>>) -> Result<(), Error>;
+        fn search_enable_plugins [S: Into<String> + Clone](names: impl Borrow<Vec<S>>, enable: bool) -> Result<(), Error>;
+        fn search_update_plugins() -> Result<(), Error>;
+    }
+}
+
+impl QbitApiBuilder {
+    /// ## Usage
+    /// blocking version of [`QbitApiBuilder::build()`]: returns the finalized blocking [`QbitApi`].
+    ///
+    /// ## Errors
+    /// the same as [`QbitApiBuilder::build()`], plus [`ErrorType::MiscError`] if the runtime couldn't be started.
+    pub fn build_blocking(self) -> Result<QbitApi, Error> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| Error::build(ErrorType::MiscError(format!("couldn't start the runtime: {}", e)), None))?;
+
+        let inner = runtime.block_on(self.build())?;
+
+        Ok(QbitApi { inner, runtime: Arc::new(BlockingRuntime(Some(runtime))) })
+    }
+}
@@ -1,15 +1,32 @@
-use std::{collections::HashMap, hash::Hash, sync::Arc};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 #[allow(unused_imports)]
 use serde_json::Value;
-use reqwest::{header::COOKIE, Client};
+use reqwest::{header::HeaderMap, Url};
 use serde::Serialize;
-use tokio::sync::{Mutex, RwLock};
+use async_lock::{OnceCell, RwLock};
 
-use crate::{code, core::cookie::Cookie, error_handling::error_type::ErrorType, post_request_no_return};
+use crate::{core::cookie::Cookie, error_handling::error_type::ErrorType, post_request_no_return};
 
-use super::creds::Credentials;
+use super::{
+    builder::QbitApiBuilder,
+    creds::AuthMode,
+    file_reader::FileReader,
+    instrument::{self, RequestSpan},
+    limiter::Limiter,
+    retry::RetryPolicy,
+    transport::{RequestBody, Transport, TransportRequest, TransportResponse},
+    version::ApiVersion,
+};
 use crate::error_handling::errors::Error;
 
+/// how long the endpoints that need a minimum version wait, after the version couldn't be detected, before trying again.
+const VERSION_DETECTION_BACKOFF: Duration = Duration::from_secs(60);
+
 ///## Description
 /// the main struct of the library.
 /// each API-related method is listed as its category, followed by its name: `category` + `name_of_the_method`, in snake case. (eg: `torrents_add_torrent`). 
@@ -21,121 +38,277 @@ use crate::error_handling::errors::Error;
 /// - if the method ends with `raw`, it means it'll return the raw json [`String`] from the response.
 /// - if the method doesn't have anything at its end, (or has `json` at the end, for cases where further clarity is needed), it'll return a serde_json [`Value`].
 /// - if it ends in any other way, it returns a custom type that represents that json (or parts of it) in a particular way.
+///
+/// ## Sharing
+/// every method takes `&self`, so a single [`QbitApi`] can be used by many tasks at once, either behind an [`Arc`] or by cloning it:
+/// clones share the same session cookie, and the same request limits.
 #[derive(Debug, Clone)]
 pub struct QbitApi {
-    pub(crate) authority: String,
+    /// url of the root of the API (eg: `https://host/qbit/api/v2/`), always ending with a `/`.
+    pub(crate) api_url: Url,
+    /// the `Referer` and `Origin` headers sent with every request, for qbittorrent's CSRF protection.
+    pub(crate) csrf_headers: HeaderMap,
     pub(crate) cookie: Arc<RwLock<Cookie>>,
-    pub(crate) reqwest_client: Client,
-    credentials: Credentials,
-    cookie_hold: Arc<Mutex<bool>>
+    pub(crate) transport: Arc<dyn Transport>,
+    pub(crate) auth: AuthMode,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) limiter: Arc<Limiter>,
+    #[cfg_attr(not(feature = "torrents"), allow(dead_code))]
+    pub(crate) file_reader: Arc<dyn FileReader>,
+    /// the version of the WebUI API, once it's been detected.
+    pub(crate) api_version: Arc<OnceCell<ApiVersion>>,
+    /// when detecting the version last failed, if it hasn't been detected since.
+    pub(crate) version_detection_failed_at: Arc<Mutex<Option<Instant>>>,
 }
 
 impl QbitApi {
     /// ## Usage
     /// 
-    /// creates a new instance of [`QbitApi`].
+    /// creates a new instance of [`QbitApi`], with a default http client.
+    /// alias `QbitApi::builder(authority, credentials).build()`.
     /// 
     /// ## Arguments
     /// 
-    /// authority: the authority for the Qbittorrent WebUI API. eg: `"http://localhost:6011/"`
-    /// credentials: the credentials to the account.
+    /// authority: the authority for the Qbittorrent WebUI API. eg: `"http://localhost:6011/"`.
+    /// if the WebUI is served from a subdirectory (eg: behind a reverse proxy), include it: eg: `"https://host/qbit/"`.
+    /// auth: how to authenticate: either the [`Credentials`](super::creds::Credentials) to the account, or any other [`AuthMode`].
     /// 
     /// ## Example
-    /// ```
+    /// ```no_run
+    /// # use qbittorrent_rust::core::{api::QbitApi, creds::Credentials};
+    /// # async fn example() {
     /// let qbit_api = QbitApi::new("http://localhost:6011/", Credentials::new("user_name", "password")).await.unwrap();
+    /// # }
     /// ```
-    pub async fn new(authority: impl AsRef<str>, credentials: Credentials) -> Result<Self, Error> {
-        let s = authority.as_ref();
-        let authority = Into::<String>::into(s) as String;
-        let authority =  authority.chars().rev().skip_while(|s| *s as u8 == 47).clone().map(|k| k.to_string()).collect::<Vec<String>>().into_iter().rev().collect::<String>();
-        //println!("{}", authority);
-        let reqwest_client = Client::new();
-        let cookie = Arc::new(RwLock::new(Cookie::new(&authority, &reqwest_client, &credentials).await?));
-        return Ok(QbitApi {
-            authority,
-            cookie,
-            reqwest_client,
-            credentials,
-            cookie_hold: Arc::new(Mutex::new(false))
-        })
+    pub async fn new(authority: impl AsRef<str>, auth: impl Into<AuthMode>) -> Result<Self, Error> {
+        Self::builder(authority, auth).build().await
+    }
+
+    /// ## Usage
+    /// returns a [`QbitApiBuilder`], the builder for [`QbitApi`], to configure the http client (timeouts, TLS, proxy, ...).
+    pub fn builder(authority: impl AsRef<str>, auth: impl Into<AuthMode>) -> QbitApiBuilder {
+        QbitApiBuilder::new(authority, auth)
+    }
+
+    /// ## Usage
+    /// returns the version of the WebUI API of the connected qbittorrent.
+    /// it's detected when connecting, and cached: if that failed, it's requested again now
+    /// (the endpoints that need a minimum version only try again once a minute).
+    ///
+    /// ## Errors
+    /// returns an [`Error`] if it wasn't detected yet and requesting `/app/webapiVersion` fails,
+    /// with error type [`ErrorType::UnexpectedResponse`] if qbittorrent's answer isn't a version.
+    pub async fn api_version(&self) -> Result<ApiVersion, Error> {
+        self.api_version.get_or_try_init(|| self.detect_api_version()).await.copied()
     }
 
-    pub(crate) async fn get_cookie(&mut self) -> Result<String, Error> {
-        let read_lock = self.cookie.read().await;
-
-        let res: String;
-
-        if read_lock.is_expired() {
-            let hold_cookie = self.cookie_hold.clone();
-            let mut hold_cookie = hold_cookie.lock().await;
-            if read_lock.is_expired() && *hold_cookie == false {
-                *hold_cookie = true;
-                drop(read_lock);
-                let cookie = self.cookie.clone();
-                let mut cookie = cookie.write().await;
-                cookie.reset(&self.authority, &self.reqwest_client, &self.credentials).await?;
-                res = (&*cookie.cookie).to_string();
-                drop(cookie);
-                *hold_cookie = false;
-            } else {
-                res = self.cookie.clone().read().await.cookie.clone();
+    /// ## Usage
+    /// returns the version of the WebUI API of the connected qbittorrent if it's been detected already, without doing any request.
+    pub fn cached_api_version(&self) -> Option<ApiVersion> {
+        self.api_version.get().copied()
+    }
+
+    async fn detect_api_version(&self) -> Result<ApiVersion, Error> {
+        let path = "/app/webapiVersion";
+        let response = self.send_authenticated(TransportRequest::post(&self.api_url, path)?).await.map_err(|e| e.with_endpoint(path))?;
+        let result = Self::read_response(path, response).and_then(|body| body.parse().map_err(|e: Error| e.with_endpoint(path).with_body(body)));
+
+        *self.version_detection_failed_at.lock().unwrap_or_else(|e| e.into_inner()) = result.is_err().then(Instant::now);
+        result
+    }
+
+    /// returns the version of the WebUI API, detecting it if it isn't known yet,
+    /// unless that already failed less than [`VERSION_DETECTION_BACKOFF`] ago.
+    async fn known_api_version(&self) -> Option<ApiVersion> {
+        let backing_off = || {
+            self.version_detection_failed_at
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .is_some_and(|failed_at| failed_at.elapsed() < VERSION_DETECTION_BACKOFF)
+        };
+
+        // checked again once the cell is ours, since another task may have just failed.
+        let detect = || async {
+            if backing_off() {
+                return Err(None);
             }
-            drop(hold_cookie);
-        } else {
-            drop(read_lock);
-            res = self.cookie.clone().read().await.cookie.clone();
+            self.detect_api_version().await.map_err(Some)
+        };
+
+        self.api_version.get_or_try_init(detect).await.ok().copied()
+    }
+
+    /// checks that `endpoint` is in the WebUI API of the connected qbittorrent (see [`ApiVersion::minimum_for()`]).
+    /// if the version can't be detected, the request is sent anyway, and it's up to qbittorrent.
+    pub(crate) async fn check_supported(&self, endpoint: &str) -> Result<(), Error> {
+        let Some(required) = ApiVersion::minimum_for(endpoint) else {
+            return Ok(());
+        };
+
+        self.require_api_version(required).await.map_err(|e| e.with_endpoint(endpoint))
+    }
+
+    /// fails with [`ErrorType::Unsupported`] if the connected qbittorrent's WebUI API is older than `required`,
+    /// or does nothing if the version can't be detected.
+    pub(crate) async fn require_api_version(&self, required: ApiVersion) -> Result<(), Error> {
+        match self.known_api_version().await {
+            Some(actual) => actual.require(required),
+            None => Ok(()),
         }
-        Ok(res)
     }
 
-    pub(crate) async fn make_request<T: Into<String>, S: Into<String>>(&mut self, url: T, custom_error: S) -> Result<String, crate::Error> {
-        let resp = self.reqwest_client.post(format!("{}/api/v2{}", self.authority, url.into()))
-            .header(COOKIE, format!("SID={}", self.get_cookie().await?))
-            .send().await.map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
+    /// returns whether the connected qbittorrent speaks the dialect of qbittorrent 5 ("stopped" instead of "paused").
+    /// if the version can't be detected, it's assumed to be older.
+    #[cfg_attr(not(feature = "torrents"), allow(dead_code))]
+    pub(crate) async fn is_qbittorrent_5(&self) -> bool {
+        self.known_api_version().await.is_some_and(|version| version >= ApiVersion::QBITTORRENT_5)
+    }
 
-        if resp.status().is_success() {
-            let text = resp.text().await.map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
+    /// returns the current session cookie, renewing it first if it's about to expire.
+    pub(crate) async fn get_cookie(&self) -> Result<Option<String>, Error> {
+        let cookie = self.cookie.read().await;
 
-            return Ok(text);
-        } else {
-            return Err(Error::build(ErrorType::MiscError(format!("something went wrong. function name: {}", custom_error.into())), Some(resp.status().as_u16())));
+        if !matches!(self.auth, AuthMode::Credentials(_)) || !cookie.is_expired() {
+            return Ok(cookie.cookie.clone());
         }
+
+        let stale = cookie.cookie.clone();
+        drop(cookie);
+        instrument::session_expired();
+        self.renew_cookie(&stale).await
     }
 
-    pub(crate) async fn make_request_with_form<T: std::cmp::Eq + Hash + Serialize, S: Serialize, U, N: Into<String>, X: Into<String>>(&mut self, url: N, custom_error: X, hashmap: HashMap<T, S, U>) -> Result<String, crate::Error> {
-        let response = self.reqwest_client.post(format!("{}/api/v2{}", self.authority, url.into()))
-                .header(reqwest::header::COOKIE, format!("SID={}", self.get_cookie().await?))
-                .form(&hashmap)
-                .send()
-                .await
-                .map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
+    /// logs in again, unless another task already replaced the `stale` cookie in the meantime.
+    pub(crate) async fn renew_cookie(&self, stale: &Option<String>) -> Result<Option<String>, Error> {
+        let mut cookie = self.cookie.write().await;
 
-            if response.status().is_success() {
-                let text = response.text().await.map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
-                Ok(text)
-            } else {
-                Err(Error::build(ErrorType::MiscError(format!("function name: {}", custom_error.into())), Some(response.status().as_u16())))
-            }
+        if &cookie.cookie == stale {
+            cookie.renew(&self.api_url, &self.csrf_headers, self.transport.as_ref(), &self.auth).await?;
+        }
+
+        Ok(cookie.cookie.clone())
+    }
+
+    /// sends `request`, retrying it according to the [`RetryPolicy`], inside its `qbittorrent.request` span.
+    pub(crate) async fn send_authenticated(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let span = RequestSpan::new(&request);
+        let (result, attempts) = span.instrument(self.send_with_retries(&request)).await;
+
+        span.finish(&result, attempts);
+        result
     }
 
-    pub(crate) async fn make_request_with_form_hash<T: std::cmp::Eq + Hash + Serialize, S: Serialize, U, N: Into<String>, X: Into<String>>(&mut self, url: N, custom_error: X, hashmap: HashMap<T, S, U>) -> Result<String, crate::Error> {
-        let response = self.reqwest_client.post(format!("{}/api/v2{}", self.authority, url.into()))
-                .header(reqwest::header::COOKIE, format!("SID={}", self.get_cookie().await?))
-                .form(&hashmap)
-                .send()
-                .await
-                .map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
-
-            // Handle the response
-            if response.status().is_success() {
-                let text = response.text().await.map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
-                Ok(text)
-            } else if response.status().as_u16() == 404 {
-                Err(Error::build(ErrorType::TorrentHashNotFound, code!(response)))
-            } else {
-                Err(Error::build(ErrorType::MiscError(format!("something went wrong. function name: {}", custom_error.into())), code!(response)))
+    /// sends `request`, retrying it according to the [`RetryPolicy`]; returns the last result, and how many attempts it took.
+    async fn send_with_retries(&self, request: &TransportRequest) -> (Result<TransportResponse, Error>, u32) {
+        let max_attempts = if self.retry_policy.is_retryable_endpoint(&request.path) { self.retry_policy.max_attempts() } else { 1 };
+        let mut attempt = 1;
+
+        loop {
+            let result = self.send_with_relogin(request).await;
+
+            let retry = match &result {
+                Ok(response) => self.retry_policy.retries_status(response.status),
+                Err(e) => self.retry_policy.retries_error(e),
+            };
+
+            if !retry || attempt >= max_attempts {
+                return (result, attempt);
             }
+
+            let backoff = self.retry_policy.backoff(attempt);
+            instrument::retry(attempt, backoff, &result);
+            futures_timer::Delay::new(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// sends `request`, authenticated with the session cookie (if there is one).
+    ///
+    /// if qbittorrent answers with `403 Forbidden`, the session is assumed to be gone (eg: it timed out, or qbittorrent restarted):
+    /// the client logs in again and retries the request once.
+    /// if the [`AuthMode`] can't provide a different session (eg: [`AuthMode::NoAuth`]), the `403 Forbidden` response is returned as is.
+    async fn send_with_relogin(&self, request: &TransportRequest) -> Result<TransportResponse, Error> {
+        let cookie = self.get_cookie().await?;
+        let response = self.send_with_session(request.clone(), &cookie).await?;
+
+        if response.status != 403 {
+            return Ok(response);
+        }
+
+        let renewed = self.renew_cookie(&cookie).await?;
+
+        if renewed == cookie {
+            return Ok(response);
+        }
+
+        instrument::relogin(&request.path);
+
+        self.send_with_session(request.clone(), &renewed).await
+    }
+
+    /// sends a single request through the [`Transport`], once the [`Limiter`] allows it.
+    /// the request counts as in flight until its whole response is read.
+    async fn send_with_session(&self, mut request: TransportRequest, cookie: &Option<String>) -> Result<TransportResponse, Error> {
+        request.headers.extend(self.csrf_headers.clone());
+
+        if let Some(cookie) = cookie {
+            request.cookies.push(("SID".to_string(), cookie.clone()));
+        }
+
+        let _permit = self.limiter.acquire().await;
+        self.transport.send(request).await
+    }
+
+    /// sends a `POST` request to `path` on behalf of the API method called `method`, and returns the body of the response.
+    pub(crate) async fn make_request<T: Into<String>>(&self, method: &'static str, path: T) -> Result<String, crate::Error> {
+        self.call(method, path.into(), RequestBody::Empty).await
+    }
+
+    /// like `make_request`, but with `hashmap` as the form of the request.
+    #[cfg_attr(not(any(feature = "torrents", feature = "app", feature = "transfer", feature = "rss", feature = "search")), allow(dead_code))]
+    pub(crate) async fn make_request_with_form<T: std::cmp::Eq + Hash + Serialize, S: Serialize, U, N: Into<String>>(&self, method: &'static str, path: N, hashmap: HashMap<T, S, U>) -> Result<String, crate::Error> {
+        let form = form_fields(&hashmap).map_err(|e| e.with_method(method))?;
+        self.call(method, path.into(), RequestBody::Form(form)).await
+    }
+
+    /// like `make_request_with_form`, but a `404 Not Found` is reported as [`ErrorType::TorrentHashNotFound`].
+    #[cfg_attr(not(feature = "torrents"), allow(dead_code))]
+    pub(crate) async fn make_request_with_form_hash<T: std::cmp::Eq + Hash + Serialize, S: Serialize, U, N: Into<String>>(&self, method: &'static str, path: N, hashmap: HashMap<T, S, U>) -> Result<String, crate::Error> {
+        self.make_request_with_form(method, path, hashmap).await.map_err(|e| match e.code {
+            Some(404) => e.retype(ErrorType::TorrentHashNotFound),
+            _ => e,
+        })
+    }
+
+    /// sends `body` to `path` inside the `qbittorrent.call` span of `method`; its errors carry both the method and the endpoint.
+    async fn call(&self, method: &'static str, path: String, body: RequestBody) -> Result<String, Error> {
+        let sent = async {
+            let request = TransportRequest::post(&self.api_url, &path)?.body(body);
+            self.check_supported(&request.path).await?;
+            let response = self.send_authenticated(request).await.map_err(|e| e.with_endpoint(&path))?;
+
+            Self::read_response(&path, response)
+        };
+
+        instrument::call(method, sent).await.map_err(|e| e.with_method(method))
+    }
+
+    /// returns the body of a successful response, or the matching [`Error`] for an unsuccessful one.
+    pub(crate) fn read_response(path: &str, response: TransportResponse) -> Result<String, Error> {
+        if !response.is_success() {
+            return Err(Error::from_response(path, response));
+        }
+
+        Ok(response.text())
     }
 
     post_request_no_return!(logout, "/auth/logout");
-}
\ No newline at end of file
+}
+
+/// turns the fields of a form into (name, value) pairs, the same way they're url-encoded.
+fn form_fields<T: Serialize>(form: &T) -> Result<Vec<(String, String)>, Error> {
+    let to_err = |e: &dyn std::fmt::Display| Error::build(ErrorType::MiscError(format!("couldn't encode the form: {}", e)), None);
+
+    let encoded = serde_urlencoded::to_string(form).map_err(|e| to_err(&e))?;
+    serde_urlencoded::from_str(&encoded).map_err(|e| to_err(&e))
+}
@@ -0,0 +1,273 @@
+use std::{sync::Arc, time::Duration};
+
+use proc_macros_qbittorrent_rust::Builder;
+use reqwest::{header::{HeaderMap, HeaderValue, ORIGIN, REFERER}, Client, Proxy, Url};
+use async_lock::{OnceCell, RwLock};
+
+use crate::{core::cookie::Cookie, error_handling::error_type::ErrorType, Error};
+
+use super::{
+    api::QbitApi,
+    creds::AuthMode,
+    file_reader::{self, FileReader},
+    fixtures::{RecordingTransport, ReplayTransport},
+    limiter::Limiter,
+    retry::RetryPolicy,
+    transport::{ReqwestTransport, Transport},
+    version::ApiVersion,
+};
+
+/// ## Info
+/// the builder struct for [`QbitApi`].
+/// create it with [`QbitApi::builder()`], set the options you need, then call `build()`.
+///
+/// ## Warning
+/// - if a preconfigured [`Client`] is set with `client`, every other http option (timeouts, user agent, headers, TLS and proxy) is ignored:
+///   configure them on the [`Client`] itself instead.
+/// - if a [`Transport`] is set with `transport`, the `client` and every other http option are ignored too.
+/// - if `replay_fixtures` is set, `transport` is ignored as well: nothing is sent over the network.
+///
+/// ## Fields
+/// | Field                  | Type        | Description                                                                 |
+/// |------------------------|-------------|-----------------------------------------------------------------------------|
+/// | `client`               | `Client`    | A preconfigured [`reqwest::Client`] to use for every request.               |
+/// | `timeout`              | `Duration`  | Timeout for each whole request, from connecting to reading the response.   |
+/// | `connect_timeout`      | `Duration`  | Timeout for the connection phase only.                                      |
+/// | `user_agent`           | `String`    | `User-Agent` header sent with every request.                                |
+/// | `default_headers`      | `HeaderMap` | Extra headers sent with every request.                                      |
+/// | `accept_invalid_certs` | `Bool`      | Accept invalid (eg: self-signed) TLS certificates. (default: false)         |
+/// | `ca_bundle`            | `Vec<u8>`   | PEM encoded bundle of extra trusted root certificates.                      |
+/// | `proxy`                | `String`    | Url of a proxy to send every request through (eg: `"http://proxy:8080"`).   |
+/// | `base_path`            | `String`    | Subdirectory the WebUI is served from, relative to the authority (eg: `"/qbit/"`). |
+/// | `referer`              | `String`    | `Referer` header sent with every request. (default: the url of the WebUI)   |
+/// | `origin`               | `String`    | `Origin` header sent with every request. (default: not sent)                |
+/// | `retry_policy`         | `RetryPolicy` | When and how failed requests are retried. (default: [`RetryPolicy::none()`]) |
+/// | `max_concurrent_requests` | `usize`  | Maximum number of requests sent at once; the others wait for their turn. (default: unlimited) |
+/// | `max_requests_per_second` | `f64`    | Maximum number of requests sent per second, spread evenly. (default: unlimited) |
+/// | `file_reader`          | `Arc<dyn FileReader>` | How .torrent files are read. (default: see [`FileReader`]) |
+/// | `transport`            | `Arc<dyn Transport>` | How the requests are sent. (default: a [`ReqwestTransport`] built with the options above) |
+/// | `record_fixtures`      | `String`    | Path of a fixture file to record every request and its response to, written when the api is dropped (see [`RecordingTransport`]). |
+/// | `replay_fixtures`      | `String`    | Path of a fixture file to answer the requests from, instead of sending them (see [`ReplayTransport`]). |
+/// | `api_version`          | `ApiVersion` | Version of the WebUI API to assume, instead of detecting it when connecting. |
+///
+/// ## Reverse proxies
+/// qbittorrent rejects requests whose `Referer` or `Origin` don't match the host it's reached at (CSRF protection).
+/// when it's behind a reverse proxy that rewrites the host, set `referer` and/or `origin` to what qbittorrent expects.
+///
+/// ## Example
+/// ```no_run
+/// # use std::time::Duration;
+/// # use qbittorrent_rust::core::{api::QbitApi, creds::Credentials};
+/// # async fn example() -> Result<(), qbittorrent_rust::Error> {
+/// let api = QbitApi::builder("https://qbit.internal/", Credentials::new("user_name", "password"))
+///     .timeout(Duration::from_secs(30))
+///     .ca_bundle(std::fs::read("internal-ca.pem").unwrap())
+///     .base_path("/qbit/")
+///     .origin("https://qbit.internal")
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+pub struct QbitApiBuilder {
+    #[builder(custom)]
+    authority: String,
+
+    #[builder(custom)]
+    auth: AuthMode,
+
+    client: Option<Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    default_headers: Option<HeaderMap>,
+    accept_invalid_certs: Option<bool>,
+    ca_bundle: Option<Vec<u8>>,
+    proxy: Option<String>,
+    base_path: Option<String>,
+    referer: Option<String>,
+    origin: Option<String>,
+    retry_policy: Option<RetryPolicy>,
+    max_concurrent_requests: Option<usize>,
+    max_requests_per_second: Option<f64>,
+    file_reader: Option<Arc<dyn FileReader>>,
+    transport: Option<Arc<dyn Transport>>,
+    record_fixtures: Option<String>,
+    replay_fixtures: Option<String>,
+    api_version: Option<ApiVersion>,
+}
+impl QbitApiBuilder {
+    /// ## Usage
+    /// creates a new instance of [`QbitApiBuilder`], with every option unset.
+    ///
+    /// ## Arguments
+    /// - authority: the authority for the Qbittorrent WebUI API. eg: `"http://localhost:6011/"`
+    /// - auth: how to authenticate: either the [`Credentials`](super::creds::Credentials) to the account, or any other [`AuthMode`].
+    pub fn new(authority: impl AsRef<str>, auth: impl Into<AuthMode>) -> Self {
+        Self {
+            authority: authority.as_ref().to_string(),
+            auth: auth.into(),
+            client: None,
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+            default_headers: None,
+            accept_invalid_certs: None,
+            ca_bundle: None,
+            proxy: None,
+            base_path: None,
+            referer: None,
+            origin: None,
+            retry_policy: None,
+            max_concurrent_requests: None,
+            max_requests_per_second: None,
+            file_reader: None,
+            transport: None,
+            record_fixtures: None,
+            replay_fixtures: None,
+            api_version: None,
+        }
+    }
+
+    /// ## Usage
+    /// builds the http client, logs in (if the [`AuthMode`] requires it), detects the version of the WebUI API (unless `api_version` is set)
+    /// and returns the finalized [`QbitApi`].
+    ///
+    /// ## Errors
+    /// - returns an [`Error`] with error type [`ErrorType::UrlParseError`] if the authority or the `base_path` are malformed.
+    /// - returns an [`Error`] with error type [`ErrorType::ParameterNotExpected`] if the `referer` or the `origin` aren't valid header values,
+    ///   if `max_concurrent_requests` or `max_requests_per_second` aren't greater than 0,
+    ///   or if `accept_invalid_certs` or `ca_bundle` are set without the `native-tls` or `rustls` feature.
+    /// - returns an [`Error`] with error type [`ErrorType::ReqwestError`] if the `ca_bundle` or the `proxy` are malformed, or if the http client couldn't be built.
+    /// - returns an [`Error`] with error type [`ErrorType::MiscError`] or [`ErrorType::JsonSerdeError`] if the `replay_fixtures` file can't be read or isn't a fixture file.
+    /// - returns an [`Error`] if logging in fails.
+    pub async fn build(self) -> Result<QbitApi, Error> {
+        if self.max_concurrent_requests == Some(0) || self.max_requests_per_second.is_some_and(|rps| !(rps > 0.0 && rps.is_finite())) {
+            return Err(Error::build(ErrorType::ParameterNotExpected, None));
+        }
+
+        let base_url = webui_url(&self.authority, self.base_path.as_deref())?;
+        let api_url = base_url.join("api/v2/").map_err(|e| Error::build(ErrorType::UrlParseError(Box::new(e)), None))?;
+
+        let mut csrf_headers = HeaderMap::new();
+        csrf_headers.insert(REFERER, header_value(self.referer.as_deref().unwrap_or(base_url.as_str()))?);
+
+        if let Some(origin) = &self.origin {
+            csrf_headers.insert(ORIGIN, header_value(origin)?);
+        }
+
+        let file_reader = self.file_reader.clone().unwrap_or_else(file_reader::default_reader);
+
+        let mut transport: Arc<dyn Transport> = match (&self.replay_fixtures, self.transport.clone()) {
+            (Some(path), _) => {
+                let json = file_reader
+                    .read(path)
+                    .await
+                    .map_err(|e| Error::build(ErrorType::MiscError(format!("couldn't read the fixtures at {}: {}", path, e)), None))?;
+
+                Arc::new(ReplayTransport::from_json(String::from_utf8_lossy(&json))?)
+            }
+            (None, Some(transport)) => transport,
+            (None, None) => Arc::new(ReqwestTransport::new(self.reqwest_client()?)),
+        };
+
+        if let Some(path) = &self.record_fixtures {
+            transport = Arc::new(RecordingTransport::new(transport, Some(path)));
+        }
+
+        let cookie = Arc::new(RwLock::new(Cookie::new(&api_url, &csrf_headers, transport.as_ref(), &self.auth).await?));
+
+        let api = QbitApi {
+            api_url,
+            csrf_headers,
+            cookie,
+            transport,
+            auth: self.auth,
+            retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+            limiter: Arc::new(Limiter::new(self.max_concurrent_requests, self.max_requests_per_second)),
+            file_reader,
+            api_version: Arc::new(self.api_version.map(OnceCell::from).unwrap_or_default()),
+            version_detection_failed_at: Arc::default(),
+        };
+
+        // if it fails, it's detected again later, when it's needed (see `QbitApi::api_version()`).
+        let _ = api.api_version().await;
+
+        Ok(api)
+    }
+
+    /// returns the [`Client`] set with `client`, or builds one with the http options.
+    fn reqwest_client(&self) -> Result<Client, Error> {
+        if let Some(client) = &self.client {
+            return Ok(client.clone());
+        }
+
+        let mut builder = Client::builder();
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        if let Some(headers) = &self.default_headers {
+            builder = builder.default_headers(headers.clone());
+        }
+
+        #[cfg(any(feature = "native-tls", feature = "rustls"))]
+        {
+            if let Some(accept_invalid_certs) = self.accept_invalid_certs {
+                builder = builder.danger_accept_invalid_certs(accept_invalid_certs);
+            }
+
+            if let Some(ca_bundle) = &self.ca_bundle {
+                let certificates = reqwest::Certificate::from_pem_bundle(ca_bundle)
+                    .map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
+
+                for certificate in certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+            }
+        }
+
+        // without a TLS backend there are no certificates to configure.
+        #[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+        if self.accept_invalid_certs.is_some() || self.ca_bundle.is_some() {
+            return Err(Error::build(ErrorType::ParameterNotExpected, None));
+        }
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = Proxy::all(proxy).map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
+            builder = builder.proxy(proxy);
+        }
+
+        builder.build().map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))
+    }
+}
+
+/// parses the authority and appends the base path to it, making sure the result ends with a `/` so that it can be joined with relative paths.
+fn webui_url(authority: &str, base_path: Option<&str>) -> Result<Url, Error> {
+    let to_err = |e| Error::build(ErrorType::UrlParseError(Box::new(e)), None);
+
+    let mut url = Url::parse(authority).map_err(to_err)?;
+
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+
+    match base_path.map(|path| path.trim_matches('/')) {
+        Some(path) if !path.is_empty() => url.join(&format!("{}/", path)).map_err(to_err),
+        _ => Ok(url),
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, Error> {
+    HeaderValue::from_str(value).map_err(|_| Error::build(ErrorType::ParameterNotExpected, None))
+}
@@ -1,22 +1,25 @@
 use std::time::Instant;
 
-use reqwest::Client;
+use reqwest::{header::HeaderMap, Url};
 
-use super::creds::Credentials;
-use crate::code;
+use super::{
+    creds::{AuthMode, Credentials},
+    transport::{RequestBody, Transport, TransportRequest},
+};
 use crate::error_handling::errors::Error;
 use crate::error_handling::error_type::ErrorType;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub(crate) struct Cookie {
-    pub(crate) cookie: String,
+    /// the session id, `None` if no session cookie should be sent.
+    pub(crate) cookie: Option<String>,
     time_of_creation: Instant,
 } impl Cookie {
     /// makes a new instance of `Cookie`.
-    pub(crate) async fn new(authority: &String, reqwest_client: &Client, credentials: &Credentials) -> Result<Self, Error> {
+    pub(crate) async fn new(api_url: &Url, headers: &HeaderMap, transport: &dyn Transport, auth: &AuthMode) -> Result<Self, Error> {
         let now = Instant::now();
         
-        let cookie = Self::request_raw_cookie(authority, reqwest_client, credentials).await?;
+        let cookie = auth.session_id(api_url, headers, transport).await?;
 
         Ok(Self {
             cookie,
@@ -24,57 +27,65 @@ pub(crate) struct Cookie {
         })
     }
 
-    pub(crate) async fn request_raw_cookie(authority: &String, reqwest_client: &Client, credentials: &Credentials) -> Result<String, Error> {
-        let response = reqwest_client
-            .post(format!("{}/api/v2/auth/login", authority))
-            .header(reqwest::header::REFERER, authority)
-            .form(&[("username", credentials.username.clone()), ("password", credentials.password.clone())])
-            .send()
-            .await
-            .map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
+    /// logs in, sending `headers` (the `Referer`/`Origin` headers) along with the credentials, and returns the raw session cookie.
+    ///
+    /// ## Errors
+    /// - [`ErrorType::HostUnreachable`] if qbittorrent couldn't be reached at all (eg: wrong authority, connection refused, timeout).
+    /// - [`ErrorType::WrongCreds`] if qbittorrent rejected the username or the password (it answers `"Fails."`).
+    /// - [`ErrorType::TooManyFailedAttempts`] if the ip is banned because of too many failed login attempts (it answers `403 Forbidden`).
+    /// - [`ErrorType::MissingSessionCookie`] if the login succeeded, but no `SID` cookie was sent back.
+    pub(crate) async fn request_raw_cookie(api_url: &Url, headers: &HeaderMap, transport: &dyn Transport, credentials: &Credentials) -> Result<String, Error> {
+        let mut request = TransportRequest::post(api_url, "/auth/login")?.body(RequestBody::Form(vec![
+            ("username".to_string(), credentials.username.clone()),
+            ("password".to_string(), credentials.password.clone()),
+        ]));
+        request.headers = headers.clone();
 
-        let status = response.status();
+        let response = transport.send(request).await.map_err(|e| e.with_endpoint("/auth/login"))?;
+        let status = response.status;
 
-        if status.is_success() {
-            match response.headers().get("set-cookie").and_then(|s| s.to_str().ok()) {
-                Some(cookie) => {
-                    println!("{}", cookie);
-                    return Ok(cookie.to_string().split("=").collect::<Vec<&str>>()[1].split(";").collect::<Vec<&str>>()[0].to_string());
-                },
+        if status == 403 {
+            return Err(Error::build(ErrorType::TooManyFailedAttempts, Some(403_u16)).with_endpoint("/auth/login"));
+        } else if !response.is_success() {
+            return Err(Error::from_response("/auth/login", response));
+        }
+
+        let sid = response.cookie("SID");
+        let body = response.text();
 
-                None => {
-                    return Err(Error::build(ErrorType::WrongCreds, None))
-                }
-            }
-        } else if status.as_u16() == 403 {
-            return Err(Error::build(ErrorType::TooManyFailedAttempts, Some(403_u16)));
-        } else {
-            return Err(Error::build(ErrorType::MiscError("Something went wrong while getting the auth cookie.".to_string()), code!(response)))
+        match sid {
+            _ if body.trim() == "Fails." => Err(Error::build(ErrorType::WrongCreds, Some(status)).with_endpoint("/auth/login")),
+            Some(sid) if !sid.is_empty() => Ok(sid),
+            _ => Err(Error::build(ErrorType::MissingSessionCookie, Some(status)).with_endpoint("/auth/login").with_body(body)),
         }
     }
     
     /// checks if the `Cookie` expired.
     /// 
     /// # FUNCTIONING
-    /// returns `true` when the cookie is expired.
+    /// returns `true` when the cookie is older than 55 minutes, which is qbittorrent's default session timeout minus 5 minutes of margin.
     /// 
     /// # WARNING
-    /// - this method relies on the elapsed seconds from the time of creation. make sure to something that messes with that.
-    /// - this will check that there is at least 1 minute of margin from the official expiration, to ensure any operation after this methis is called is still possible.
+    /// - this is only an optimization to renew the cookie before it's rejected: the session timeout is configurable, and sessions are dropped when qbittorrent restarts.
+    ///   a cookie that was rejected anyway is detected by [`crate::core::api::QbitApi`], which logs in again and retries the request.
     pub(crate) fn is_expired(&self) -> bool {
-        if self.time_of_creation.elapsed().as_secs() >= 3300 {
-            true
-        } else {
-            false
-        }
+        self.time_of_creation.elapsed().as_secs() >= 3300
     }
 
-    /// checks if the `Cookie` is expired, and if it is, requests a new one.
-    pub(crate) async fn reset(&mut self, authority: &String, reqwest_client: &Client, credentials: &Credentials) -> Result<(), Error> {
-        if self.is_expired() {
-            self.cookie = Self::request_raw_cookie(authority, reqwest_client, credentials).await?;
-        } 
+    /// requests a new cookie, replacing the current one.
+    pub(crate) async fn renew(&mut self, api_url: &Url, headers: &HeaderMap, transport: &dyn Transport, auth: &AuthMode) -> Result<(), Error> {
+        self.cookie = auth.session_id(api_url, headers, transport).await?;
+        self.time_of_creation = Instant::now();
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl std::fmt::Debug for Cookie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cookie")
+            .field("cookie", &self.cookie.as_ref().map(|_| "<redacted>"))
+            .field("time_of_creation", &self.time_of_creation)
+            .finish()
+    }
+}
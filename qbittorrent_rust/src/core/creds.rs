@@ -1,6 +1,18 @@
+use std::{fmt::Debug, sync::Arc};
+
+use futures_util::future::BoxFuture;
+use reqwest::{header::HeaderMap, Url};
+
+use crate::{
+    core::{cookie::Cookie, transport::Transport},
+    Error,
+};
+
 /// ## Description
 /// represents credentials to an account.
-#[derive(Debug, Clone)]
+///
+/// its [`Debug`] output never contains the password, so it's safe to log.
+#[derive(Clone)]
 pub struct Credentials {
     pub(crate) username: String,
     pub(crate) password: String,
@@ -17,4 +29,82 @@ impl Credentials {
             password: passwd
         }
     }
-}
\ No newline at end of file
+}
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// ## Info
+/// a source of session ids, for setups where the session is obtained some other way than logging in with a username and a password
+/// (eg: from a secrets store, or from an authenticating proxy).
+///
+/// `session_id` is called once when the [`crate::core::api::QbitApi`] is built, then again every time qbittorrent rejects the current session id.
+///
+/// ## Example
+/// ```no_run
+/// # use futures_util::future::BoxFuture;
+/// # use qbittorrent_rust::{core::creds::AuthProvider, Error};
+/// struct FromEnv;
+///
+/// impl AuthProvider for FromEnv {
+///     fn session_id(&self) -> BoxFuture<'_, Result<String, Error>> {
+///         Box::pin(async { Ok(std::env::var("QBIT_SID").unwrap_or_default()) })
+///     }
+/// }
+/// ```
+pub trait AuthProvider: Send + Sync {
+    /// ## Usage
+    /// returns the session id (the value of the `SID` cookie) to authenticate the requests with.
+    fn session_id(&self) -> BoxFuture<'_, Result<String, Error>>;
+}
+
+/// ## Description
+/// describes how the client authenticates to qbittorrent.
+///
+/// [`Credentials`] convert into [`AuthMode::Credentials`], so they can be passed wherever an [`AuthMode`] is expected.
+/// like [`Credentials`], its [`Debug`] output never contains secrets.
+#[derive(Clone)]
+pub enum AuthMode {
+    /// log in with a username and a password; the session is renewed automatically when it expires.
+    Credentials(Credentials),
+    /// don't authenticate at all. for qbittorrent instances with authentication bypass enabled for localhost or for whitelisted subnets.
+    NoAuth,
+    /// use an already existing session id (the value of the `SID` cookie). it can't be renewed: once qbittorrent drops the session, requests fail.
+    PreexistingSid(String),
+    /// ask an [`AuthProvider`] for the session id, and again whenever it's rejected.
+    Provider(Arc<dyn AuthProvider>),
+}
+
+impl AuthMode {
+    /// returns the session id to authenticate with, logging in if needed. `None` means no session cookie is sent.
+    pub(crate) async fn session_id(&self, api_url: &Url, headers: &HeaderMap, transport: &dyn Transport) -> Result<Option<String>, Error> {
+        match self {
+            AuthMode::Credentials(credentials) => Cookie::request_raw_cookie(api_url, headers, transport, credentials).await.map(Some),
+            AuthMode::NoAuth => Ok(None),
+            AuthMode::PreexistingSid(sid) => Ok(Some(sid.clone())),
+            AuthMode::Provider(provider) => provider.session_id().await.map(Some),
+        }
+    }
+}
+
+impl From<Credentials> for AuthMode {
+    fn from(credentials: Credentials) -> Self {
+        AuthMode::Credentials(credentials)
+    }
+}
+
+impl Debug for AuthMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthMode::Credentials(credentials) => f.debug_tuple("Credentials").field(credentials).finish(),
+            AuthMode::NoAuth => write!(f, "NoAuth"),
+            AuthMode::PreexistingSid(_) => write!(f, "PreexistingSid(<redacted>)"),
+            AuthMode::Provider(_) => write!(f, "Provider(..)"),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt::Debug;
+
+use futures_util::future::BoxFuture;
+
+/// ## Info
+/// reads the .torrent files added with [`crate::TorrentType::TorrentFile`], so that the library doesn't depend on the file system api of a specific async runtime.
+///
+/// by default, [`TokioFileReader`] is used if the `tokio` feature is enabled, and [`StdFileReader`] otherwise;
+/// set a different one with [`crate::core::builder::QbitApiBuilder::file_reader()`] (eg: to use `async-std`'s file system api, or to read from somewhere else).
+///
+/// ## Example
+/// ```no_run
+/// # use futures_util::future::BoxFuture;
+/// # use qbittorrent_rust::core::file_reader::FileReader;
+/// struct InMemory(std::collections::HashMap<String, Vec<u8>>);
+///
+/// impl FileReader for InMemory {
+///     fn read<'a>(&'a self, path: &'a str) -> BoxFuture<'a, std::io::Result<Vec<u8>>> {
+///         Box::pin(async move { self.0.get(path).cloned().ok_or_else(|| std::io::ErrorKind::NotFound.into()) })
+///     }
+/// }
+/// ```
+pub trait FileReader: Send + Sync {
+    /// ## Usage
+    /// returns the whole content of the file at `path`.
+    fn read<'a>(&'a self, path: &'a str) -> BoxFuture<'a, std::io::Result<Vec<u8>>>;
+}
+
+impl Debug for dyn FileReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FileReader")
+    }
+}
+
+/// ## Info
+/// a [`FileReader`] that uses [`std::fs::read`].
+///
+/// it blocks the executor while reading; that's usually fine for .torrent files, which are small.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdFileReader;
+
+impl FileReader for StdFileReader {
+    fn read<'a>(&'a self, path: &'a str) -> BoxFuture<'a, std::io::Result<Vec<u8>>> {
+        Box::pin(async move { std::fs::read(path) })
+    }
+}
+
+/// ## Info
+/// a [`FileReader`] that uses [`tokio::fs::read`]. requires the `tokio` feature, and a tokio runtime.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioFileReader;
+
+#[cfg(feature = "tokio")]
+impl FileReader for TokioFileReader {
+    fn read<'a>(&'a self, path: &'a str) -> BoxFuture<'a, std::io::Result<Vec<u8>>> {
+        Box::pin(tokio::fs::read(path))
+    }
+}
+
+/// returns the [`FileReader`] used when none is set.
+pub(crate) fn default_reader() -> std::sync::Arc<dyn FileReader> {
+    #[cfg(feature = "tokio")]
+    return std::sync::Arc::new(TokioFileReader);
+
+    #[cfg(not(feature = "tokio"))]
+    return std::sync::Arc::new(StdFileReader);
+}
@@ -0,0 +1,311 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use futures_util::future::BoxFuture;
+use reqwest::header::{HeaderName, HeaderValue, SET_COOKIE};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{error_handling::error_type::ErrorType, Error};
+
+use super::instrument;
+use super::transport::{MultipartValue, RequestBody, Transport, TransportRequest, TransportResponse};
+
+/// what secrets are replaced with in the fixtures.
+const REDACTED: &str = "<redacted>";
+
+/// ## Info
+/// a request and the response qbittorrent gave to it, as stored in a fixture file.
+///
+/// fixture files are json arrays of [`Fixture`]s: record them with [`RecordingTransport`] and serve them back with [`ReplayTransport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub request: FixtureRequest,
+    pub response: FixtureResponse,
+}
+
+/// ## Info
+/// the part of a [`TransportRequest`] that identifies it: two requests with the same [`FixtureRequest`] get the same recorded responses.
+///
+/// passwords are redacted, session cookies and the other headers are left out, and the parameters are sorted, so that recording twice gives the same fixture.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixtureRequest {
+    /// the http method (eg: `"POST"`).
+    pub method: String,
+    /// the endpoint, relative to the root of the API (eg: `"/torrents/info"`).
+    pub path: String,
+    /// every parameter (query string, form and multipart text fields), sorted.
+    #[serde(default)]
+    pub params: Vec<(String, String)>,
+    /// the names of the uploaded files; their contents aren't stored.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+}
+impl FixtureRequest {
+    /// ## Usage
+    /// returns the [`FixtureRequest`] that identifies `request`.
+    pub fn from_request(request: &TransportRequest) -> Self {
+        let mut params = request.query.clone();
+        let mut files = vec![];
+
+        match &request.body {
+            RequestBody::Empty => {}
+            RequestBody::Form(fields) => params.extend(fields.iter().cloned()),
+            RequestBody::Multipart(fields) => {
+                for field in fields {
+                    match &field.value {
+                        MultipartValue::Text(text) => params.push((field.name.clone(), text.clone())),
+                        MultipartValue::File { file_name, .. } => files.push(file_name.clone()),
+                    }
+                }
+            }
+        }
+
+        let mut params: Vec<(String, String)> = params.into_iter().map(|(name, value)| (name.clone(), redact_param(&name, value))).collect();
+        params.sort();
+        files.sort();
+
+        Self { method: request.method.to_string(), path: request.path.clone(), params, files }
+    }
+}
+
+/// ## Info
+/// a response, as stored in a fixture file.
+///
+/// the body is stored as text; passwords in json bodies and session cookies are redacted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixtureResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub body: String,
+}
+impl FixtureResponse {
+    /// ## Usage
+    /// returns the [`FixtureResponse`] to store for `response`.
+    pub fn from_response(response: &TransportResponse) -> Self {
+        let headers = response
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let value = value.to_str().ok()?;
+
+                // keep the cookie, so that logging in still works when replaying, but not the session id.
+                let value = match (name == SET_COOKIE, value.split_once(';')) {
+                    (true, Some((pair, rest))) => format!("{}={};{}", pair.split('=').next().unwrap_or_default(), REDACTED, rest),
+                    (true, None) => format!("{}={}", value.split('=').next().unwrap_or_default(), REDACTED),
+                    (false, _) => value.to_string(),
+                };
+
+                Some((name.to_string(), value))
+            })
+            .collect();
+
+        Self { status: response.status, headers, body: redact_json_text(response.text()) }
+    }
+
+    /// ## Usage
+    /// turns the fixture back into a [`TransportResponse`]. headers that aren't valid anymore are skipped.
+    pub fn to_response(&self) -> TransportResponse {
+        let mut response = TransportResponse::new(self.status, self.body.clone());
+
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (HeaderName::try_from(name.as_str()), HeaderValue::from_str(value)) {
+                response.headers.append(name, value);
+            }
+        }
+
+        response
+    }
+}
+
+/// passwords in the parameters, and in the json ones (eg: the preferences sent to `/app/setPreferences`).
+fn redact_param(name: &str, value: String) -> String {
+    if name.contains("password") {
+        return REDACTED.to_string();
+    }
+
+    redact_json_text(value)
+}
+
+/// redacts the passwords of a json object or array, leaving any other text (and json without passwords) untouched.
+fn redact_json_text(text: String) -> String {
+    let Ok(mut json) = serde_json::from_str::<Value>(&text) else {
+        return text;
+    };
+
+    match redact_json(&mut json) {
+        true => json.to_string(),
+        false => text,
+    }
+}
+
+/// replaces the value of every key containing `password` (eg: `proxy_password` in the preferences), returning whether there was any.
+fn redact_json(value: &mut Value) -> bool {
+    let mut redacted = false;
+
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if key.contains("password") && value.is_string() {
+                    *value = Value::String(REDACTED.to_string());
+                    redacted = true;
+                } else {
+                    redacted |= redact_json(value);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                redacted |= redact_json(value);
+            }
+        }
+        _ => {}
+    }
+
+    redacted
+}
+
+/// ## Info
+/// a [`Transport`] that sends the requests with another transport, and records each request and its response as a [`Fixture`].
+///
+/// the fixtures are kept in memory; if it has a path, they're written to it by [`RecordingTransport::save()`],
+/// and when the transport is dropped (with the last clone of the [`crate::core::api::QbitApi`] using it).
+/// a failure to write them when it's dropped can't be returned: it's only logged (with the `tracing` feature).
+///
+/// usually set up with [`crate::core::builder::QbitApiBuilder::record_fixtures()`].
+///
+/// ## Warning
+/// passwords and session ids are redacted, but everything else (torrent names, paths, trackers...) ends up in the fixture as is.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    path: Option<String>,
+    fixtures: Mutex<Vec<Fixture>>,
+    /// how many of the fixtures are already in the file.
+    saved: AtomicUsize,
+}
+impl RecordingTransport {
+    /// ## Usage
+    /// creates a new [`RecordingTransport`] sending the requests with `inner`, and writing the fixtures to `path` if it's set.
+    pub fn new(inner: Arc<dyn Transport>, path: Option<impl Into<String>>) -> Self {
+        Self { inner, path: path.map(Into::into), fixtures: Mutex::new(vec![]), saved: AtomicUsize::new(0) }
+    }
+
+    /// ## Usage
+    /// returns the fixtures recorded so far.
+    pub fn fixtures(&self) -> Vec<Fixture> {
+        self.fixtures.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// ## Usage
+    /// writes the fixtures recorded so far to the path of the transport, if it has one.
+    /// the file is written with [`std::fs::write`], which blocks until it's done.
+    ///
+    /// ## Errors
+    /// returns an [`Error`] with error type [`ErrorType::MiscError`] if the file can't be written.
+    pub fn save(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let fixtures = self.fixtures();
+        let json = serde_json::to_string_pretty(&fixtures).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?;
+        std::fs::write(path, json).map_err(|e| Error::build(ErrorType::MiscError(format!("couldn't write the fixtures to {}: {}", path, e)), None))?;
+
+        self.saved.store(fixtures.len(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn record(&self, fixture: Fixture) {
+        self.fixtures.lock().unwrap_or_else(|e| e.into_inner()).push(fixture);
+    }
+}
+
+impl Drop for RecordingTransport {
+    fn drop(&mut self) {
+        let recorded = self.fixtures.get_mut().unwrap_or_else(|e| e.into_inner()).len();
+
+        if recorded > *self.saved.get_mut() {
+            if let Err(e) = self.save() {
+                instrument::fixtures_not_saved(&e);
+            }
+        }
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            let fixture_request = FixtureRequest::from_request(&request);
+            let response = self.inner.send(request).await?;
+
+            self.record(Fixture { request: fixture_request, response: FixtureResponse::from_response(&response) });
+
+            Ok(response)
+        })
+    }
+}
+
+/// ## Info
+/// a [`Transport`] that answers the requests with recorded [`Fixture`]s, without any network.
+///
+/// the responses recorded for the same [`FixtureRequest`] are served in the order they were recorded; once only one is left, it's served every time.
+/// a request that wasn't recorded fails with [`ErrorType::MiscError`].
+///
+/// usually set up with [`crate::core::builder::QbitApiBuilder::replay_fixtures()`].
+#[derive(Debug)]
+pub struct ReplayTransport {
+    responses: Mutex<HashMap<FixtureRequest, VecDeque<FixtureResponse>>>,
+}
+impl ReplayTransport {
+    /// ## Usage
+    /// creates a new [`ReplayTransport`] serving `fixtures`.
+    pub fn new(fixtures: impl IntoIterator<Item = Fixture>) -> Self {
+        let mut responses: HashMap<FixtureRequest, VecDeque<FixtureResponse>> = HashMap::new();
+
+        for fixture in fixtures {
+            responses.entry(fixture.request).or_default().push_back(fixture.response);
+        }
+
+        Self { responses: Mutex::new(responses) }
+    }
+
+    /// ## Usage
+    /// creates a new [`ReplayTransport`] serving the fixtures of a fixture file.
+    ///
+    /// ## Errors
+    /// returns an [`Error`] with error type [`ErrorType::JsonSerdeError`] if `json` isn't a list of [`Fixture`]s.
+    pub fn from_json(json: impl AsRef<str>) -> Result<Self, Error> {
+        let fixtures: Vec<Fixture> = serde_json::from_str(json.as_ref()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?;
+        Ok(Self::new(fixtures))
+    }
+
+    fn answer(&self, request: &TransportRequest) -> Result<TransportResponse, Error> {
+        let key = FixtureRequest::from_request(request);
+        let mut responses = self.responses.lock().unwrap_or_else(|e| e.into_inner());
+
+        let response = match responses.get_mut(&key) {
+            Some(queue) if queue.len() > 1 => queue.pop_front(),
+            Some(queue) => queue.front().cloned(),
+            None => None,
+        };
+
+        response.map(|response| response.to_response()).ok_or_else(|| {
+            Error::build(ErrorType::MiscError(format!("no recorded response for {} {}.", key.method, key.path)), None)
+                .with_endpoint(&key.path)
+        })
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move { self.answer(&request) })
+    }
+}
@@ -0,0 +1,119 @@
+//! the `tracing` instrumentation of the requests: without the `tracing` feature, everything here compiles down to nothing.
+//!
+//! - every API method that sends a request runs inside a `qbittorrent.call` span, with the name of the method in `method`;
+//! - every request runs inside a `qbittorrent.request` span, with the `endpoint` and the http method when it starts,
+//!   and the `status`, `latency_ms`, `bytes` and `retries` (or the `error`) once it's done;
+//! - logging in again (after a `403 Forbidden`, or because the session expired), and retrying a request, emit an event inside the request span;
+//! - failing to write the recorded fixtures when the [`super::fixtures::RecordingTransport`] is dropped emits an error event.
+
+use std::{future::Future, time::Duration};
+
+use super::transport::{TransportRequest, TransportResponse};
+use crate::Error;
+
+/// runs `future`, the body of the API method called `method`, inside a `qbittorrent.call` span.
+#[cfg(feature = "tracing")]
+pub(crate) fn call<F: Future>(method: &'static str, future: F) -> tracing::instrument::Instrumented<F> {
+    use tracing::Instrument;
+
+    future.instrument(tracing::info_span!("qbittorrent.call", method))
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn call<F: Future>(_method: &'static str, future: F) -> F {
+    future
+}
+
+/// the span of a single request, retries included.
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl RequestSpan {
+    pub(crate) fn new(request: &TransportRequest) -> Self {
+        use tracing::field::Empty;
+
+        let span = tracing::info_span!(
+            "qbittorrent.request",
+            endpoint = %request.path,
+            http.method = %request.method,
+            status = Empty,
+            latency_ms = Empty,
+            bytes = Empty,
+            retries = Empty,
+            error = Empty,
+        );
+
+        Self { span, start: std::time::Instant::now() }
+    }
+
+    /// runs `future` inside the span.
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> tracing::instrument::Instrumented<F> {
+        use tracing::Instrument;
+
+        future.instrument(self.span.clone())
+    }
+
+    /// records the outcome of the request, after `attempts` attempts.
+    pub(crate) fn finish(&self, result: &Result<TransportResponse, Error>, attempts: u32) {
+        self.span.record("latency_ms", self.start.elapsed().as_millis() as u64);
+        self.span.record("retries", attempts - 1);
+
+        match result {
+            Ok(response) => {
+                self.span.record("status", response.status);
+                self.span.record("bytes", response.body.len() as u64);
+            }
+            Err(e) => {
+                if let Some(code) = e.code {
+                    self.span.record("status", code);
+                }
+                self.span.record("error", tracing::field::display(e));
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl RequestSpan {
+    pub(crate) fn new(_request: &TransportRequest) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> F {
+        future
+    }
+
+    pub(crate) fn finish(&self, _result: &Result<TransportResponse, Error>, _attempts: u32) {}
+}
+
+/// the session was rejected with a `403 Forbidden`, and the client logged in again to retry the request.
+pub(crate) fn relogin(_endpoint: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(endpoint = _endpoint, "session rejected, logged in again");
+}
+
+/// the session cookie is about to expire, and the client is logging in again before using it.
+pub(crate) fn session_expired() {
+    #[cfg(feature = "tracing")]
+    tracing::debug!("session cookie expired, logging in again");
+}
+
+/// the fixtures recorded by a [`super::fixtures::RecordingTransport`] couldn't be written when it was dropped.
+pub(crate) fn fixtures_not_saved(_error: &Error) {
+    #[cfg(feature = "tracing")]
+    tracing::error!(error = %_error, "couldn't save the recorded fixtures");
+}
+
+/// attempt number `attempt` failed, and the request will be sent again after `backoff`.
+pub(crate) fn retry(_attempt: u32, _backoff: Duration, _result: &Result<TransportResponse, Error>) {
+    #[cfg(feature = "tracing")]
+    match _result {
+        Ok(response) => tracing::warn!(attempt = _attempt, backoff_ms = _backoff.as_millis() as u64, status = response.status, "retrying request"),
+        Err(e) => tracing::warn!(attempt = _attempt, backoff_ms = _backoff.as_millis() as u64, error = %e, "retrying request"),
+    }
+}
@@ -0,0 +1,46 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_lock::{Mutex, Semaphore, SemaphoreGuardArc};
+
+/// limits how many requests are sent at once, and how many are sent per second.
+///
+/// it's shared by every clone of a [`crate::core::api::QbitApi`], so the limits apply to all of them together.
+#[derive(Debug)]
+pub(crate) struct Limiter {
+    in_flight: Option<Arc<Semaphore>>,
+    interval: Option<Duration>,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl Limiter {
+    /// creates a new [`Limiter`]. `None` means no limit.
+    pub(crate) fn new(max_in_flight: Option<usize>, requests_per_second: Option<f64>) -> Self {
+        Self {
+            in_flight: max_in_flight.map(|max| Arc::new(Semaphore::new(max))),
+            interval: requests_per_second.map(|rps| Duration::from_secs_f64(1.0 / rps)),
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    /// waits until a request can be sent. the returned permit must be held until the request is done.
+    pub(crate) async fn acquire(&self) -> Option<SemaphoreGuardArc> {
+        if let Some(interval) = self.interval {
+            let slot = {
+                let mut next_slot = self.next_slot.lock().await;
+                let slot = next_slot.map_or(Instant::now(), |next| next.max(Instant::now()));
+                *next_slot = Some(slot + interval);
+                slot
+            };
+
+            futures_timer::Delay::new(slot.saturating_duration_since(Instant::now())).await;
+        }
+
+        match &self.in_flight {
+            Some(semaphore) => Some(semaphore.acquire_arc().await),
+            None => None,
+        }
+    }
+}
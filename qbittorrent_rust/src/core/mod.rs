@@ -1,3 +1,11 @@
 pub mod creds;
 pub mod api;
-pub mod cookie;
\ No newline at end of file
+pub mod cookie;
+pub mod builder;
+pub mod retry;
+pub mod file_reader;
+pub mod transport;
+pub mod fixtures;
+pub mod version;
+pub(crate) mod limiter;
+pub(crate) mod instrument;
\ No newline at end of file
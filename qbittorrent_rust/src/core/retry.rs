@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use proc_macros_qbittorrent_rust::Builder;
+
+use crate::{error_handling::error_type::ErrorType, Error};
+
+/// ## Info
+/// describes when and how failed requests are retried.
+///
+/// a request is retried only if its endpoint is retryable (by default, only the endpoints that don't change anything), and it either:
+/// - couldn't be sent at all (eg: qbittorrent is restarting), if `retry_connection_errors` is set;
+/// - got a response with one of the retryable status codes (by default: `500`, `502`, `503` and `504`).
+///
+/// between two attempts, the client waits for an exponentially growing backoff (doubling after each attempt, up to `max_backoff`),
+/// randomized with jitter so that many clients don't retry all at once.
+///
+/// [`crate::core::api::QbitApi`] doesn't retry anything unless a [`RetryPolicy`] is set with [`crate::core::builder::QbitApiBuilder::retry_policy()`].
+///
+/// ## Example
+/// ```no_run
+/// # use std::time::Duration;
+/// # use qbittorrent_rust::core::{api::QbitApi, creds::Credentials, retry::RetryPolicy};
+/// # async fn example() -> Result<(), qbittorrent_rust::Error> {
+/// let policy = RetryPolicy::builder()
+///     .max_attempts(5)
+///     .max_backoff(Duration::from_secs(10))
+///     .build();
+///
+/// let api = QbitApi::builder("http://localhost:6011/", Credentials::new("user_name", "password"))
+///     .retry_policy(policy)
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_connection_errors: bool,
+    retryable_statuses: Vec<u16>,
+    retryable_endpoints: Vec<String>,
+}
+
+impl RetryPolicy {
+    /// ## Usage
+    /// creates a [`RetryPolicy`] with the default options.
+    /// alias `RetryPolicy::builder().build()`.
+    pub fn new() -> Self {
+        RetryPolicyBuilder::new().build()
+    }
+
+    /// ## Usage
+    /// creates a [`RetryPolicy`] that never retries.
+    pub fn none() -> Self {
+        RetryPolicyBuilder::new().max_attempts(1).build()
+    }
+
+    /// ## Usage
+    /// returns a [`RetryPolicyBuilder`], the builder for [`RetryPolicy`].
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::new()
+    }
+
+    /// ## Usage
+    /// returns the maximum number of attempts for a single request, including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// ## Usage
+    /// returns whether requests to `endpoint` (eg: `"/torrents/info"`) can be retried.
+    pub fn is_retryable_endpoint(&self, endpoint: &str) -> bool {
+        let endpoint = format!("/{}", endpoint.split('?').next().unwrap_or_default().trim_start_matches('/'));
+
+        self.retryable_endpoints.iter().any(|allowed| {
+            if allowed.ends_with('/') {
+                endpoint.starts_with(allowed.as_str())
+            } else {
+                &endpoint == allowed
+            }
+        })
+    }
+
+    /// returns whether a response with this status code should be retried.
+    pub(crate) fn retries_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    /// returns whether a request that failed with this error should be retried.
+    pub(crate) fn retries_error(&self, error: &Error) -> bool {
+        self.retry_connection_errors && matches!(error.err_type, ErrorType::HostUnreachable(_) | ErrorType::ReqwestError(_))
+    }
+
+    /// returns how long to wait after the `attempt`-th attempt (starting from 1) failed.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self.initial_backoff.saturating_mul(1 << exponent).min(self.max_backoff);
+
+        if self.jitter {
+            // "equal jitter": half of the backoff is kept, the other half is random.
+            let half = backoff / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ## Info
+/// the builder struct for [`RetryPolicy`].
+///
+/// ## Fields
+/// | Field                     | Type          | Description                                                                                   |
+/// |---------------------------|---------------|-----------------------------------------------------------------------------------------------|
+/// | `max_attempts`            | `u32`         | Maximum number of attempts, including the first one. (default: 3)                             |
+/// | `initial_backoff`         | `Duration`    | Time to wait after the first failed attempt. (default: 250ms)                                 |
+/// | `max_backoff`             | `Duration`    | Maximum time to wait between two attempts. (default: 5s)                                      |
+/// | `jitter`                  | `Bool`        | Randomize the time waited between two attempts. (default: true)                               |
+/// | `retry_connection_errors` | `Bool`        | Retry requests that couldn't be sent, or whose response couldn't be read. (default: true)     |
+/// | `retryable_statuses`      | `Vec<u16>`    | Status codes of the responses to retry. (default: `500`, `502`, `503`, `504`)                 |
+/// | `retryable_endpoints`     | `Vec<String>` | Endpoints that are safe to retry; entries ending in `/` match every endpoint under them. (default: [`RetryPolicyBuilder::READ_ONLY_ENDPOINTS`]) |
+#[derive(Debug, Clone, Builder)]
+pub struct RetryPolicyBuilder {
+    max_attempts: Option<u32>,
+    initial_backoff: Option<Duration>,
+    max_backoff: Option<Duration>,
+    jitter: Option<bool>,
+    retry_connection_errors: Option<bool>,
+    retryable_statuses: Option<Vec<u16>>,
+    retryable_endpoints: Option<Vec<String>>,
+}
+
+impl RetryPolicyBuilder {
+    /// the endpoints that only read data, and can therefore be retried safely.
+    pub const READ_ONLY_ENDPOINTS: &'static [&'static str] = &[
+        "/app/version",
+        "/app/webapiVersion",
+        "/app/buildInfo",
+        "/app/preferences",
+        "/app/defaultSavePath",
+        "/log/",
+        "/sync/",
+        "/transfer/info",
+        "/transfer/speedLimitsMode",
+        "/transfer/downloadLimit",
+        "/transfer/uploadLimit",
+        "/torrents/info",
+        "/torrents/properties",
+        "/torrents/trackers",
+        "/torrents/webseeds",
+        "/torrents/files",
+        "/torrents/pieceStates",
+        "/torrents/pieceHashes",
+        "/torrents/downloadLimit",
+        "/torrents/uploadLimit",
+        "/torrents/categories",
+        "/torrents/tags",
+        "/rss/items",
+        "/rss/rules",
+        "/rss/matchingArticles",
+        "/search/status",
+        "/search/results",
+        "/search/plugins",
+    ];
+
+    /// ## Usage
+    /// creates a new instance of [`RetryPolicyBuilder`], with every option unset.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: None,
+            initial_backoff: None,
+            max_backoff: None,
+            jitter: None,
+            retry_connection_errors: None,
+            retryable_statuses: None,
+            retryable_endpoints: None,
+        }
+    }
+
+    /// ## Usage
+    /// returns the finalized [`RetryPolicy`], using the default value of every option that wasn't set.
+    pub fn build(self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.max_attempts.unwrap_or(3).max(1),
+            initial_backoff: self.initial_backoff.unwrap_or(Duration::from_millis(250)),
+            max_backoff: self.max_backoff.unwrap_or(Duration::from_secs(5)),
+            jitter: self.jitter.unwrap_or(true),
+            retry_connection_errors: self.retry_connection_errors.unwrap_or(true),
+            retryable_statuses: self.retryable_statuses.unwrap_or_else(|| vec![500, 502, 503, 504]),
+            retryable_endpoints: self
+                .retryable_endpoints
+                .unwrap_or_else(|| Self::READ_ONLY_ENDPOINTS.iter().map(|e| e.to_string()).collect()),
+        }
+    }
+}
+
+impl Default for RetryPolicyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
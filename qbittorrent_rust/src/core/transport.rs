@@ -0,0 +1,275 @@
+use std::fmt::Debug;
+
+use futures_util::future::BoxFuture;
+use reqwest::{
+    header::{HeaderMap, COOKIE, SET_COOKIE},
+    Client, Method, Url,
+};
+
+use crate::{error_handling::error_type::ErrorType, Error};
+
+/// ## Info
+/// sends the http requests of a [`crate::core::api::QbitApi`], and returns their responses.
+///
+/// by default, [`ReqwestTransport`] is used; set a different one with [`crate::core::builder::QbitApiBuilder::transport()`]
+/// (eg: to record and replay requests in tests, to reach qbittorrent through a unix socket, or to run on another runtime than tokio).
+///
+/// the [`crate::core::api::QbitApi`] takes care of everything else: authentication, retries and rate limiting all happen before the request reaches the transport.
+///
+/// ## Errors
+/// a transport should only return an [`Error`] if no response could be obtained at all;
+/// use [`ErrorType::HostUnreachable`] when qbittorrent couldn't be reached, so that the request can be retried.
+/// unsuccessful responses (eg: `404 Not Found`) must be returned as [`TransportResponse`]s.
+///
+/// ## Example
+/// ```no_run
+/// # use futures_util::future::BoxFuture;
+/// # use qbittorrent_rust::{core::transport::{Transport, TransportRequest, TransportResponse}, Error};
+/// struct AlwaysOk;
+///
+/// impl Transport for AlwaysOk {
+///     fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+///         Box::pin(async move {
+///             println!("{} {}", request.method, request.path);
+///             Ok(TransportResponse::new(200, "Ok."))
+///         })
+///     }
+/// }
+/// ```
+pub trait Transport: Send + Sync {
+    /// ## Usage
+    /// sends `request`, returning the response to it.
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>>;
+}
+
+impl Debug for dyn Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Transport")
+    }
+}
+
+/// ## Info
+/// a request to the API, as handed to a [`Transport`].
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    /// the http method.
+    pub method: Method,
+    /// url of the root of the API (eg: `http://localhost:6011/api/v2/`), always ending with a `/`.
+    pub api_url: Url,
+    /// the endpoint, relative to the root of the API, without the query string (eg: `"/torrents/info"`).
+    pub path: String,
+    /// the (decoded) query string parameters.
+    pub query: Vec<(String, String)>,
+    /// the headers to send, besides the cookies (eg: `Referer` and `Origin`).
+    pub headers: HeaderMap,
+    /// the cookies to send (eg: `("SID", "...")`).
+    pub cookies: Vec<(String, String)>,
+    /// the body of the request.
+    pub body: RequestBody,
+}
+impl TransportRequest {
+    /// creates a new `POST` request to `path` (which can include a query string), with no headers, no cookies and no body.
+    pub(crate) fn post(api_url: &Url, path: &str) -> Result<Self, Error> {
+        let full = api_url
+            .join(path.trim_start_matches('/'))
+            .map_err(|e| Error::build(ErrorType::UrlParseError(Box::new(e)), None))?;
+
+        Ok(Self {
+            method: Method::POST,
+            api_url: api_url.clone(),
+            path: format!("/{}", path.split('?').next().unwrap_or_default().trim_start_matches('/')),
+            query: full.query_pairs().into_owned().collect(),
+            headers: HeaderMap::new(),
+            cookies: vec![],
+            body: RequestBody::Empty,
+        })
+    }
+
+    /// sets the body of the request.
+    pub(crate) fn body(mut self, body: RequestBody) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// ## Usage
+    /// returns the full url of the request, query string included.
+    pub fn url(&self) -> Result<Url, Error> {
+        let mut url = self
+            .api_url
+            .join(self.path.trim_start_matches('/'))
+            .map_err(|e| Error::build(ErrorType::UrlParseError(Box::new(e)), None))?;
+
+        if !self.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.query);
+        }
+
+        Ok(url)
+    }
+
+    /// ## Usage
+    /// returns the cookies formatted as the value of a `Cookie` header (eg: `"SID=abc; other=def"`), or `None` if there are none.
+    pub fn cookie_header(&self) -> Option<String> {
+        if self.cookies.is_empty() {
+            return None;
+        }
+
+        Some(self.cookies.iter().map(|(name, value)| format!("{}={}", name, value)).collect::<Vec<String>>().join("; "))
+    }
+}
+
+/// ## Info
+/// the body of a [`TransportRequest`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestBody {
+    /// no body.
+    Empty,
+    /// an `application/x-www-form-urlencoded` body.
+    Form(Vec<(String, String)>),
+    /// a `multipart/form-data` body.
+    Multipart(Vec<MultipartField>),
+}
+
+/// ## Info
+/// a field of a [`RequestBody::Multipart`] body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipartField {
+    /// the name of the field.
+    pub name: String,
+    /// the value of the field.
+    pub value: MultipartValue,
+}
+
+/// ## Info
+/// the value of a [`MultipartField`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MultipartValue {
+    /// a plain text value.
+    Text(String),
+    /// a file.
+    File {
+        file_name: String,
+        content_type: String,
+        bytes: Vec<u8>,
+    },
+}
+
+/// ## Info
+/// the response to a [`TransportRequest`], as returned by a [`Transport`].
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    /// the http status code.
+    pub status: u16,
+    /// the response headers.
+    pub headers: HeaderMap,
+    /// the raw response body.
+    pub body: Vec<u8>,
+    /// the cookies set by the response, as `(name, value)`, if the transport parsed them itself (like [`ReqwestTransport`] does).
+    /// when empty, they're read from the `Set-Cookie` headers.
+    pub cookies: Vec<(String, String)>,
+}
+impl TransportResponse {
+    /// ## Usage
+    /// creates a new [`TransportResponse`] with no headers.
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self { status, headers: HeaderMap::new(), body: body.into(), cookies: vec![] }
+    }
+
+    /// ## Usage
+    /// returns whether the status code is in the `2xx` range.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// ## Usage
+    /// returns the body as text, replacing invalid utf-8 sequences.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// ## Usage
+    /// returns the value of the cookie named `name` set by the response, if any.
+    ///
+    /// the cookies parsed by the transport are looked up first; otherwise, the `Set-Cookie` headers are read one by one,
+    /// skipping the ones that aren't a `name=value` pair (eg: only attributes, or no `=`). values are returned as they were sent, quotes included.
+    pub fn cookie(&self, name: &str) -> Option<String> {
+        if let Some((_, value)) = self.cookies.iter().find(|(cookie_name, _)| cookie_name == name) {
+            return Some(value.clone());
+        }
+
+        self.headers.get_all(SET_COOKIE).iter().find_map(|header| {
+            let pair = header.to_str().ok()?.split(';').next()?;
+            let (cookie_name, value) = pair.split_once('=')?;
+            (cookie_name.trim() == name).then(|| value.trim().to_string())
+        })
+    }
+}
+
+/// ## Info
+/// the default [`Transport`], sending the requests with a [`reqwest::Client`]. like reqwest, it needs a tokio runtime.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+impl ReqwestTransport {
+    /// ## Usage
+    /// creates a new [`ReqwestTransport`] that sends the requests with `client`.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            let mut builder = self.client.request(request.method.clone(), request.url()?).headers(request.headers.clone());
+
+            if let Some(cookies) = request.cookie_header() {
+                builder = builder.header(COOKIE, cookies);
+            }
+
+            builder = match request.body {
+                RequestBody::Empty => builder,
+                RequestBody::Form(fields) => builder.form(&fields),
+                RequestBody::Multipart(fields) => with_multipart(builder, fields)?,
+            };
+
+            let response = builder.send().await.map_err(Error::from_reqwest)?;
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            // reqwest parses the `Set-Cookie` headers with the same parser as its cookie store.
+            let cookies = response.cookies().map(|cookie| (cookie.name().to_string(), cookie.value().to_string())).collect();
+            let body = response.bytes().await.map_err(Error::from_reqwest)?.to_vec();
+
+            Ok(TransportResponse { status, headers, body, cookies })
+        })
+    }
+}
+
+/// sets the fields of a multipart body as the body of the request.
+#[cfg(feature = "torrents")]
+fn with_multipart(builder: reqwest::RequestBuilder, fields: Vec<MultipartField>) -> Result<reqwest::RequestBuilder, Error> {
+    let mut form = reqwest::multipart::Form::new();
+
+    for field in fields {
+        form = match field.value {
+            MultipartValue::Text(text) => form.text(field.name, text),
+            MultipartValue::File { file_name, content_type, bytes } => {
+                let mut headers = HeaderMap::new();
+                headers.insert(
+                    reqwest::header::CONTENT_TYPE,
+                    content_type.parse().map_err(|_| Error::build(ErrorType::ParameterNotExpected, None))?,
+                );
+
+                form.part(field.name, reqwest::multipart::Part::bytes(bytes).file_name(file_name).headers(headers))
+            }
+        };
+    }
+
+    Ok(builder.multipart(form))
+}
+
+/// multipart bodies are only sent by the torrents category, which enables reqwest's multipart support.
+#[cfg(not(feature = "torrents"))]
+fn with_multipart(_: reqwest::RequestBuilder, _: Vec<MultipartField>) -> Result<reqwest::RequestBuilder, Error> {
+    Err(Error::build(ErrorType::MiscError("multipart bodies need the `torrents` feature.".to_string()), None))
+}
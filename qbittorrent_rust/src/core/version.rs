@@ -0,0 +1,106 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{error_handling::error_type::ErrorType, Error};
+
+/// ## Info
+/// a version of the WebUI API (eg: `2.9.3`), as returned by `/app/webapiVersion`; it's not the version of qbittorrent itself (eg: `v4.6.7`).
+///
+/// versions compare component by component, so they can be checked against the minimum version an endpoint needs:
+/// ```
+/// # use qbittorrent_rust::core::version::ApiVersion;
+/// let version: ApiVersion = "2.8.19".parse().unwrap();
+///
+/// assert!(version >= ApiVersion::new(2, 7, 0));
+/// assert!(version < ApiVersion::new(2, 11, 0));
+/// ```
+///
+/// [`crate::core::api::QbitApi`] detects it when connecting, and the endpoints that need a newer version than the connected qbittorrent
+/// fail with [`ErrorType::Unsupported`] instead of being sent (see [`ApiVersion::minimum_for()`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+/// the minimum WebUI API version of the endpoints that weren't there from the start, or whose parameters changed since.
+///
+/// qbittorrent 4.1.0 shipped WebUI API 2.0.0; the endpoints that aren't listed are in every 2.x version.
+const MINIMUM_VERSIONS: &[(&str, ApiVersion)] = &[
+    ("/torrents/setShareLimits", ApiVersion::new(2, 0, 1)),
+    ("/torrents/editCategory", ApiVersion::new(2, 1, 0)),
+    ("/torrents/editTracker", ApiVersion::new(2, 2, 0)),
+    ("/torrents/removeTrackers", ApiVersion::new(2, 2, 0)),
+    ("/torrents/addPeers", ApiVersion::new(2, 3, 0)),
+    ("/torrents/tags", ApiVersion::new(2, 3, 0)),
+    ("/torrents/createTags", ApiVersion::new(2, 3, 0)),
+    ("/torrents/deleteTags", ApiVersion::new(2, 3, 0)),
+    ("/transfer/banPeers", ApiVersion::new(2, 3, 0)),
+    ("/app/buildInfo", ApiVersion::new(2, 3, 0)),
+    ("/rss/markAsRead", ApiVersion::new(2, 5, 1)),
+    ("/rss/matchingArticles", ApiVersion::new(2, 5, 1)),
+    // `renameFile` takes `oldPath`/`newPath` (instead of a file id) since 2.7.0, the same version that added `renameFolder`.
+    ("/torrents/renameFile", ApiVersion::new(2, 7, 0)),
+    ("/torrents/renameFolder", ApiVersion::new(2, 7, 0)),
+    // qbittorrent 5 renamed `pause`/`resume` to `stop`/`start`.
+    ("/torrents/stop", ApiVersion::QBITTORRENT_5),
+    ("/torrents/start", ApiVersion::QBITTORRENT_5),
+];
+
+impl ApiVersion {
+    /// the version of the WebUI API shipped with qbittorrent 5.0, which renamed "paused" to "stopped" (and "resumed" to "running")
+    /// in the endpoints, the state filters and the parameters of `/torrents/add`.
+    pub const QBITTORRENT_5: ApiVersion = ApiVersion::new(2, 11, 0);
+
+    /// ## Usage
+    /// creates a new [`ApiVersion`].
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// ## Usage
+    /// returns the minimum version of the WebUI API needed by `endpoint` (eg: `"/torrents/renameFolder"`),
+    /// or `None` if every version of the WebUI API 2.x has it.
+    pub fn minimum_for(endpoint: impl AsRef<str>) -> Option<ApiVersion> {
+        MINIMUM_VERSIONS.iter().find(|(path, _)| *path == endpoint.as_ref()).map(|(_, version)| *version)
+    }
+
+    /// ## Usage
+    /// returns `Ok(())` if this version is at least `required`.
+    ///
+    /// ## Errors
+    /// returns an [`Error`] with error type [`ErrorType::Unsupported`] if it's older than `required`.
+    pub fn require(&self, required: ApiVersion) -> Result<(), Error> {
+        match *self >= required {
+            true => Ok(()),
+            false => Err(Error::build(ErrorType::Unsupported { required, actual: *self }, None)),
+        }
+    }
+}
+
+impl Display for ApiVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = Error;
+
+    /// parses `major.minor` or `major.minor.patch`, with an optional leading `v` (eg: `"2.9.3"`, `"v2.8"`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::build(ErrorType::UnexpectedResponse(format!("`{}` isn't a valid WebUI API version.", s.trim())), None);
+
+        let trimmed = s.trim();
+        let mut parts = trimmed.strip_prefix('v').unwrap_or(trimmed).split('.').map(|part| part.parse::<u32>().map_err(|_| invalid()));
+
+        let major = parts.next().ok_or_else(invalid)??;
+        let minor = parts.next().ok_or_else(invalid)??;
+        let patch = parts.next().transpose()?.unwrap_or(0);
+
+        match parts.next() {
+            Some(_) => Err(invalid()),
+            None => Ok(Self { major, minor, patch }),
+        }
+    }
+}
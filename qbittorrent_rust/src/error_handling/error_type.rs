@@ -1,5 +1,13 @@
+use crate::core::version::ApiVersion;
+
+/// a boxed error that can be sent across threads, used as the source of the errors coming from dependencies.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
 /// ## Description
 /// describes the type of error which occurred.
+///
+/// the variants that describe a documented status code of an endpoint (`400`, `404`, `409`, `415`) contain the reason given by the documentation of that endpoint,
+/// or the body of the response if qbittorrent's documentation doesn't specify one.
 #[derive(Debug)]
 pub enum ErrorType {
     TorrentsNotSet,
@@ -8,14 +16,45 @@ pub enum ErrorType {
     TorrenQueueingNotEnabled,
     WrongCreds,
     TooManyFailedAttempts,
+    MissingSessionCookie,
     ParameterNotExpected,
+    /// `400 Bad Request`: qbittorrent rejected the parameters of the request.
+    BadParameters(String),
+    /// `403 Forbidden`: qbittorrent refused the session; with credentials, even after logging in again (the other [`crate::core::creds::AuthMode`]s can't log in again).
+    Forbidden,
+    /// `404 Not Found`: the requested item (search job, category, ...) doesn't exist.
+    NotFound(String),
+    /// `409 Conflict`: the request conflicts with the current state of qbittorrent.
+    Conflict(String),
+    /// `415 Unsupported Media Type`: the torrent file is not valid.
+    InvalidTorrent,
+    /// qbittorrent answered successfully, but with data that doesn't have the expected shape.
+    UnexpectedResponse(String),
+    /// the endpoint needs a newer version of the WebUI API than the one of the connected qbittorrent, so the request wasn't sent.
+    Unsupported { required: ApiVersion, actual: ApiVersion },
     MiscNetError(u16),
     MiscError(String),
-    ReqwestError(Box<dyn std::error::Error>),
-    JsonSerdeError(Box<dyn std::error::Error>),
+    ReqwestError(BoxError),
+    JsonSerdeError(BoxError),
+    UrlParseError(BoxError),
+    HostUnreachable(BoxError),
 }
 
 impl ErrorType {
+    /// maps the status code of an unsuccessful response to the matching error type. `body` is used as the reason when there's one.
+    pub(crate) fn from_status(status: u16, body: &str) -> ErrorType {
+        let reason = |default: &str| if body.trim().is_empty() { default.to_string() } else { body.trim().to_string() };
+
+        match status {
+            400 => ErrorType::BadParameters(reason("the request was malformed.")),
+            403 => ErrorType::Forbidden,
+            404 => ErrorType::NotFound(reason("the requested item doesn't exist.")),
+            409 => ErrorType::Conflict(reason("the request conflicts with the current state of qbittorrent.")),
+            415 => ErrorType::InvalidTorrent,
+            _ => ErrorType::MiscNetError(status),
+        }
+    }
+
     pub(crate) fn get_message(&self) -> String {
         match self {
             ErrorType::TorrentsNotSet => "no torrents were specified.".to_string(),
@@ -24,11 +63,21 @@ impl ErrorType {
             ErrorType::TorrenQueueingNotEnabled => "torrent queuing id not enabled.".to_string(),
             ErrorType::WrongCreds => "the credetials are wrong.".to_string(),
             ErrorType::TooManyFailedAttempts => "the user has been banned for an amount of time because of too many failed login attempts.".to_string(),
+            ErrorType::MissingSessionCookie => "the login succeeded, but qbittorrent didn't send back a session cookie.".to_string(),
             ErrorType::ParameterNotExpected => "one or more of the parameters speciied were wrong".to_string(),
+            ErrorType::BadParameters(reason) => format!("qbittorrent rejected the parameters. reason: {}", reason),
+            ErrorType::Forbidden => "qbittorrent refused the session (after logging in again, when the client has credentials to do so).".to_string(),
+            ErrorType::NotFound(reason) => format!("the requested item couldn't be found. reason: {}", reason),
+            ErrorType::Conflict(reason) => format!("the request conflicts with the state of qbittorrent. reason: {}", reason),
+            ErrorType::InvalidTorrent => "the torrent file is not valid.".to_string(),
+            ErrorType::UnexpectedResponse(e) => format!("qbittorrent sent an unexpected response. {}", e),
+            ErrorType::Unsupported { required, actual } => format!("this requires version {} of the WebUI API, but qbittorrent has version {}.", required, actual),
             ErrorType::MiscError(e) => format!("Something went wrong. {}", e),
             ErrorType::ReqwestError(e) => format!("there was an error while handling networking. error: {}", e),
             ErrorType::JsonSerdeError(e) => format!("there was an error while handling JSON data. error: {}", e),
+            ErrorType::UrlParseError(e) => format!("the url is malformed. error: {}", e),
+            ErrorType::HostUnreachable(e) => format!("qbittorrent couldn't be reached. error: {}", e),
             ErrorType::MiscNetError(e) => format!("there was an error during a request. error code: {}", e),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,14 +1,26 @@
 use std::fmt::{Display, Formatter};
 
+use serde::de::DeserializeOwned;
+
+use crate::core::transport::TransportResponse;
+
 use super::error_type::ErrorType;
 
+/// maximum length (in bytes) of the response body kept in an [`Error`].
+const MAX_BODY_LEN: usize = 512;
+
 /// ## Description
 /// represents an error given by the library.
+///
+/// it's `Send + Sync + 'static`, so it can be moved across tasks and converted into other error types (eg: `anyhow::Error`).
 /// 
 /// ## Fields
 /// - err_type: error type.
 /// - message: error message.
 /// - code: eventual HTTP error code.
+/// - method: eventual name of the [`crate::core::api::QbitApi`] method that failed (eg: `"torrents_get_torrent_list_raw"`).
+/// - endpoint: eventual endpoint of the request that failed, relative to `/api/v2` (eg: `"/torrents/info"`).
+/// - body: eventual body of the response, truncated to 512 bytes.
 #[derive(Debug)]
 pub struct Error {
     ///error type
@@ -16,35 +28,134 @@ pub struct Error {
     /// error message
     pub message: String,
     /// eventual HTTP error code
-    pub code: Option<u16>
+    pub code: Option<u16>,
+    /// eventual name of the method that failed
+    pub method: Option<&'static str>,
+    /// eventual endpoint of the request that failed
+    pub endpoint: Option<String>,
+    /// eventual (truncated) body of the response, boxed to keep [`Error`] small
+    pub body: Option<Box<str>>,
 } impl Error {
+    /// ## Usage
+    /// creates a new [`Error`], with the default message of its type.
+    /// useful for custom [`crate::core::transport::Transport`]s and [`crate::core::creds::AuthProvider`]s.
+    pub fn new(err_type: ErrorType, code: Option<u16>) -> Error {
+        Self::build(err_type, code)
+    }
+
     pub(crate) fn build(err_type: ErrorType, code: Option<u16>) -> Error {
         let message = err_type.get_message();
-        Error { err_type, message, code }
+        Error { err_type, message, code, method: None, endpoint: None, body: None }
+    }
+
+    /// builds an error out of an unsuccessful response, keeping (a truncated copy of) its body.
+    pub(crate) fn from_response(endpoint: impl AsRef<str>, response: TransportResponse) -> Error {
+        let status = response.status;
+        let body = response.text();
+
+        Error::build(ErrorType::from_status(status, &body), Some(status))
+            .with_endpoint(endpoint)
+            .with_body(body)
+    }
+
+    /// wraps an error given by reqwest, telling apart the cases where qbittorrent couldn't be reached at all.
+    pub(crate) fn from_reqwest(e: reqwest::Error) -> Error {
+        let code = e.status().map(|s| s.as_u16());
+
+        if e.is_connect() || e.is_timeout() {
+            Error::build(ErrorType::HostUnreachable(Box::new(e)), code)
+        } else {
+            Error::build(ErrorType::ReqwestError(Box::new(e)), code)
+        }
+    }
+
+    /// sets the name of the API method that failed.
+    pub(crate) fn with_method(mut self, method: &'static str) -> Error {
+        self.method = Some(method);
+        self
+    }
+
+    /// sets the endpoint of the request that failed, dropping its query string.
+    pub(crate) fn with_endpoint(mut self, endpoint: impl AsRef<str>) -> Error {
+        let path = endpoint.as_ref().split('?').next().unwrap_or_default().trim_start_matches('/');
+        self.endpoint = Some(format!("/{}", path));
+        self
     }
+
+    /// sets the body of the response, truncating it to [`MAX_BODY_LEN`] bytes.
+    pub(crate) fn with_body(mut self, mut body: String) -> Error {
+        if body.len() > MAX_BODY_LEN {
+            let mut end = MAX_BODY_LEN;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            body.truncate(end);
+            body.push('…');
+        }
+
+        self.body = Some(body.into_boxed_str());
+        self
+    }
+
+    /// replaces the error type (and message), keeping the code, the method, the endpoint and the body.
+    #[cfg_attr(not(any(feature = "torrents", feature = "rss", feature = "search")), allow(dead_code))]
+    pub(crate) fn retype(mut self, err_type: ErrorType) -> Error {
+        self.message = err_type.get_message();
+        self.err_type = err_type;
+        self
+    }
+}
+
+/// ## Usage
+/// parses `body`, the answer of `endpoint` to the api method `method`, as json.
+///
+/// ## Errors
+/// returns an [`Error`] with error type [`ErrorType::JsonSerdeError`], keeping the method, the endpoint and (a truncated copy of) the body,
+/// if `body` isn't valid json or doesn't match `T`.
+#[cfg_attr(not(any(feature = "torrents", feature = "rss", feature = "search", feature = "log", feature = "transfer", feature = "app")), allow(dead_code))]
+pub(crate) fn parse_json<T: DeserializeOwned>(body: &str, method: &'static str, endpoint: &str) -> Result<T, Error> {
+    serde_json::from_str(body).map_err(|e| {
+        Error::build(ErrorType::JsonSerdeError(Box::new(e)), None)
+            .with_method(method)
+            .with_endpoint(endpoint)
+            .with_body(body.to_string())
+    })
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
+        write!(f, "{}", self.message)?;
+
+        let details: Vec<String> = [
+            self.method.map(|method| format!("method: {}", method)),
+            self.endpoint.as_ref().map(|endpoint| format!("endpoint: {}", endpoint)),
+            self.code.map(|code| format!("status: {}", code)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        match details.is_empty() {
+            true => Ok(()),
+            false => write!(f, " ({})", details.join(", ")),
+        }
     }
 }
 
-
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match &self.err_type {
             ErrorType::JsonSerdeError(e) => Some(e.as_ref()),
             ErrorType::ReqwestError(e) => Some(e.as_ref()),
+            ErrorType::UrlParseError(e) => Some(e.as_ref()),
+            ErrorType::HostUnreachable(e) => Some(e.as_ref()),
             _ => None
         }
     }
+}
 
-    fn description(&self) -> &str {
-        "description() is deprecated; use Display."
-    }
-
-    fn cause(&self) -> Option<&dyn std::error::Error> {
-        self.source()
-    }
-}
\ No newline at end of file
+// errors must be able to cross task boundaries.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<Error>();
+};
@@ -24,6 +24,36 @@
 //! | rss |  holds everything related to RSS. |
 //!
 //! each method in [`QbitApi`] starts with its category, followed by the method's name, all in snake case. example: `torrents_add_torrent`
+//!
+//! ## Features
+//!
+//! every category has a cargo feature with its name (`torrents`, `log`, `app`, `transfer`, `sync`, `search`, `rss`), all enabled by default:
+//! disable the default features and pick only the categories you need to compile less code.
+//! the TLS backend is chosen with either `native-tls` (default) or `rustls`.
+//! the `tokio` feature (default) reads .torrent files with tokio's file system api.
+//!
+//! ## Runtimes
+//!
+//! out of the box, the library needs a tokio runtime: the default [`core::transport::ReqwestTransport`] is built on reqwest,
+//! which needs a tokio reactor, and with the `tokio` feature .torrent files are read with [`core::file_reader::TokioFileReader`].
+//! to use it with async-std, smol or any other executor, set a custom [`core::transport::Transport`] with
+//! [`core::builder::QbitApiBuilder::transport()`], and either set [`core::file_reader::StdFileReader`] with
+//! [`core::builder::QbitApiBuilder::file_reader()`] or disable the default features (`default-features = false`).
+//! everything else (locks, timers, retries, rate limiting) doesn't depend on a specific runtime.
+//!
+//! ## Blocking
+//!
+//! with the `blocking` feature, [`blocking::QbitApi`] offers the same methods without async, for scripts and synchronous codebases.
+//!
+//! ## Testing
+//!
+//! with the `testing` feature, [`testing::MockQbittorrent`] answers the requests of a [`QbitApi`] on a local port, from an in-memory state:
+//! enable it in your `[dev-dependencies]` to test your own code without running qbittorrent.
+//!
+//! ## Metrics
+//!
+//! with the `metrics` feature, [`metrics::MetricsExporter`] exports the transfer info and torrent counts of a qbittorrent instance
+//! in the Prometheus text format, on a small embedded http endpoint.
 
 
 pub mod core;
@@ -31,10 +61,23 @@ pub mod misc;
 pub mod error_handling;
 pub mod api_fns;
 pub mod macros;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 
 pub use error_handling::errors::Error;
-pub use api_fns::application::app_preferences::*;
+#[cfg(feature = "app")]
+pub use api_fns::application::{app::*, app_preferences::*};
+#[cfg(feature = "log")]
 pub use api_fns::log::logs::*;
+#[cfg(feature = "rss")]
 pub use api_fns::rss::rss::*;
+#[cfg(feature = "search")]
 pub use api_fns::search::search::*;
+#[cfg(feature = "sync")]
+pub use api_fns::sync::{events::*, main_data::*, peers::*};
+#[cfg(feature = "torrents")]
 pub use api_fns::torrents::{add_torrent::*, info::*, torrent_managing_misc::*, torrents::*};
\ No newline at end of file
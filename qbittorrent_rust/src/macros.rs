@@ -2,34 +2,21 @@
 macro_rules! post_request {
     ($(#[$meta:meta])* $func_name:ident, $path:expr) => {
         $(#[$meta])*
-        pub async fn $func_name(&mut self) -> Result <String, crate::Error> {
-            Self::make_request(self, $path, format!("{}", stringify!($func_name))).await
+        pub async fn $func_name(&self) -> Result <String, $crate::Error> {
+            Self::make_request(self, stringify!($func_name), $path).await
         }
     };
 
     ($(#[$meta:meta])* $func_name:ident, $path:expr, $(($name_arg:tt, $type:ty)),+) => {
         $(#[$meta])*
-        pub async fn $func_name(&mut self, $($name_arg: $type),+) -> Result <String, crate::Error> {
+        pub async fn $func_name(&self, $($name_arg: $type),+) -> Result <String, $crate::Error> {
             let mut form_data = std::collections::HashMap::new();
 
             $(
                 form_data.insert(stringify!($name_arg), $name_arg.to_string());
             )+
 
-
-            let response = self.reqwest_client.post(format!("{}/api/v2{}", self.authority, $path))
-                .header(reqwest::header::COOKIE, format!("SID={}", self.get_cookie().await?))
-                .form(&form_data)
-                .send()
-                .await
-                .map_err(|e| crate::error_handling::flat_error::FlatError::ReqwestError(e.to_string()).unflatten_err())?;
-
-            // Handle the response
-            if response.status().is_success() {
-                Ok(response.text().await.unwrap_or_default()) // Or return something meaningful
-            } else {
-                Err(crate::error_handling::flat_error::FlatError::ReqwestError(format!("something went wrong ({}). code: {}", stringify!($func_name), response.status().as_u16())).unflatten_err())
-            }
+            self.make_request_with_form(stringify!($func_name), $path, form_data).await
         }
     };
 }
@@ -38,37 +25,23 @@ macro_rules! post_request {
 macro_rules! post_request_no_return {
     ($(#[$meta:meta])* $func_name:ident, $path:expr) => {
         $(#[$meta])* 
-        pub async fn $func_name(&mut self) -> Result <(), crate::Error> {
-            Self::make_request(self, $path, format!("{}", stringify!($func_name))).await?;
+        pub async fn $func_name(&self) -> Result <(), $crate::Error> {
+            Self::make_request(self, stringify!($func_name), $path).await?;
             Ok(())
         }
     };
 
     ($(#[$meta:meta])*  $func_name:ident, $path:expr, $(($name_arg:tt, $type:ty)),+) => {
         $(#[$meta])*
-        pub async fn $func_name(&mut self, $($name_arg: $type),+) -> Result <(), crate::Error> {
-            use crate::error_handling::error_type::ErrorType;
+        pub async fn $func_name(&self, $($name_arg: $type),+) -> Result <(), $crate::Error> {
             let mut form_data = std::collections::HashMap::new();
 
             $(
                 form_data.insert(stringify!($name_arg), $name_arg.to_string());
             )+
 
-
-            let response = self.reqwest_client.post(format!("{}/api/v2{}", self.authority, $path))
-                .header(reqwest::header::COOKIE, format!("SID={}", self.get_cookie().await?))
-                .form(&form_data)
-                .send()
-                .await
-                .map_err(|e| Error::build(ErrorType::ReqwestError(Box::new(e)), None))?;
-
-            // Handle the response
-            if response.status().is_success() {
-
-                Ok(())
-            } else {
-                Err(Error::build(ErrorType::MiscNetError(response.status().as_u16()), Some(response.status().as_u16())))
-            }
+            self.make_request_with_form(stringify!($func_name), $path, form_data).await?;
+            Ok(())
         }
     };
 }
@@ -119,20 +92,20 @@ macro_rules! url {
 macro_rules! post_request_hash {
     ($(#[$meta:meta])* $func_name:ident, $path:expr) => {
         $(#[$meta])*
-        pub async fn $func_name(&mut self, hash: impl Borrow<TorrentHash>) -> Result <String, crate::Error> {
+        pub async fn $func_name(&self, hash: impl Borrow<TorrentHash>) -> Result <String, $crate::Error> {
             let mut hashmap = HashMap::new();
             hashmap.insert("hash", hash.get_hash());
-            self.make_request_with_form_hash($path, stringify!($func_name), hashmap).await
+            self.make_request_with_form_hash(stringify!($func_name), $path, hashmap).await
         }
     };
 }
 
 #[macro_export]
 macro_rules! fn_value_from_string {
-    ($(#[$meta:meta])* $func_name:ident, $other_func:ident) => {
+    ($(#[$meta:meta])* $func_name:ident, $other_func:ident, $path:expr) => {
         $(#[$meta])*
-        pub async fn $func_name(&mut self, hash: impl Borrow<TorrentHash>) -> Result <Value, crate::Error> {
-            Ok(serde_json::from_str(self.$other_func(hash).await?.as_str()).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None))?)
+        pub async fn $func_name(&self, hash: impl Borrow<TorrentHash>) -> Result <Value, $crate::Error> {
+            $crate::error_handling::errors::parse_json(&self.$other_func(hash).await?, stringify!($func_name), $path)
         }
     };
 }
@@ -141,7 +114,7 @@ macro_rules! fn_value_from_string {
 macro_rules! fn_hash_value_pair {
     ($(#[$meta1:meta])* $func_name:ident,$(#[$meta2:meta])* $other_func:ident, $path:expr) => {
         post_request_hash!($(#[$meta1])* $func_name, $path);
-        fn_value_from_string!($(#[$meta2])* $other_func, $func_name);
+        fn_value_from_string!($(#[$meta2])* $other_func, $func_name, $path);
     };
 }
 
@@ -149,12 +122,12 @@ macro_rules! fn_hash_value_pair {
 macro_rules! torrents_fn_mult_hashes {
     ($(#[$meta:meta])* $func_name:ident, $url:expr) => {
         $(#[$meta])*
-        pub async fn $func_name(&mut self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error> {
+        pub async fn $func_name(&self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error> {
             let hashes_str = hashes.borrow().get_string("|");
             
             let url = url!($url, ("hashes", Some(hashes_str)));
     
-            self.make_request(url, stringify!($func_name)).await?;
+            self.make_request(stringify!($func_name), url).await?;
     
             Ok(())
         }
@@ -165,12 +138,12 @@ macro_rules! torrents_fn_mult_hashes {
 #[macro_export]
 macro_rules! torrents_fn_mult_hashes_res {
     ($func_name:ident, $url:expr) => {
-        pub async fn $func_name(&mut self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<String, Error> {
+        pub async fn $func_name(&self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<String, Error> {
             let hashes_str = hashes.borrow().get_string("|");
             
             let url = url!($url, ("hashes", Some(hashes_str)));
     
-            let res = self.make_request(url, stringify!($func_name)).await?;
+            let res = self.make_request(stringify!($func_name), url).await?;
             Ok(res)
         }
     };
@@ -180,15 +153,15 @@ macro_rules! torrents_fn_mult_hashes_res {
 macro_rules! torrents_fn_mult_hashes_prios {
     ($(#[$meta:meta])* $func_name:ident, $url:expr) => {
         $(#[$meta])*
-        pub async fn $func_name(&mut self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error> {
+        pub async fn $func_name(&self, hashes: impl Borrow<TorrentHashesDesc>) -> Result<(), Error> {
             let hashes_str = hashes.borrow().get_string("|");
             
             let url = url!($url, ("hashes", Some(hashes_str)));
     
-            self.make_request(url, stringify!($func_name)).await.map_err(|e| {
+            self.make_request(stringify!($func_name), url).await.map_err(|e| {
                 if let Some(num) = e.code {
                     if num == 409 {
-                        Error::build(ErrorType::TorrenQueueingNotEnabled, Some(num))
+                        e.retype(ErrorType::TorrenQueueingNotEnabled)
                     } else {
                         e
                     }
@@ -211,29 +184,14 @@ macro_rules! code {
 
 #[macro_export]
 macro_rules! request_error_focus {
-    ($func_name:expr, $url:expr, $hashmap:expr, $(($status_code:expr, $error:expr)),+ ($else_err:expr)) => {
-        self.make_request_with_form(url, $func_name, $hashmap).await.map_err(|e| {
-            if let Some(num) = e.code {
-                match num {
-                    $(
-                        $status_code => Error::build($error, num),
-                    )+
-                    _ => $else_err,
-                }
-            } else {
-                e
-            }
-        })?;
-    };
-
     ($self:expr, $func_name:ident, $url:expr, $hashmap:expr, $(($status_code:expr, $error:expr)),+) => {
-        $self.make_request_with_form($url, stringify!($func_name), $hashmap).await.map_err(|e| {
+        $self.make_request_with_form(stringify!($func_name), $url, $hashmap).await.map_err(|e| {
             if let Some(num) = e.code {
                 match num {
                     $(
-                        $status_code => Error::build($error, Some(num)),
+                        $status_code => e.retype($error),
                     )+
-                    _ => panic!("code not handled: {}", num),
+                    _ => e,
                 }
             } else {
                 e
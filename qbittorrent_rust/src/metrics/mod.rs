@@ -0,0 +1,201 @@
+//! a Prometheus exporter for qbittorrent, built on `/transfer/info` and `/sync/maindata`.
+//!
+//! requires the `metrics` feature.
+
+mod server;
+
+use std::{collections::BTreeMap, fmt::Write, time::Duration};
+
+use crate::{api_fns::torrents::info::TorrentState, core::api::QbitApi, error_handling::error_type::ErrorType, Error, MainDataSync, ServerState, SyncState};
+
+/// how often the metrics are refreshed by default.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// ## Info
+/// collects metrics about a qbittorrent instance, and renders them in the Prometheus text format.
+///
+/// each refresh reads `/transfer/info` (rates, session totals, DHT nodes) and the changes of `/sync/maindata` since the previous refresh
+/// (all-time totals, free disk space, and the torrents, to count them by state, category and tracker).
+///
+/// | Metric                                          | Type    | Labels     |
+/// |-------------------------------------------------|---------|------------|
+/// | `qbittorrent_up`                                | gauge   |            |
+/// | `qbittorrent_connection_status`                 | gauge   | `status`   |
+/// | `qbittorrent_download_speed_bytes_per_second`   | gauge   |            |
+/// | `qbittorrent_upload_speed_bytes_per_second`     | gauge   |            |
+/// | `qbittorrent_download_limit_bytes_per_second`   | gauge   |            |
+/// | `qbittorrent_upload_limit_bytes_per_second`     | gauge   |            |
+/// | `qbittorrent_session_downloaded_bytes_total`    | counter |            |
+/// | `qbittorrent_session_uploaded_bytes_total`      | counter |            |
+/// | `qbittorrent_alltime_downloaded_bytes_total`    | counter |            |
+/// | `qbittorrent_alltime_uploaded_bytes_total`      | counter |            |
+/// | `qbittorrent_dht_nodes`                         | gauge   |            |
+/// | `qbittorrent_free_disk_space_bytes`             | gauge   |            |
+/// | `qbittorrent_peer_connections`                  | gauge   |            |
+/// | `qbittorrent_global_ratio`                      | gauge   |            |
+/// | `qbittorrent_torrents`                          | gauge   | `state`    |
+/// | `qbittorrent_category_torrents`                 | gauge   | `category` |
+/// | `qbittorrent_tracker_torrents`                  | gauge   | `tracker`  |
+/// | `qbittorrent_tracker_working_torrents`          | gauge   | `tracker`  |
+///
+/// `qbittorrent_tracker_working_torrents` counts the torrents for which the tracker is the one currently working.
+/// if a refresh fails, only `qbittorrent_up 0` is exported until the next successful one.
+///
+/// ## Example
+/// ```no_run
+/// # use qbittorrent_rust::{core::{api::QbitApi, creds::Credentials}, metrics::MetricsExporter};
+/// # async fn example() -> Result<(), qbittorrent_rust::Error> {
+/// let api = QbitApi::new("http://localhost:6011/", Credentials::new("user_name", "password")).await?;
+///
+/// // serves the metrics on http://0.0.0.0:9101/metrics, until an error occurs.
+/// MetricsExporter::new(api).serve("0.0.0.0:9101").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MetricsExporter {
+    api: QbitApi,
+    sync: MainDataSync,
+    interval: Duration,
+    metrics: String,
+}
+
+impl MetricsExporter {
+    /// ## Usage
+    /// creates a new [`MetricsExporter`] for `api`, refreshing every [`DEFAULT_INTERVAL`]. no request is done until the first refresh.
+    pub fn new(api: QbitApi) -> Self {
+        let sync = MainDataSync::new(api.clone());
+
+        Self { api, sync, interval: DEFAULT_INTERVAL, metrics: render_down() }
+    }
+
+    /// ## Usage
+    /// sets how often [`MetricsExporter::serve()`] refreshes the metrics.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// ## Usage
+    /// returns how often [`MetricsExporter::serve()`] refreshes the metrics.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// ## Usage
+    /// returns the metrics of the last refresh, in the Prometheus text format.
+    pub fn metrics(&self) -> &str {
+        &self.metrics
+    }
+
+    /// ## Usage
+    /// reads the transfer info and the main data again, and renders the metrics.
+    ///
+    /// ## Errors
+    /// returns the [`Error`] of the failed request; the metrics are then only `qbittorrent_up 0`,
+    /// and the next refresh starts again from a full snapshot of the main data.
+    pub async fn refresh(&mut self) -> Result<&str, Error> {
+        match self.collect().await {
+            Ok(metrics) => {
+                self.metrics = metrics;
+                Ok(&self.metrics)
+            }
+            Err(e) => {
+                self.metrics = render_down();
+                self.sync.reset();
+                Err(e)
+            }
+        }
+    }
+
+    async fn collect(&mut self) -> Result<String, Error> {
+        let transfer = self.api.transfer_get_global_transfer_info().await?;
+        // `/transfer/info` has the same fields as the server state, so it's read as one.
+        let transfer: ServerState = serde_json::from_value(transfer).map_err(|e| Error::build(ErrorType::JsonSerdeError(Box::new(e)), None).with_endpoint("/transfer/info"))?;
+        let state = self.sync.update().await?;
+
+        Ok(render(&transfer, state))
+    }
+}
+
+/// the metrics when qbittorrent can't be read.
+fn render_down() -> String {
+    let mut out = Exposition::default();
+    out.metric("qbittorrent_up", "whether the last refresh of the metrics succeeded.", "gauge", [(None, 0.0)]);
+    out.0
+}
+
+/// renders the metrics from the transfer info (for what changes the most) and the main data (for everything else).
+fn render(transfer: &ServerState, state: &SyncState) -> String {
+    let server = state.server_state();
+    let mut out = Exposition::default();
+
+    out.metric("qbittorrent_up", "whether the last refresh of the metrics succeeded.", "gauge", [(None, 1.0)]);
+    out.metric(
+        "qbittorrent_connection_status",
+        "connection status of qbittorrent: connected, firewalled or disconnected.",
+        "gauge",
+        [(Some(("status", transfer.connection_status.as_str())), 1.0)],
+    );
+    out.metric("qbittorrent_download_speed_bytes_per_second", "global download rate, in bytes per second.", "gauge", [(None, transfer.dl_info_speed as f64)]);
+    out.metric("qbittorrent_upload_speed_bytes_per_second", "global upload rate, in bytes per second.", "gauge", [(None, transfer.up_info_speed as f64)]);
+    out.metric("qbittorrent_download_limit_bytes_per_second", "global download limit, in bytes per second (0 if there's none).", "gauge", [(None, transfer.dl_rate_limit as f64)]);
+    out.metric("qbittorrent_upload_limit_bytes_per_second", "global upload limit, in bytes per second (0 if there's none).", "gauge", [(None, transfer.up_rate_limit as f64)]);
+    out.metric("qbittorrent_session_downloaded_bytes_total", "data downloaded this session, in bytes.", "counter", [(None, transfer.dl_info_data as f64)]);
+    out.metric("qbittorrent_session_uploaded_bytes_total", "data uploaded this session, in bytes.", "counter", [(None, transfer.up_info_data as f64)]);
+    out.metric("qbittorrent_alltime_downloaded_bytes_total", "data downloaded since the first start of qbittorrent, in bytes.", "counter", [(None, server.alltime_dl as f64)]);
+    out.metric("qbittorrent_alltime_uploaded_bytes_total", "data uploaded since the first start of qbittorrent, in bytes.", "counter", [(None, server.alltime_ul as f64)]);
+    out.metric("qbittorrent_dht_nodes", "number of DHT nodes connected to.", "gauge", [(None, transfer.dht_nodes as f64)]);
+    out.metric("qbittorrent_free_disk_space_bytes", "free space on the disk of the default save path, in bytes.", "gauge", [(None, server.free_space_on_disk as f64)]);
+    out.metric("qbittorrent_peer_connections", "total number of peer connections.", "gauge", [(None, server.total_peer_connections as f64)]);
+    out.metric("qbittorrent_global_ratio", "global share ratio.", "gauge", [(None, server.global_ratio.parse().unwrap_or(0.0))]);
+
+    // every state is exported, even without torrents in it, so that its series drops to 0 instead of disappearing.
+    let state_name = |state: TorrentState| serde_json::to_value(state).ok().and_then(|state| state.as_str().map(str::to_string)).unwrap_or_default();
+    let mut states: BTreeMap<String, f64> = TorrentState::ALL.into_iter().map(|state| (state_name(state), 0.0)).collect();
+    let mut categories: BTreeMap<&str, f64> = state.categories().keys().map(|name| (name.as_str(), 0.0)).collect();
+    let mut working: BTreeMap<&str, f64> = BTreeMap::new();
+
+    for torrent in state.torrents().values() {
+        *states.entry(state_name(torrent.state)).or_default() += 1.0;
+        *categories.entry(torrent.category.as_str()).or_default() += 1.0;
+
+        if !torrent.tracker.is_empty() {
+            *working.entry(torrent.tracker.as_str()).or_default() += 1.0;
+        }
+    }
+
+    let trackers: BTreeMap<&str, f64> = state.trackers().iter().map(|(url, hashes)| (url.as_str(), hashes.len() as f64)).collect();
+    let working = trackers.keys().map(|url| (Some(("tracker", *url)), working.get(url).copied().unwrap_or(0.0)));
+
+    out.metric("qbittorrent_torrents", "number of torrents, by state.", "gauge", states.iter().map(|(state, count)| (Some(("state", state.as_str())), *count)));
+    out.metric("qbittorrent_category_torrents", "number of torrents, by category (empty for the uncategorized ones).", "gauge", categories.iter().map(|(category, count)| (Some(("category", *category)), *count)));
+    out.metric("qbittorrent_tracker_torrents", "number of torrents using the tracker.", "gauge", trackers.iter().map(|(url, count)| (Some(("tracker", *url)), *count)));
+    out.metric("qbittorrent_tracker_working_torrents", "number of torrents for which the tracker is the one currently working.", "gauge", working);
+
+    out.0
+}
+
+/// a document in the Prometheus text exposition format.
+#[derive(Default)]
+struct Exposition(String);
+impl Exposition {
+    /// writes a metric with its `HELP` and `TYPE` lines, and a sample for each (label, value) pair.
+    fn metric<'a>(&mut self, name: &str, help: &str, kind: &str, samples: impl IntoIterator<Item = (Option<(&'a str, &'a str)>, f64)>) {
+        // writing to a String never fails.
+        let _ = writeln!(self.0, "# HELP {} {}", name, help);
+        let _ = writeln!(self.0, "# TYPE {} {}", name, kind);
+
+        for (label, value) in samples {
+            let _ = match label {
+                Some((label, label_value)) => writeln!(self.0, "{}{{{}=\"{}\"}} {}", name, label, escape(label_value), value),
+                None => writeln!(self.0, "{} {}", name, value),
+            };
+        }
+    }
+}
+
+/// escapes a label value: backslashes, double quotes and newlines.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
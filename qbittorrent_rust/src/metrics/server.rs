@@ -0,0 +1,128 @@
+use std::{
+    pin::pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_timer::Delay;
+use futures_util::{
+    future::{select, Either},
+    stream::{FuturesUnordered, StreamExt},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use super::MetricsExporter;
+use crate::{error_handling::error_type::ErrorType, Error};
+
+/// how long a scrape can take before the connection is dropped.
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(5);
+/// the longest request head that's read; prometheus sends far less.
+const MAX_REQUEST_SIZE: usize = 8192;
+
+impl MetricsExporter {
+    /// ## Usage
+    /// binds `addr`, and serves the metrics at `/metrics` over http, refreshing them every [`MetricsExporter::interval()`].
+    /// alias `MetricsExporter::serve_listener(TcpListener::bind(addr).await?)`.
+    ///
+    /// ## Errors
+    /// returns an [`Error`] with error type [`ErrorType::MiscError`] if `addr` can't be bound, or if accepting a connection fails.
+    pub async fn serve(self, addr: impl ToSocketAddrs) -> Result<(), Error> {
+        let listener = TcpListener::bind(addr).await.map_err(|e| io_error("couldn't bind the metrics endpoint", e))?;
+        self.serve_listener(listener).await
+    }
+
+    /// ## Usage
+    /// serves the metrics at `/metrics` over http on `listener`, refreshing them every [`MetricsExporter::interval()`].
+    ///
+    /// the first refresh is done before accepting any connection; after it, the refreshes run alongside the scrapes,
+    /// which are answered concurrently with the metrics of the last finished refresh, so they never wait for qbittorrent or for each other.
+    /// a failed refresh doesn't stop the endpoint: it's exported as `qbittorrent_up 0`.
+    ///
+    /// ## Errors
+    /// returns an [`Error`] with error type [`ErrorType::MiscError`] if accepting a connection fails.
+    pub async fn serve_listener(mut self, listener: TcpListener) -> Result<(), Error> {
+        let _ = self.refresh().await;
+        let published = Mutex::new(Arc::<str>::from(self.metrics()));
+
+        let refreshing = async {
+            loop {
+                Delay::new(self.interval).await;
+                let _ = self.refresh().await;
+                *published.lock().unwrap() = Arc::from(self.metrics());
+            }
+        };
+
+        let (refreshing, accepting) = (pin!(refreshing), pin!(accept_scrapes(&listener, &published)));
+
+        match select(refreshing, accepting).await {
+            Either::Left(_) => Err(Error::build(ErrorType::MiscError("the metrics stopped being refreshed".to_string()), None)),
+            Either::Right((result, _)) => result,
+        }
+    }
+}
+
+/// accepts connections on `listener`, answering each with the `published` metrics of when it was accepted.
+async fn accept_scrapes(listener: &TcpListener, published: &Mutex<Arc<str>>) -> Result<(), Error> {
+    let mut scrapes = FuturesUnordered::new();
+
+    loop {
+        let connection = match scrapes.is_empty() {
+            true => listener.accept().await,
+            // accepting is cancel safe, so it can be restarted whenever a scrape ends.
+            false => match select(pin!(listener.accept()), scrapes.next()).await {
+                Either::Left((connection, _)) => connection,
+                Either::Right(_) => continue,
+            },
+        };
+
+        let (stream, _) = connection.map_err(|e| io_error("couldn't accept a connection to the metrics endpoint", e))?;
+        let metrics = published.lock().unwrap().clone();
+        scrapes.push(respond(stream, metrics));
+    }
+}
+
+fn io_error(message: &str, e: std::io::Error) -> Error {
+    Error::build(ErrorType::MiscError(format!("{}: {}", message, e)), None)
+}
+
+/// answers a single http request, giving up after [`SCRAPE_TIMEOUT`].
+async fn respond(mut stream: TcpStream, metrics: Arc<str>) {
+    let exchange = async {
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") && request.len() < MAX_REQUEST_SIZE {
+            let read = stream.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+        }
+
+        let request = String::from_utf8_lossy(&request);
+        let mut request_line = request.lines().next().unwrap_or_default().split(' ');
+        let (method, path) = (request_line.next().unwrap_or_default(), request_line.next().unwrap_or_default());
+        let path = path.split('?').next().unwrap_or_default();
+
+        let (status, body) = match (method, path) {
+            ("GET", "/metrics") => ("200 OK", &*metrics),
+            ("GET", _) => ("404 Not Found", "not found, the metrics are at /metrics.\n"),
+            _ => ("405 Method Not Allowed", ""),
+        };
+
+        let head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            body.len()
+        );
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(body.as_bytes()).await?;
+        stream.shutdown().await
+    };
+
+    // a scrape that failed or timed out is the scraper's problem: it'll try again.
+    let _ = select(pin!(exchange), Delay::new(SCRAPE_TIMEOUT)).await;
+}
@@ -0,0 +1,182 @@
+use serde_json::{json, Map, Value};
+
+use crate::core::transport::TransportResponse;
+
+use super::{json, not_found, ok, text, MockRequest, MockState, MOCK_VERSION, MOCK_WEB_API_VERSION};
+
+pub(super) fn handle(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    match endpoint {
+        "version" => text(200, MOCK_VERSION),
+        "webapiVersion" => text(200, MOCK_WEB_API_VERSION),
+        "buildInfo" => json(json!({
+            "qt": "6.4.2",
+            "libtorrent": "1.2.19.0",
+            "boost": "1.83.0",
+            "openssl": "3.1.4",
+            "zlib": "1.3",
+            "bitness": 64,
+            "platform": "linux",
+        })),
+        "shutdown" => {
+            state.shut_down = true;
+            ok()
+        }
+        "defaultSavePath" => text(200, state.preferences.get("save_path").and_then(Value::as_str).unwrap_or_default().to_string()),
+        "preferences" => json(Value::Object(state.preferences.clone())),
+        "setPreferences" => match request.param("json").map(serde_json::from_str::<Map<String, Value>>) {
+            Some(Ok(changes)) => {
+                // qbittorrent never sends the password back.
+                state.preferences.extend(changes.into_iter().filter(|(key, _)| key != "web_ui_password"));
+                ok()
+            }
+            _ => text(400, ""),
+        },
+        _ => not_found(),
+    }
+}
+
+/// the preferences of a fresh qbittorrent install.
+pub(super) fn default_preferences() -> Map<String, Value> {
+    serde_json::from_str(DEFAULT_PREFERENCES).expect("the default preferences are valid json")
+}
+
+const DEFAULT_PREFERENCES: &str = r#"{
+    "locale": "en",
+    "create_subfolder_enabled": true,
+    "start_paused_enabled": false,
+    "auto_delete_mode": 0,
+    "preallocate_all": false,
+    "incomplete_files_ext": false,
+    "auto_tmm_enabled": false,
+    "torrent_changed_tmm_enabled": true,
+    "save_path_changed_tmm_enabled": false,
+    "category_changed_tmm_enabled": false,
+    "save_path": "/downloads/",
+    "temp_path_enabled": false,
+    "temp_path": "/downloads/temp/",
+    "scan_dirs": {},
+    "export_dir": "",
+    "export_dir_fin": "",
+    "mail_notification_enabled": false,
+    "mail_notification_sender": "qBittorrent_notification@example.com",
+    "mail_notification_email": "",
+    "mail_notification_smtp": "smtp.changeme.com",
+    "mail_notification_ssl_enabled": false,
+    "mail_notification_auth_enabled": false,
+    "mail_notification_username": "",
+    "autorun_enabled": false,
+    "autorun_program": "",
+    "queueing_enabled": true,
+    "max_active_downloads": 3,
+    "max_active_torrents": 5,
+    "max_active_uploads": 3,
+    "dont_count_slow_torrents": false,
+    "slow_torrent_dl_rate_threshold": 2,
+    "slow_torrent_ul_rate_threshold": 2,
+    "slow_torrent_inactive_timer": 60,
+    "max_ratio_enabled": false,
+    "max_ratio": -1,
+    "max_ratio_act": 0,
+    "max_seeding_time_enabled": false,
+    "max_seeding_time": -1,
+    "listen_port": 6881,
+    "upnp": true,
+    "random_port": false,
+    "dl_limit": 0,
+    "up_limit": 0,
+    "alt_dl_limit": 10240,
+    "alt_up_limit": 10240,
+    "max_connec": 500,
+    "max_connec_per_torrent": 100,
+    "max_uploads": 20,
+    "max_uploads_per_torrent": 4,
+    "bittorrent_protocol": 0,
+    "limit_utp_rate": true,
+    "limit_tcp_overhead": false,
+    "limit_lan_peers": true,
+    "scheduler_enabled": false,
+    "schedule_from_hour": 8,
+    "schedule_from_min": 0,
+    "schedule_to_hour": 20,
+    "schedule_to_min": 0,
+    "scheduler_days": 0,
+    "dht": true,
+    "pex": true,
+    "lsd": true,
+    "encryption": 0,
+    "anonymous_mode": false,
+    "proxy_type": "None",
+    "proxy_ip": "0.0.0.0",
+    "proxy_port": 8080,
+    "proxy_peer_connections": false,
+    "proxy_auth_enabled": false,
+    "proxy_username": "",
+    "proxy_torrents_only": false,
+    "ip_filter_enabled": false,
+    "ip_filter_path": "",
+    "ip_filter_trackers": false,
+    "web_ui_domain_list": "*",
+    "web_ui_address": "*",
+    "web_ui_port": 8080,
+    "web_ui_upnp": false,
+    "web_ui_username": "admin",
+    "web_ui_csrf_protection_enabled": true,
+    "web_ui_clickjacking_protection_enabled": true,
+    "web_ui_secure_cookie_enabled": true,
+    "web_ui_max_auth_fail_count": 5,
+    "web_ui_ban_duration": 3600,
+    "web_ui_session_timeout": 3600,
+    "web_ui_host_header_validation_enabled": true,
+    "bypass_local_auth": false,
+    "bypass_auth_subnet_whitelist_enabled": false,
+    "bypass_auth_subnet_whitelist": "",
+    "alternative_webui_enabled": false,
+    "alternative_webui_path": "",
+    "use_https": false,
+    "dyndns_enabled": false,
+    "dyndns_service": 0,
+    "dyndns_username": "",
+    "dyndns_domain": "changeme.dyndns.org",
+    "rss_refresh_interval": 30,
+    "rss_max_articles_per_feed": 50,
+    "rss_processing_enabled": false,
+    "rss_auto_downloading_enabled": false,
+    "rss_download_repack_proper_episodes": true,
+    "rss_smart_episode_filters": "s(\\d+)e(\\d+)\n(\\d+)x(\\d+)\n(\\d{4}[.\\-]\\d{1,2}[.\\-]\\d{1,2})",
+    "add_trackers_enabled": false,
+    "add_trackers": "",
+    "web_ui_use_custom_http_headers_enabled": false,
+    "web_ui_custom_http_headers": "",
+    "announce_ip": "",
+    "announce_to_all_tiers": true,
+    "announce_to_all_trackers": false,
+    "async_io_threads": 10,
+    "banned_IPs": "",
+    "checking_memory_use": 32,
+    "current_interface_address": "",
+    "current_network_interface": "",
+    "disk_cache": -1,
+    "disk_cache_ttl": 60,
+    "embedded_tracker_port": 9000,
+    "enable_coalesce_read_write": true,
+    "enable_embedded_tracker": false,
+    "enable_multi_connections_from_same_ip": false,
+    "enable_os_cache": true,
+    "enable_upload_suggestions": false,
+    "file_pool_size": 500,
+    "outgoing_ports_max": 0,
+    "outgoing_ports_min": 0,
+    "recheck_completed_torrents": false,
+    "resolve_peer_countries": true,
+    "save_resume_data_interval": 60,
+    "send_buffer_low_watermark": 10,
+    "send_buffer_watermark": 500,
+    "send_buffer_watermark_factor": 50,
+    "socket_backlog_size": 30,
+    "stop_tracker_timeout": 5,
+    "upload_choking_algorithm": 1,
+    "upload_slots_behavior": 0,
+    "upnp_lease_duration": 0,
+    "utp_tcp_mixed_mode": 0,
+    "enable_piece_extent_affinity": false
+}"#;
@@ -0,0 +1,65 @@
+use serde_json::{json, Value};
+
+use crate::core::transport::TransportResponse;
+
+use super::{json, not_found, now, MockRequest, MockState, MOCK_VERSION};
+
+/// the main log and the peer log. ids start at 0 and grow by one with each message.
+#[derive(Debug)]
+pub(super) struct LogState {
+    main: Vec<Value>,
+    peers: Vec<Value>,
+}
+
+impl LogState {
+    pub(super) fn new() -> Self {
+        let mut log = Self { main: vec![], peers: vec![] };
+
+        log.push(1, format!("qBittorrent {} started", MOCK_VERSION));
+        log.push(2, "Using config directory: /config/qBittorrent/".to_string());
+        log.push(2, "Trying to listen on the following list of IP addresses: \"0.0.0.0:6881,[::]:6881\"".to_string());
+        log.push(4, "UPnP/NAT-PMP support: OFF".to_string());
+
+        log
+    }
+
+    /// adds a message to the main log. `level` is 1 (normal), 2 (info), 4 (warning) or 8 (critical).
+    pub(super) fn push(&mut self, level: u8, message: String) {
+        let id = self.main.len();
+        self.main.push(json!({ "id": id, "message": message, "timestamp": now(), "type": level }));
+    }
+
+    /// adds a peer to the peer log.
+    pub(super) fn push_peer(&mut self, ip: &str, blocked: bool, reason: &str) {
+        let id = self.peers.len();
+        self.peers.push(json!({ "id": id, "ip": ip, "timestamp": now(), "blocked": blocked, "reason": reason }));
+    }
+}
+
+pub(super) fn handle(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    let last_known_id = request.int("last_known_id").unwrap_or(-1);
+    let newer = |entry: &&Value| entry["id"].as_i64().unwrap_or_default() > last_known_id;
+
+    match endpoint {
+        "main" => {
+            let levels = [("normal", 1), ("info", 2), ("warning", 4), ("critical", 8)]
+                .into_iter()
+                .filter(|(name, _)| request.bool(name).unwrap_or(true))
+                .map(|(_, level)| level)
+                .collect::<Vec<i64>>();
+
+            json(Value::Array(
+                state
+                    .log
+                    .main
+                    .iter()
+                    .filter(newer)
+                    .filter(|entry| levels.contains(&entry["type"].as_i64().unwrap_or_default()))
+                    .cloned()
+                    .collect(),
+            ))
+        }
+        "peers" => json(Value::Array(state.log.peers.iter().filter(newer).cloned().collect())),
+        _ => not_found(),
+    }
+}
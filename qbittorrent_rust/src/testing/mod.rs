@@ -0,0 +1,460 @@
+//! a mock of the qbittorrent WebUI API, served on a local port, to test code that uses [`QbitApi`] without a real qbittorrent.
+//!
+//! requires the `testing` feature.
+
+mod app;
+mod log;
+mod rss;
+mod search;
+mod server;
+mod sync;
+mod torrents;
+mod transfer;
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE, SET_COOKIE};
+use serde_json::Value;
+
+use crate::{
+    core::{
+        api::QbitApi,
+        builder::QbitApiBuilder,
+        creds::{AuthMode, Credentials},
+        transport::TransportResponse,
+    },
+    Error,
+};
+
+pub use torrents::{MockFile, MockTorrent};
+
+/// the version of qbittorrent the mock pretends to be.
+pub const MOCK_VERSION: &str = "v4.6.7";
+/// the version of the WebUI API the mock pretends to serve.
+pub const MOCK_WEB_API_VERSION: &str = "2.9.3";
+
+/// ## Info
+/// a mock qbittorrent, answering the requests of a [`QbitApi`] from an in-memory state.
+///
+/// it's a real http server, listening on a free port of `127.0.0.1` (see [`MockQbittorrent::url()`]), so the requests go through
+/// the same [`crate::core::transport::ReqwestTransport`] as with a real qbittorrent. it answers on threads of its own:
+/// it works with any runtime, and with the blocking client too.
+/// every category of the API is implemented (`/auth`, `/app`, `/log`, `/sync`, `/transfer`, `/torrents`, `/rss` and `/search`),
+/// with the same json and the same status codes as qbittorrent, and the state changes the same way:
+/// eg: a torrent added with `torrents_add_torrent` shows up in `torrents_get_torrent_list` and in `sync_get_main_data`.
+///
+/// cloning a [`MockQbittorrent`] gives another handle to the same state.
+///
+/// ## Authentication
+/// by default, it only accepts the credentials `admin`/`adminadmin`, and answers `403 Forbidden` to requests without a valid session:
+/// change them with [`MockQbittorrent::with_credentials()`], or disable authentication with [`MockQbittorrent::without_auth()`].
+///
+/// ## Example
+/// ```
+/// # use qbittorrent_rust::{testing::{MockQbittorrent, MockTorrent}, TorrentListGetConfig};
+/// # async fn example() -> Result<(), qbittorrent_rust::Error> {
+/// let mock = MockQbittorrent::new().with_torrent(MockTorrent::new("8c212779b4abde7c6bc608063a0d008b7e40ce32", "debian.iso"));
+/// let api = mock.api().await?;
+///
+/// let torrents = api.torrents_get_torrent_list_typed(TorrentListGetConfig::new()).await?;
+/// assert_eq!(torrents[0].name, "debian.iso");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockQbittorrent {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockQbittorrent {
+    /// ## Usage
+    /// creates a new [`MockQbittorrent`], with no torrents, two search plugins and qbittorrent's default preferences.
+    pub fn new() -> Self {
+        Self { state: Arc::new(Mutex::new(MockState::new())) }
+    }
+
+    /// ## Usage
+    /// only accepts `username` and `password` when logging in.
+    pub fn with_credentials(self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.state().credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// ## Usage
+    /// accepts every request, even without a session (like qbittorrent does for whitelisted clients).
+    pub fn without_auth(self) -> Self {
+        self.state().credentials = None;
+        self
+    }
+
+    /// ## Usage
+    /// adds `torrent`, replacing any torrent with the same hash.
+    pub fn with_torrent(self, torrent: MockTorrent) -> Self {
+        self.state().torrents.insert(torrent);
+        self
+    }
+
+    /// ## Usage
+    /// returns the url of the mock (eg: `http://127.0.0.1:41235/`), to pass to [`QbitApi::new()`] or [`QbitApi::builder()`].
+    ///
+    /// the mock starts listening the first time it's called.
+    ///
+    /// ## Panics
+    /// panics if no port can be opened on `127.0.0.1`.
+    pub fn url(&self) -> String {
+        let mut state = self.state();
+        let addr = *state.addr.get_or_insert_with(|| server::start(self.clone()));
+
+        format!("http://{}/", addr)
+    }
+
+    /// ## Usage
+    /// returns a [`QbitApiBuilder`] that sends its requests to this mock, logging in with the mock's credentials.
+    pub fn builder(&self) -> QbitApiBuilder {
+        let auth = match &self.state().credentials {
+            Some((username, password)) => AuthMode::Credentials(Credentials::new(username.clone(), password.clone())),
+            None => AuthMode::NoAuth,
+        };
+
+        QbitApi::builder(self.url(), auth)
+    }
+
+    /// ## Usage
+    /// returns a [`QbitApi`] logged into this mock.
+    ///
+    /// ## Errors
+    /// returns an [`Error`] if logging in fails.
+    pub async fn api(&self) -> Result<QbitApi, Error> {
+        self.builder().build().await
+    }
+
+    /// ## Usage
+    /// returns the torrent with the hash `hash`, if there is one.
+    pub fn torrent(&self, hash: impl AsRef<str>) -> Option<MockTorrent> {
+        self.state().torrents.get(hash.as_ref()).cloned()
+    }
+
+    /// ## Usage
+    /// returns every torrent, in queue order.
+    pub fn torrents(&self) -> Vec<MockTorrent> {
+        self.state().torrents.iter().cloned().collect()
+    }
+
+    /// ## Usage
+    /// returns the current preferences, as returned by `/app/preferences`.
+    pub fn preferences(&self) -> Value {
+        Value::Object(self.state().preferences.clone())
+    }
+
+    /// ## Usage
+    /// returns the peers banned with `/transfer/banPeers`.
+    pub fn banned_peers(&self) -> Vec<String> {
+        self.state().transfer.banned_peers.clone()
+    }
+
+    /// ## Usage
+    /// returns whether `/app/shutdown` was called. once it is, the mock stops listening, so every request fails with
+    /// [`crate::error_handling::error_type::ErrorType::HostUnreachable`].
+    pub fn is_shut_down(&self) -> bool {
+        self.state().shut_down
+    }
+
+    /// ## Usage
+    /// invalidates every session, like a restart of qbittorrent would.
+    pub fn expire_sessions(&self) {
+        self.state().sessions.clear();
+    }
+
+    /// ## Usage
+    /// answers every request to `path` (eg: `"/torrents/info"`) with `status` and `body`, instead of the mock's own answer.
+    /// useful to test how unusual responses are handled.
+    pub fn respond_with(&self, path: impl Into<String>, status: u16, body: impl Into<String>) {
+        self.respond_with_sequence(path, [MockResponse::new(status, body)]);
+    }
+
+    /// ## Usage
+    /// answers the requests to `path` with each of `responses` in order, instead of the mock's own answer;
+    /// the last one is repeated once the others are used up.
+    /// useful to test retries, or responses with unusual headers.
+    pub fn respond_with_sequence(&self, path: impl Into<String>, responses: impl IntoIterator<Item = MockResponse>) {
+        self.state().overrides.insert(path.into(), responses.into_iter().collect());
+    }
+
+    /// ## Usage
+    /// undoes [`MockQbittorrent::respond_with()`] for `path`.
+    pub fn reset_response(&self, path: impl AsRef<str>) {
+        self.state().overrides.remove(path.as_ref());
+    }
+
+    /// ## Usage
+    /// returns the paths of every request received so far, in order (eg: `["/auth/login", "/torrents/info"]`).
+    pub fn requests(&self) -> Vec<String> {
+        self.state().requests.clone()
+    }
+
+    /// ## Usage
+    /// returns how many requests were made to `path`.
+    pub fn hits(&self, path: impl AsRef<str>) -> usize {
+        self.state().requests.iter().filter(|p| p.as_str() == path.as_ref()).count()
+    }
+
+    /// ## Usage
+    /// returns the highest number of requests that were being answered at the same time.
+    pub fn max_in_flight(&self) -> usize {
+        self.state().max_in_flight
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        // a panic in a test doesn't make the state unusable.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// the answer to `request`, and how long to wait before sending it.
+    fn answer(&self, request: MockRequest) -> (TransportResponse, Duration) {
+        let mut state = self.state();
+        state.requests.push(request.path.clone());
+
+        if let Some(queue) = state.overrides.get_mut(&request.path) {
+            let response = if queue.len() > 1 { queue.pop_front() } else { queue.front().cloned() };
+            if let Some(response) = response {
+                return (response.response, response.delay);
+            }
+        }
+
+        (state.respond(&request), Duration::ZERO)
+    }
+}
+
+impl Default for MockQbittorrent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ## Info
+/// a response given by [`MockQbittorrent::respond_with_sequence()`] instead of the mock's own answer.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    response: TransportResponse,
+    delay: Duration,
+}
+
+impl MockResponse {
+    /// ## Usage
+    /// creates a new [`MockResponse`] with `status` and `body`, and no headers.
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self { response: TransportResponse::new(status, body.into()), delay: Duration::ZERO }
+    }
+
+    /// ## Usage
+    /// adds the header `name: value` (eg: `Set-Cookie`) to the response.
+    ///
+    /// ## Panics
+    /// panics if `name` or `value` aren't a valid header name or value.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        let name = HeaderName::from_bytes(name.as_bytes()).expect("a valid header name");
+        self.response.headers.append(name, HeaderValue::from_str(value).expect("a valid header value"));
+        self
+    }
+
+    /// ## Usage
+    /// waits for `delay` before sending the response.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// the whole state of a [`MockQbittorrent`].
+#[derive(Debug)]
+struct MockState {
+    credentials: Option<(String, String)>,
+    sessions: HashSet<String>,
+    next_session: u64,
+    failed_logins: u32,
+    shut_down: bool,
+    preferences: serde_json::Map<String, Value>,
+    torrents: torrents::Torrents,
+    categories: BTreeMap<String, String>,
+    tags: Vec<String>,
+    transfer: transfer::TransferState,
+    log: log::LogState,
+    sync: sync::SyncHistory,
+    rss: rss::RssState,
+    search: search::SearchState,
+    overrides: HashMap<String, VecDeque<MockResponse>>,
+    requests: Vec<String>,
+    addr: Option<SocketAddr>,
+    in_flight: usize,
+    max_in_flight: usize,
+}
+
+impl MockState {
+    fn new() -> Self {
+        Self {
+            credentials: Some(("admin".to_string(), "adminadmin".to_string())),
+            sessions: HashSet::new(),
+            next_session: 1,
+            failed_logins: 0,
+            shut_down: false,
+            preferences: app::default_preferences(),
+            torrents: torrents::Torrents::default(),
+            categories: BTreeMap::new(),
+            tags: vec![],
+            transfer: transfer::TransferState::default(),
+            log: log::LogState::new(),
+            sync: sync::SyncHistory::default(),
+            rss: rss::RssState::default(),
+            search: search::SearchState::new(),
+            overrides: HashMap::new(),
+            requests: vec![],
+            addr: None,
+            in_flight: 0,
+            max_in_flight: 0,
+        }
+    }
+
+    /// the mock's own answer to `request`.
+    fn respond(&mut self, request: &MockRequest) -> TransportResponse {
+        if request.path == "/auth/login" {
+            return self.login(request);
+        }
+
+        if !self.is_authenticated(request) {
+            return text(403, "Forbidden");
+        }
+
+        let (category, endpoint) = request.path.trim_start_matches('/').split_once('/').unwrap_or_default();
+
+        match category {
+            "auth" if endpoint == "logout" => self.logout(request),
+            "app" => app::handle(self, endpoint, request),
+            "log" => log::handle(self, endpoint, request),
+            "sync" => sync::handle(self, endpoint, request),
+            "transfer" => transfer::handle(self, endpoint, request),
+            "torrents" => torrents::handle(self, endpoint, request),
+            "rss" => rss::handle(self, endpoint, request),
+            "search" => search::handle(self, endpoint, request),
+            _ => not_found(),
+        }
+    }
+
+    /// a request to the mock started being answered.
+    fn started(&mut self) {
+        self.in_flight += 1;
+        self.max_in_flight = self.max_in_flight.max(self.in_flight);
+    }
+
+    /// a request to the mock was answered.
+    fn finished(&mut self) {
+        self.in_flight -= 1;
+    }
+
+    fn is_authenticated(&self, request: &MockRequest) -> bool {
+        self.credentials.is_none() || request.sid.as_ref().is_some_and(|sid| self.sessions.contains(sid))
+    }
+
+    /// qbittorrent bans a client after 5 failed logins in a row.
+    fn login(&mut self, request: &MockRequest) -> TransportResponse {
+        if self.failed_logins >= 5 {
+            return text(403, "Your IP address has been banned after too many failed authentication attempts.");
+        }
+
+        let Some((username, password)) = &self.credentials else {
+            return text(200, "Ok.");
+        };
+
+        if request.param("username") != Some(username) || request.param("password") != Some(password) {
+            self.failed_logins += 1;
+            return text(200, "Fails.");
+        }
+
+        self.failed_logins = 0;
+        let sid = format!("mock-session-{}", self.next_session);
+        self.next_session += 1;
+        self.sessions.insert(sid.clone());
+
+        let mut response = text(200, "Ok.");
+        let cookie = HeaderValue::from_str(&format!("SID={}; HttpOnly; SameSite=Strict; path=/", sid)).expect("the session id is ascii");
+        response.headers.insert(SET_COOKIE, cookie);
+        response
+    }
+
+    fn logout(&mut self, request: &MockRequest) -> TransportResponse {
+        if let Some(sid) = &request.sid {
+            self.sessions.remove(sid);
+        }
+
+        ok()
+    }
+}
+
+/// a request to the mock, with every parameter (query string, form and multipart text fields) gathered together.
+#[derive(Debug)]
+struct MockRequest {
+    path: String,
+    params: HashMap<String, String>,
+    files: Vec<Vec<u8>>,
+    sid: Option<String>,
+}
+
+impl MockRequest {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    /// a `|` separated list of values (eg: `hashes`), empty if the parameter is missing.
+    fn list(&self, name: &str, separator: char) -> Vec<String> {
+        self.param(name)
+            .map(|value| value.split(separator).map(str::trim).filter(|v| !v.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    fn bool(&self, name: &str) -> Option<bool> {
+        self.param(name).and_then(|value| value.parse().ok())
+    }
+
+    fn int(&self, name: &str) -> Option<i64> {
+        self.param(name).and_then(|value| value.parse().ok())
+    }
+}
+
+fn text(status: u16, body: impl Into<Vec<u8>>) -> TransportResponse {
+    let mut response = TransportResponse::new(status, body);
+    response.headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain; charset=UTF-8"));
+    response
+}
+
+fn json(value: Value) -> TransportResponse {
+    let mut response = TransportResponse::new(200, value.to_string());
+    response.headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn ok() -> TransportResponse {
+    text(200, "")
+}
+
+fn not_found() -> TransportResponse {
+    text(404, "Not Found")
+}
+
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or_default()
+}
+
+/// a fake, but stable, 40 characters long hex hash of `seed` (like the sha1 info hash of a torrent).
+fn fake_hash(seed: &[u8]) -> String {
+    (0..3_u64)
+        .map(|round| {
+            let hash = seed.iter().fold(0xcbf29ce484222325_u64 ^ round, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3));
+            format!("{:016x}", hash)
+        })
+        .collect::<String>()[..40]
+        .to_string()
+}
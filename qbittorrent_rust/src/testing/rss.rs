@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::core::transport::TransportResponse;
+
+use super::{fake_hash, json, not_found, now, ok, text, MockRequest, MockState};
+
+/// the feeds and folders (as a tree, with paths separated by `\`), and the auto downloading rules.
+#[derive(Debug, Default)]
+pub(super) struct RssState {
+    root: BTreeMap<String, RssItem>,
+    rules: BTreeMap<String, Map<String, Value>>,
+}
+
+#[derive(Debug, Clone)]
+enum RssItem {
+    Folder(BTreeMap<String, RssItem>),
+    Feed(Feed),
+}
+
+#[derive(Debug, Clone)]
+struct Feed {
+    uid: String,
+    url: String,
+    articles: Vec<Map<String, Value>>,
+}
+
+impl Feed {
+    /// a feed with two articles, as if it had just been downloaded.
+    fn new(url: &str) -> Self {
+        let uid = fake_hash(url.as_bytes());
+        let uid = format!("{{{}-{}-{}-{}-{}}}", &uid[..8], &uid[8..12], &uid[12..16], &uid[16..20], &uid[20..32]);
+
+        let articles = (1..=2)
+            .map(|episode| {
+                let title = format!("Example.Show.S01E0{}.1080p.WEB.h264", episode);
+                let article = json!({
+                    "id": fake_hash(format!("{}{}", url, episode).as_bytes()),
+                    "title": title,
+                    "date": now(),
+                    "description": format!("episode {} of the example show.", episode),
+                    "link": format!("https://example.org/torrents/{}", episode),
+                    "torrentURL": format!("https://example.org/torrents/{}.torrent", title),
+                    "isRead": false,
+                });
+
+                article.as_object().cloned().unwrap_or_default()
+            })
+            .collect();
+
+        Self { uid, url: url.to_string(), articles }
+    }
+
+    fn to_json(&self, with_data: bool) -> Value {
+        let mut feed = json!({ "uid": self.uid, "url": self.url });
+
+        if with_data {
+            feed["title"] = json!("Example feed");
+            feed["lastBuildDate"] = json!("");
+            feed["isLoading"] = json!(false);
+            feed["hasError"] = json!(false);
+            feed["articles"] = json!(self.articles);
+        }
+
+        feed
+    }
+}
+
+impl RssState {
+    fn get(&self, path: &str) -> Option<&RssItem> {
+        let mut parts = path.split('\\');
+        let mut item = self.root.get(parts.next()?)?;
+
+        for part in parts {
+            match item {
+                RssItem::Folder(children) => item = children.get(part)?,
+                RssItem::Feed(_) => return None,
+            }
+        }
+
+        Some(item)
+    }
+
+    fn get_mut(&mut self, path: &str) -> Option<&mut RssItem> {
+        let mut parts = path.split('\\');
+        let mut item = self.root.get_mut(parts.next()?)?;
+
+        for part in parts {
+            match item {
+                RssItem::Folder(children) => item = children.get_mut(part)?,
+                RssItem::Feed(_) => return None,
+            }
+        }
+
+        Some(item)
+    }
+
+    /// the folder that contains (or would contain) `path`, and the name of the item in it.
+    fn parent_mut<'a>(&mut self, path: &'a str) -> Option<(&mut BTreeMap<String, RssItem>, &'a str)> {
+        match path.rsplit_once('\\') {
+            None => Some((&mut self.root, path)),
+            Some((parent, name)) => match self.get_mut(parent)? {
+                RssItem::Folder(children) => Some((children, name)),
+                RssItem::Feed(_) => None,
+            },
+        }
+    }
+
+    /// inserts `item` at `path`, unless something is there already or the parent folder doesn't exist.
+    fn insert(&mut self, path: &str, item: RssItem) -> bool {
+        if path.is_empty() {
+            return false;
+        }
+
+        match self.parent_mut(path) {
+            Some((folder, name)) if !name.is_empty() && !folder.contains_key(name) => {
+                folder.insert(name.to_string(), item);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn remove(&mut self, path: &str) -> Option<RssItem> {
+        let (folder, name) = self.parent_mut(path)?;
+        folder.remove(name)
+    }
+
+    /// every feed, with its full path.
+    fn feeds(&self) -> Vec<(String, &Feed)> {
+        fn walk<'a>(prefix: &str, folder: &'a BTreeMap<String, RssItem>, feeds: &mut Vec<(String, &'a Feed)>) {
+            for (name, item) in folder {
+                let path = if prefix.is_empty() { name.clone() } else { format!("{}\\{}", prefix, name) };
+
+                match item {
+                    RssItem::Folder(children) => walk(&path, children, feeds),
+                    RssItem::Feed(feed) => feeds.push((path, feed)),
+                }
+            }
+        }
+
+        let mut feeds = vec![];
+        walk("", &self.root, &mut feeds);
+        feeds
+    }
+}
+
+fn items_json(folder: &BTreeMap<String, RssItem>, with_data: bool) -> Value {
+    Value::Object(
+        folder
+            .iter()
+            .map(|(name, item)| {
+                let value = match item {
+                    RssItem::Folder(children) => items_json(children, with_data),
+                    RssItem::Feed(feed) => feed.to_json(with_data),
+                };
+                (name.clone(), value)
+            })
+            .collect(),
+    )
+}
+
+fn mark_read(item: &mut RssItem, article_id: Option<&str>) {
+    match item {
+        RssItem::Folder(children) => children.values_mut().for_each(|child| mark_read(child, article_id)),
+        RssItem::Feed(feed) => {
+            for article in feed.articles.iter_mut().filter(|article| article_id.is_none_or(|id| article["id"] == id)) {
+                article.insert("isRead".to_string(), json!(true));
+            }
+        }
+    }
+}
+
+/// a rule with every field set to qbittorrent's default.
+fn default_rule() -> Map<String, Value> {
+    let rule = json!({
+        "enabled": true,
+        "mustContain": "",
+        "mustNotContain": "",
+        "useRegex": false,
+        "episodeFilter": "",
+        "smartFilter": false,
+        "previouslyMatchedEpisodes": [],
+        "affectedFeeds": [],
+        "ignoreDays": 0,
+        "lastMatch": "",
+        "addPaused": null,
+        "assignedCategory": "",
+        "savePath": "",
+    });
+
+    rule.as_object().cloned().unwrap_or_default()
+}
+
+pub(super) fn handle(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    let rss = &mut state.rss;
+    let param = |name: &str| request.param(name).unwrap_or_default().to_string();
+
+    match endpoint {
+        "addFolder" => match rss.insert(&param("path"), RssItem::Folder(BTreeMap::new())) {
+            true => ok(),
+            false => text(409, "Failed to add folder"),
+        },
+        "addFeed" => {
+            let url = param("url");
+            let path = request.param("path").filter(|path| !path.is_empty()).unwrap_or(&url).to_string();
+
+            if url.is_empty() || rss.feeds().iter().any(|(_, feed)| feed.url == url) || !rss.insert(&path, RssItem::Feed(Feed::new(&url))) {
+                return text(409, "Failed to add feed");
+            }
+            ok()
+        }
+        "removeItem" => match rss.remove(&param("path")) {
+            Some(_) => ok(),
+            None => text(409, "Failed to remove item"),
+        },
+        "moveItem" => {
+            let (from, to) = (param("itemPath"), param("destPath"));
+
+            let Some(item) = rss.get(&from).cloned() else {
+                return text(409, "Failed to move item");
+            };
+
+            if to.starts_with(&format!("{}\\", from)) || !rss.insert(&to, item) {
+                return text(409, "Failed to move item");
+            }
+
+            rss.remove(&from);
+            ok()
+        }
+        "items" => json(items_json(&rss.root, request.bool("withData").unwrap_or(false))),
+        "markAsRead" => {
+            let article_id = request.param("articleId");
+            if let Some(item) = rss.get_mut(&param("itemPath")) {
+                mark_read(item, article_id);
+            }
+            ok()
+        }
+        "refreshItem" => ok(),
+
+        "setRule" => {
+            let Some(Ok(definition)) = request.param("ruleDef").map(serde_json::from_str::<Map<String, Value>>) else {
+                return text(400, "");
+            };
+
+            let mut rule = rss.rules.remove(&param("ruleName")).unwrap_or_else(default_rule);
+            rule.extend(definition);
+            rss.rules.insert(param("ruleName"), rule);
+            ok()
+        }
+        "renameRule" => match rss.rules.remove(&param("ruleName")) {
+            Some(rule) => {
+                rss.rules.insert(param("newRuleName"), rule);
+                ok()
+            }
+            None => text(409, "Failed to rename rule"),
+        },
+        "removeRule" => {
+            rss.rules.remove(&param("ruleName"));
+            ok()
+        }
+        "rules" => json(json!(rss.rules)),
+        "matchingArticles" => {
+            let Some(rule) = rss.rules.get(&param("ruleName")) else {
+                return json(json!({}));
+            };
+
+            let lowercase = |key: &str| rule.get(key).and_then(Value::as_str).unwrap_or_default().to_lowercase();
+            let (must_contain, must_not_contain) = (lowercase("mustContain"), lowercase("mustNotContain"));
+            let affected: Vec<&str> = rule.get("affectedFeeds").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).collect();
+
+            let matching: Map<String, Value> = rss
+                .feeds()
+                .into_iter()
+                .filter(|(_, feed)| affected.contains(&feed.url.as_str()))
+                .map(|(path, feed)| {
+                    let titles: Vec<&Value> = feed
+                        .articles
+                        .iter()
+                        .map(|article| &article["title"])
+                        .filter(|title| {
+                            let title = title.as_str().unwrap_or_default().to_lowercase();
+                            title.contains(&must_contain) && (must_not_contain.is_empty() || !title.contains(&must_not_contain))
+                        })
+                        .collect();
+
+                    (path, json!(titles))
+                })
+                .filter(|(_, titles)| titles.as_array().is_some_and(|titles| !titles.is_empty()))
+                .collect();
+
+            json(Value::Object(matching))
+        }
+        _ => not_found(),
+    }
+}
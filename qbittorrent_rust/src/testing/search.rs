@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use crate::core::transport::TransportResponse;
+
+use super::{fake_hash, json, not_found, ok, text, MockRequest, MockState};
+
+/// how many searches can run at once; qbittorrent answers `409 Conflict` to more.
+const MAX_RUNNING: usize = 5;
+
+/// the installed plugins, and the search jobs.
+#[derive(Debug)]
+pub(super) struct SearchState {
+    plugins: Vec<Value>,
+    jobs: BTreeMap<u64, Job>,
+    next_id: u64,
+}
+
+#[derive(Debug)]
+struct Job {
+    running: bool,
+    results: Vec<Value>,
+}
+
+impl SearchState {
+    pub(super) fn new() -> Self {
+        let plugins = vec![
+            plugin("legittorrents", "Legit Torrents", "http://www.legittorrents.info", true, &[("all", "All categories"), ("movies", "Movies"), ("music", "Music"), ("tv", "TV shows")]),
+            plugin("eztv", "EZTV", "https://eztvx.to/", false, &[("all", "All categories"), ("tv", "TV shows")]),
+        ];
+
+        Self { plugins, jobs: BTreeMap::new(), next_id: 1 }
+    }
+}
+
+fn plugin(name: &str, full_name: &str, url: &str, enabled: bool, categories: &[(&str, &str)]) -> Value {
+    json!({
+        "enabled": enabled,
+        "fullName": full_name,
+        "name": name,
+        "supportedCategories": categories.iter().map(|(id, name)| json!({ "id": id, "name": name })).collect::<Vec<Value>>(),
+        "url": url,
+        "version": "1.0",
+    })
+}
+
+/// three results for each plugin, named after the pattern.
+fn results(pattern: &str, plugins: &[&Value]) -> Vec<Value> {
+    plugins
+        .iter()
+        .flat_map(|plugin| {
+            let site = plugin["url"].as_str().unwrap_or_default().trim_end_matches('/').to_string();
+
+            (1..=3_u64).map(move |n| {
+                let file_name = format!("{} {} [{}]", pattern, n, plugin["name"].as_str().unwrap_or_default());
+
+                json!({
+                    "fileName": file_name,
+                    "fileUrl": format!("magnet:?xt=urn:btih:{}&dn={}", fake_hash(file_name.as_bytes()), n),
+                    "fileSize": n * 700 * 1024 * 1024,
+                    "nbSeeders": 100 / n,
+                    "nbLeechers": 10 / n,
+                    "siteUrl": site,
+                    "descrLink": format!("{}/details/{}", site, n),
+                })
+            })
+        })
+        .collect()
+}
+
+pub(super) fn handle(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    let search = &mut state.search;
+    let id = request.int("id").map(|id| id as u64);
+
+    match endpoint {
+        "plugins" => json(Value::Array(search.plugins.clone())),
+        "start" => {
+            if search.jobs.values().filter(|job| job.running).count() >= MAX_RUNNING {
+                return text(409, "");
+            }
+
+            let pattern = request.param("pattern").unwrap_or_default();
+            let names = request.list("plugins", '|');
+            let category = request.param("category").unwrap_or("all");
+
+            let plugins: Vec<&Value> = search
+                .plugins
+                .iter()
+                .filter(|plugin| match names.first().map(String::as_str) {
+                    Some("all") => true,
+                    Some("enabled") => plugin["enabled"] == true,
+                    _ => names.iter().any(|name| plugin["name"] == name.as_str()),
+                })
+                .filter(|plugin| {
+                    category == "all" || plugin["supportedCategories"].as_array().into_iter().flatten().any(|c| c["id"] == category)
+                })
+                .collect();
+
+            let job = Job { running: true, results: results(pattern, &plugins) };
+            let id = search.next_id;
+            search.next_id += 1;
+            search.jobs.insert(id, job);
+
+            json(json!({ "id": id }))
+        }
+        "stop" | "delete" => match id.filter(|id| search.jobs.contains_key(id)) {
+            Some(id) if endpoint == "stop" => {
+                if let Some(job) = search.jobs.get_mut(&id) {
+                    job.running = false;
+                }
+                ok()
+            }
+            Some(id) => {
+                search.jobs.remove(&id);
+                ok()
+            }
+            None => text(404, ""),
+        },
+        "status" => {
+            let status = |(id, job): (&u64, &Job)| json!({ "id": id, "status": if job.running { "Running" } else { "Stopped" }, "total": job.results.len() });
+
+            match id {
+                Some(id) => match search.jobs.get_key_value(&id) {
+                    Some(job) => json(json!([status(job)])),
+                    None => text(404, ""),
+                },
+                None => json(Value::Array(search.jobs.iter().map(status).collect())),
+            }
+        }
+        "results" => {
+            let Some(job) = id.and_then(|id| search.jobs.get(&id)) else {
+                return text(404, "");
+            };
+
+            let total = job.results.len() as i64;
+            let offset = request.int("offset").unwrap_or(0);
+            let offset = if offset < 0 { total + offset } else { offset };
+
+            if offset < 0 || offset > total {
+                return text(409, "");
+            }
+
+            let limit = request.int("limit").filter(|limit| *limit > 0).map_or(usize::MAX, |limit| limit as usize);
+            let results: Vec<&Value> = job.results.iter().skip(offset as usize).take(limit).collect();
+
+            json(json!({ "results": results, "status": if job.running { "Running" } else { "Stopped" }, "total": total }))
+        }
+        "installPlugin" => {
+            for source in request.list("sources", '|') {
+                let name = source.rsplit('/').next().unwrap_or(&source).trim_end_matches(".py").to_string();
+
+                if !search.plugins.iter().any(|plugin| plugin["name"] == name.as_str()) {
+                    search.plugins.push(plugin(&name, &name, &source, true, &[("all", "All categories")]));
+                }
+            }
+            ok()
+        }
+        "uninstallPlugin" => {
+            let names = request.list("names", '|');
+            search.plugins.retain(|plugin| !names.iter().any(|name| plugin["name"] == name.as_str()));
+            ok()
+        }
+        "enablePlugin" => {
+            let names = request.list("names", '|');
+            let enable = request.bool("enable").unwrap_or(false);
+
+            for plugin in search.plugins.iter_mut().filter(|plugin| names.iter().any(|name| plugin["name"] == name.as_str())) {
+                plugin["enabled"] = json!(enable);
+            }
+            ok()
+        }
+        "updatePlugins" => ok(),
+        _ => not_found(),
+    }
+}
@@ -0,0 +1,174 @@
+//! the http side of [`MockQbittorrent`]: a listener on `127.0.0.1`, answering each connection on a thread of its own.
+//!
+//! it only uses `std`, so the mock can be used from any runtime (or from none, with the blocking client).
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use reqwest::{header::CONTENT_LENGTH, StatusCode};
+
+use super::{MockQbittorrent, MockRequest};
+
+/// how long a connection can stay silent before it's dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// ## Usage
+/// starts answering the requests for `mock` on a new port, until `/app/shutdown` is called
+/// (the apis using the mock can outlive it). returns the address of the listener.
+///
+/// ## Panics
+/// panics if no port can be opened on `127.0.0.1`.
+pub(super) fn start(mock: MockQbittorrent) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("the mock can open a port on 127.0.0.1");
+    let addr = listener.local_addr().expect("the listener has an address");
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            // once qbittorrent is shut down, connections are refused.
+            if mock.is_shut_down() {
+                break;
+            }
+
+            if let Ok(stream) = stream {
+                let mock = mock.clone();
+                thread::spawn(move || handle(mock, stream, addr));
+            }
+        }
+    });
+
+    addr
+}
+
+fn handle(mock: MockQbittorrent, mut stream: TcpStream, addr: SocketAddr) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let Some(request) = read_request(&mut stream) else { return };
+
+    if mock.is_shut_down() {
+        return;
+    }
+
+    mock.state().started();
+    let (response, delay) = mock.answer(request);
+    thread::sleep(delay);
+    mock.state().finished();
+
+    if mock.is_shut_down() {
+        close(addr);
+    }
+
+    let status = StatusCode::from_u16(response.status).ok().and_then(|status| status.canonical_reason()).unwrap_or("Unknown");
+    let mut raw = format!("HTTP/1.1 {} {}\r\n", response.status, status).into_bytes();
+    for (name, value) in response.headers.iter().filter(|(name, _)| *name != CONTENT_LENGTH) {
+        raw.extend_from_slice(format!("{}: ", name).as_bytes());
+        raw.extend_from_slice(value.as_bytes());
+        raw.extend_from_slice(b"\r\n");
+    }
+    raw.extend_from_slice(format!("content-length: {}\r\nconnection: close\r\n\r\n", response.body.len()).as_bytes());
+    raw.extend_from_slice(&response.body);
+
+    let _ = stream.write_all(&raw);
+}
+
+/// wakes the listener up until it has stopped, so that no request is accepted after the answer to `/app/shutdown`.
+fn close(addr: SocketAddr) {
+    while TcpStream::connect(addr).is_ok() {
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// reads an http request, with its `Content-Length` long body (the only kind of body reqwest sends for the client's requests).
+fn read_request(stream: &mut TcpStream) -> Option<MockRequest> {
+    let mut raw = vec![];
+    let mut buf = [0; 8192];
+
+    let head_end = loop {
+        if let Some(i) = find(&raw, b"\r\n\r\n") {
+            break i + 4;
+        }
+
+        let n = stream.read(&mut buf).ok().filter(|n| *n > 0)?;
+        raw.extend_from_slice(&buf[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&raw[..head_end]).into_owned();
+    let mut lines = head.lines();
+    let target = lines.next()?.split_whitespace().nth(1)?.to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers.get("content-length").and_then(|len| len.parse().ok()).unwrap_or(0);
+    while raw.len() < head_end + content_length {
+        let n = stream.read(&mut buf).ok().filter(|n| *n > 0)?;
+        raw.extend_from_slice(&buf[..n]);
+    }
+    let body = &raw[head_end..head_end + content_length];
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let mut params: HashMap<String, String> = serde_urlencoded::from_str::<Vec<(String, String)>>(query).unwrap_or_default().into_iter().collect();
+    let mut files = vec![];
+
+    let content_type = headers.get("content-type").map(String::as_str).unwrap_or_default();
+    if content_type.starts_with("application/x-www-form-urlencoded") {
+        params.extend(serde_urlencoded::from_bytes::<Vec<(String, String)>>(body).unwrap_or_default());
+    } else if let Some(boundary) = content_type.split("boundary=").nth(1) {
+        multipart(body, boundary.trim_matches('"'), &mut params, &mut files);
+    }
+
+    let sid = headers
+        .get("cookie")
+        .and_then(|cookies| cookies.split(';').filter_map(|cookie| cookie.trim().split_once('=')).find(|(name, _)| *name == "SID"))
+        .map(|(_, value)| value.to_string());
+
+    Some(MockRequest {
+        path: path.strip_prefix("/api/v2").unwrap_or(path).to_string(),
+        params,
+        files,
+        sid,
+    })
+}
+
+/// splits a `multipart/form-data` body into its text fields (in `params`) and its files (in `files`).
+fn multipart(body: &[u8], boundary: &str, params: &mut HashMap<String, String>, files: &mut Vec<Vec<u8>>) {
+    let delimiter = format!("\r\n--{}", boundary);
+    // the first delimiter isn't preceded by a line break.
+    let mut rest = match body.strip_prefix(&delimiter.as_bytes()[2..]) {
+        Some(rest) => rest,
+        None => return,
+    };
+
+    // each part starts right after a delimiter, and the last delimiter is followed by `--`.
+    while !rest.starts_with(b"--") {
+        let Some(part) = rest.strip_prefix(b"\r\n") else { break };
+        let end = find(part, delimiter.as_bytes()).unwrap_or(part.len());
+        let (field, next) = part.split_at(end);
+        rest = &next[delimiter.len().min(next.len())..];
+
+        let Some(head_end) = find(field, b"\r\n\r\n") else { continue };
+        let head = String::from_utf8_lossy(&field[..head_end]);
+        let content = &field[head_end + 4..];
+
+        let Some(line) = head.lines().find(|line| line.to_lowercase().starts_with("content-disposition:")) else { continue };
+        let disposition = |key: &str| {
+            line.split(';').find_map(|param| param.trim().strip_prefix(key)?.strip_prefix("=\"")?.strip_suffix('"').map(str::to_string))
+        };
+
+        match (disposition("name"), disposition("filename")) {
+            (_, Some(_)) => files.push(content.to_vec()),
+            (Some(name), None) => {
+                params.insert(name, String::from_utf8_lossy(content).into_owned());
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
@@ -0,0 +1,222 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{json, Map, Value};
+
+use crate::core::transport::TransportResponse;
+
+use super::{json, not_found, text, MockRequest, MockState};
+
+/// how many past responses are kept to compute deltas; older `rid`s get a full update, like in qbittorrent.
+const KEPT_SNAPSHOTS: usize = 64;
+
+/// the responses already sent, by `rid`, to answer the next request with only what changed since.
+#[derive(Debug, Default)]
+pub(super) struct SyncHistory {
+    last_rid: u64,
+    main_data: BTreeMap<u64, Map<String, Value>>,
+    peers: BTreeMap<(String, u64), Map<String, Value>>,
+}
+
+impl SyncHistory {
+    fn next_rid(&mut self) -> u64 {
+        self.last_rid += 1;
+
+        while self.main_data.len() > KEPT_SNAPSHOTS {
+            self.main_data.pop_first();
+        }
+
+        while self.peers.len() > KEPT_SNAPSHOTS {
+            self.peers.pop_first();
+        }
+
+        self.last_rid
+    }
+}
+
+pub(super) fn handle(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    let rid = request.int("rid").unwrap_or(0).max(0) as u64;
+
+    match endpoint {
+        "maindata" => {
+            let snapshot = main_data(state);
+            let previous = state.sync.main_data.get(&rid).cloned();
+            let next_rid = state.sync.next_rid();
+            state.sync.main_data.insert(next_rid, snapshot.clone());
+
+            let mut response = match previous {
+                Some(previous) => main_data_delta(&previous, &snapshot),
+                None => {
+                    let mut full = snapshot;
+                    full.insert("full_update".to_string(), json!(true));
+                    full
+                }
+            };
+
+            response.insert("rid".to_string(), json!(next_rid));
+            json(Value::Object(response))
+        }
+        "torrentPeers" => {
+            let hash = request.param("hash").unwrap_or_default().to_string();
+            let Some(torrent) = state.torrents.get(&hash) else {
+                return text(404, "Torrent hash was not found");
+            };
+
+            let snapshot = torrent.peers_json();
+            let previous = state.sync.peers.get(&(hash.clone(), rid)).cloned();
+            let next_rid = state.sync.next_rid();
+            state.sync.peers.insert((hash, next_rid), snapshot.clone());
+
+            let mut response = Map::new();
+            response.insert("rid".to_string(), json!(next_rid));
+            response.insert("show_flags".to_string(), json!(true));
+
+            match previous {
+                Some(previous) => {
+                    let (changed, removed) = objects_delta(&previous, &snapshot);
+                    insert_non_empty(&mut response, "peers", Value::Object(changed));
+                    insert_non_empty(&mut response, "peers_removed", json!(removed));
+                }
+                None => {
+                    response.insert("full_update".to_string(), json!(true));
+                    response.insert("peers".to_string(), Value::Object(snapshot));
+                }
+            }
+
+            json(Value::Object(response))
+        }
+        _ => not_found(),
+    }
+}
+
+/// the full main data, as sent with `full_update`.
+fn main_data(state: &MockState) -> Map<String, Value> {
+    let queueing = state.preferences.get("queueing_enabled").and_then(Value::as_bool).unwrap_or(false);
+
+    let torrents: Map<String, Value> = state
+        .torrents
+        .info(queueing)
+        .into_iter()
+        .map(|mut torrent| {
+            let hash = torrent["hash"].as_str().unwrap_or_default().to_string();
+            if let Value::Object(map) = &mut torrent {
+                map.remove("hash");
+            }
+            (hash, torrent)
+        })
+        .collect();
+
+    let categories: Map<String, Value> = state
+        .categories
+        .iter()
+        .map(|(name, save_path)| (name.clone(), json!({ "name": name, "savePath": save_path })))
+        .collect();
+
+    let mut trackers: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for torrent in state.torrents.iter() {
+        for url in &torrent.trackers {
+            trackers.entry(url.clone()).or_default().push(torrent.hash.clone());
+        }
+    }
+
+    let server_state = json!({
+        "connection_status": "connected",
+        "dht_nodes": 312,
+        "dl_info_data": 0,
+        "dl_info_speed": 0,
+        "dl_rate_limit": state.transfer.dl_limit,
+        "up_info_data": 0,
+        "up_info_speed": 0,
+        "up_rate_limit": state.transfer.up_limit,
+        "alltime_dl": 0,
+        "alltime_ul": 0,
+        "free_space_on_disk": 500_000_000_000_i64,
+        "global_ratio": "0.00",
+        "queueing": queueing,
+        "use_alt_speed_limits": state.transfer.alt_speed_limits,
+        "use_subcategories": false,
+        "refresh_interval": 1500,
+        "total_peer_connections": state.torrents.iter().map(|torrent| torrent.peers.len()).sum::<usize>(),
+        "total_wasted_session": 0,
+        "average_time_queue": 0,
+        "queued_io_jobs": 0,
+        "total_buffers_size": 0,
+        "total_queued_size": 0,
+        "read_cache_hits": "0",
+        "read_cache_overload": "0",
+        "write_cache_overload": "0",
+    });
+
+    let mut data = Map::new();
+    data.insert("torrents".to_string(), Value::Object(torrents));
+    data.insert("categories".to_string(), Value::Object(categories));
+    data.insert("tags".to_string(), json!(state.tags));
+    data.insert("trackers".to_string(), json!(trackers));
+    data.insert("server_state".to_string(), server_state);
+    data
+}
+
+/// only what changed between two snapshots of the main data, with the keys qbittorrent uses.
+fn main_data_delta(previous: &Map<String, Value>, current: &Map<String, Value>) -> Map<String, Value> {
+    let object = |map: &Map<String, Value>, key: &str| map.get(key).and_then(Value::as_object).cloned().unwrap_or_default();
+    let mut delta = Map::new();
+
+    for key in ["torrents", "categories"] {
+        let (changed, removed) = objects_delta(&object(previous, key), &object(current, key));
+        insert_non_empty(&mut delta, key, Value::Object(changed));
+        insert_non_empty(&mut delta, &format!("{}_removed", key), json!(removed));
+    }
+
+    let tags = |map: &Map<String, Value>| -> BTreeSet<String> {
+        map.get("tags").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).map(str::to_string).collect()
+    };
+    let (old_tags, new_tags) = (tags(previous), tags(current));
+    insert_non_empty(&mut delta, "tags", json!(new_tags.difference(&old_tags).collect::<Vec<_>>()));
+    insert_non_empty(&mut delta, "tags_removed", json!(old_tags.difference(&new_tags).collect::<Vec<_>>()));
+
+    let (old_trackers, new_trackers) = (object(previous, "trackers"), object(current, "trackers"));
+    let changed_trackers: Map<String, Value> = new_trackers.iter().filter(|(url, hashes)| old_trackers.get(*url) != Some(hashes)).map(|(k, v)| (k.clone(), v.clone())).collect();
+    let removed_trackers: Vec<&String> = old_trackers.keys().filter(|url| !new_trackers.contains_key(*url)).collect();
+    insert_non_empty(&mut delta, "trackers", Value::Object(changed_trackers));
+    insert_non_empty(&mut delta, "trackers_removed", json!(removed_trackers));
+
+    let (old_server, new_server) = (object(previous, "server_state"), object(current, "server_state"));
+    insert_non_empty(&mut delta, "server_state", Value::Object(fields_delta(&old_server, &new_server)));
+
+    delta
+}
+
+/// for two maps of objects: the objects that were added or changed (with only their changed fields), and the keys that were removed.
+fn objects_delta(previous: &Map<String, Value>, current: &Map<String, Value>) -> (Map<String, Value>, Vec<String>) {
+    let changed = current
+        .iter()
+        .filter_map(|(key, value)| {
+            let fields = match (previous.get(key).and_then(Value::as_object), value.as_object()) {
+                (Some(old), Some(new)) => fields_delta(old, new),
+                _ => value.as_object().cloned().unwrap_or_default(),
+            };
+
+            (!fields.is_empty()).then(|| (key.clone(), Value::Object(fields)))
+        })
+        .collect();
+
+    let removed = previous.keys().filter(|key| !current.contains_key(*key)).cloned().collect();
+
+    (changed, removed)
+}
+
+fn fields_delta(previous: &Map<String, Value>, current: &Map<String, Value>) -> Map<String, Value> {
+    current.iter().filter(|(key, value)| previous.get(*key) != Some(value)).map(|(k, v)| (k.clone(), v.clone())).collect()
+}
+
+/// qbittorrent leaves out the keys with nothing to report.
+fn insert_non_empty(map: &mut Map<String, Value>, key: &str, value: Value) {
+    let empty = match &value {
+        Value::Object(object) => object.is_empty(),
+        Value::Array(array) => array.is_empty(),
+        _ => false,
+    };
+
+    if !empty {
+        map.insert(key.to_string(), value);
+    }
+}
@@ -0,0 +1,837 @@
+use serde_json::{json, Map, Value};
+
+use crate::core::transport::TransportResponse;
+
+use super::{fake_hash, json, not_found, now, ok, text, MockRequest, MockState};
+
+/// the default size of a piece, 4 MiB.
+const PIECE_SIZE: i64 = 4 * 1024 * 1024;
+
+/// ## Info
+/// a torrent of a [`super::MockQbittorrent`].
+///
+/// every field can be changed before adding it with [`super::MockQbittorrent::with_torrent()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockTorrent {
+    pub hash: String,
+    pub name: String,
+    /// from 0.0 to 1.0.
+    pub progress: f64,
+    pub paused: bool,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub save_path: String,
+    pub dl_limit: i64,
+    pub up_limit: i64,
+    pub ratio_limit: f64,
+    pub seeding_time_limit: i64,
+    pub inactive_seeding_time_limit: i64,
+    pub auto_tmm: bool,
+    pub seq_dl: bool,
+    pub f_l_piece_prio: bool,
+    pub force_start: bool,
+    pub super_seeding: bool,
+    pub trackers: Vec<String>,
+    pub web_seeds: Vec<String>,
+    pub files: Vec<MockFile>,
+    /// the addresses (`ip:port`) of the connected peers.
+    pub peers: Vec<String>,
+    pub added_on: i64,
+}
+
+impl MockTorrent {
+    /// ## Usage
+    /// creates a new [`MockTorrent`], not started yet, with a single 64 MiB file named `name` and one tracker.
+    pub fn new(hash: impl Into<String>, name: impl Into<String>) -> Self {
+        let name = name.into();
+
+        Self {
+            hash: hash.into(),
+            files: vec![MockFile::new(name.clone(), 64 * 1024 * 1024)],
+            name,
+            progress: 0.0,
+            paused: false,
+            category: String::new(),
+            tags: vec![],
+            save_path: "/downloads/".to_string(),
+            dl_limit: 0,
+            up_limit: 0,
+            ratio_limit: -2.0,
+            seeding_time_limit: -2,
+            inactive_seeding_time_limit: -2,
+            auto_tmm: false,
+            seq_dl: false,
+            f_l_piece_prio: false,
+            force_start: false,
+            super_seeding: false,
+            trackers: vec!["udp://tracker.opentrackr.org:1337/announce".to_string()],
+            web_seeds: vec![],
+            peers: vec![],
+            added_on: now(),
+        }
+    }
+
+    /// the sum of the sizes of the files.
+    pub fn size(&self) -> i64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+
+    fn pieces(&self) -> i64 {
+        ((self.size() + PIECE_SIZE - 1) / PIECE_SIZE).max(1)
+    }
+
+    fn state(&self) -> &'static str {
+        let completed = self.progress >= 1.0;
+
+        match (self.paused, self.force_start, completed) {
+            (true, _, true) => "pausedUP",
+            (true, _, false) => "pausedDL",
+            (false, true, true) => "forcedUP",
+            (false, true, false) => "forcedDL",
+            (false, false, true) => "stalledUP",
+            (false, false, false) => "downloading",
+        }
+    }
+
+    /// the torrent as listed by `/torrents/info`.
+    fn info(&self, priority: i64) -> Value {
+        let size = self.size();
+        let completed = (size as f64 * self.progress) as i64;
+
+        let mut info = json!({
+            "hash": self.hash,
+            "infohash_v1": self.hash,
+            "infohash_v2": "",
+            "name": self.name,
+            "state": self.state(),
+            "progress": self.progress,
+            "ratio": 0.0,
+            "max_ratio": self.ratio_limit.max(-1.0),
+            "ratio_limit": self.ratio_limit,
+            "size": size,
+            "total_size": size,
+            "amount_left": size - completed,
+            "completed": completed,
+            "downloaded": completed,
+            "downloaded_session": completed,
+            "uploaded": 0,
+            "uploaded_session": 0,
+            "dlspeed": 0,
+            "upspeed": 0,
+            "dl_limit": self.dl_limit,
+            "up_limit": self.up_limit,
+            "eta": if self.progress >= 1.0 { 8640000 } else { 3600 },
+            "added_on": self.added_on,
+            "completion_on": if self.progress >= 1.0 { self.added_on } else { -1 },
+            "last_activity": self.added_on,
+            "seen_complete": -1,
+            "time_active": 0,
+            "seeding_time": 0,
+            "max_seeding_time": self.seeding_time_limit.max(-1),
+            "seeding_time_limit": self.seeding_time_limit,
+            "inactive_seeding_time_limit": self.inactive_seeding_time_limit,
+        });
+
+        // split in two, the whole object is too big for a single `json!`.
+        let rest = json!({
+            "category": self.category,
+            "tags": self.tags.join(", "),
+            "tracker": self.trackers.first().cloned().unwrap_or_default(),
+            "trackers_count": self.trackers.len(),
+            "save_path": self.save_path,
+            "content_path": format!("{}{}", self.save_path, self.name),
+            "download_path": "",
+            "magnet_uri": format!("magnet:?xt=urn:btih:{}&dn={}", self.hash, self.name),
+            "priority": priority,
+            "num_seeds": 0,
+            "num_complete": 0,
+            "num_leechs": self.peers.len(),
+            "num_incomplete": self.peers.len(),
+            "availability": if self.peers.is_empty() { 0.0 } else { 1.0 },
+            "auto_tmm": self.auto_tmm,
+            "f_l_piece_prio": self.f_l_piece_prio,
+            "force_start": self.force_start,
+            "seq_dl": self.seq_dl,
+            "super_seeding": self.super_seeding,
+            "private": false,
+        });
+
+        if let (Value::Object(info), Value::Object(rest)) = (&mut info, rest) {
+            info.extend(rest);
+        }
+
+        info
+    }
+
+    fn properties(&self) -> Value {
+        let size = self.size();
+        let pieces = self.pieces();
+
+        json!({
+            "hash": self.hash,
+            "name": self.name,
+            "save_path": self.save_path,
+            "creation_date": self.added_on,
+            "piece_size": PIECE_SIZE,
+            "comment": "",
+            "created_by": "mktorrent 1.1",
+            "is_private": false,
+            "total_wasted": 0,
+            "total_uploaded": 0,
+            "total_uploaded_session": 0,
+            "total_downloaded": (size as f64 * self.progress) as i64,
+            "total_downloaded_session": (size as f64 * self.progress) as i64,
+            "total_size": size,
+            "up_limit": if self.up_limit == 0 { -1 } else { self.up_limit },
+            "dl_limit": if self.dl_limit == 0 { -1 } else { self.dl_limit },
+            "time_elapsed": 0,
+            "seeding_time": 0,
+            "nb_connections": self.peers.len(),
+            "nb_connections_limit": 100,
+            "share_ratio": 0.0,
+            "addition_date": self.added_on,
+            "completion_date": if self.progress >= 1.0 { self.added_on } else { -1 },
+            "last_seen": -1,
+            "dl_speed": 0,
+            "dl_speed_avg": 0,
+            "up_speed": 0,
+            "up_speed_avg": 0,
+            "eta": if self.progress >= 1.0 { 8640000 } else { 3600 },
+            "peers": self.peers.len(),
+            "peers_total": self.peers.len(),
+            "seeds": 0,
+            "seeds_total": 0,
+            "pieces_have": self.pieces_have(),
+            "pieces_num": pieces,
+            "reannounce": 1800,
+        })
+    }
+
+    fn pieces_have(&self) -> i64 {
+        (self.pieces() as f64 * self.progress).round() as i64
+    }
+
+    fn trackers_json(&self) -> Value {
+        let fixed = ["** [DHT] **", "** [PeX] **", "** [LSD] **"].into_iter().map(|url| {
+            json!({ "url": url, "status": 2, "tier": -1, "num_peers": 0, "num_seeds": 0, "num_leeches": 0, "num_downloaded": 0, "msg": "" })
+        });
+
+        let trackers = self.trackers.iter().enumerate().map(|(tier, url)| {
+            json!({ "url": url, "status": 2, "tier": tier, "num_peers": self.peers.len(), "num_seeds": 0, "num_leeches": self.peers.len(), "num_downloaded": 0, "msg": "" })
+        });
+
+        Value::Array(fixed.chain(trackers).collect())
+    }
+
+    fn files_json(&self, indexes: Option<Vec<usize>>) -> Value {
+        let mut offset = 0;
+
+        let files = self.files.iter().enumerate().map(|(index, file)| {
+            // pieces span file boundaries: a file covers every piece its bytes are in.
+            let range = [offset / PIECE_SIZE, (offset + file.size.max(1) - 1) / PIECE_SIZE];
+            offset += file.size;
+
+            json!({
+                "index": index,
+                "name": file.name,
+                "size": file.size,
+                "progress": self.progress,
+                "priority": file.priority,
+                "is_seed": self.progress >= 1.0,
+                "piece_range": range,
+                "availability": if self.peers.is_empty() { 0.0 } else { 1.0 },
+            })
+        });
+
+        Value::Array(
+            files
+                .enumerate()
+                .filter(|(index, _)| indexes.as_ref().is_none_or(|indexes| indexes.contains(index)))
+                .map(|(_, file)| file)
+                .collect(),
+        )
+    }
+
+    fn peer_json(&self, address: &str) -> Value {
+        let (ip, port) = address.rsplit_once(':').unwrap_or((address, "0"));
+
+        json!({
+            "ip": ip,
+            "port": port.parse::<u16>().unwrap_or_default(),
+            "client": "qBittorrent 4.6.7",
+            "peer_id_client": "-qB4670-",
+            "country": "",
+            "country_code": "",
+            "flags": "D X",
+            "flags_desc": "D = Currently downloading (interested and not choked)\nX = Peer from PEX",
+            "progress": 0.5,
+            "dl_speed": 0,
+            "up_speed": 0,
+            "downloaded": 0,
+            "uploaded": 0,
+            "connection": "BT",
+            "relevance": 1.0,
+            "files": self.files.first().map(|file| file.name.clone()).unwrap_or_default(),
+        })
+    }
+
+    /// the connected peers, as listed by `/sync/torrentPeers`.
+    pub(super) fn peers_json(&self) -> Map<String, Value> {
+        self.peers.iter().map(|address| (address.clone(), self.peer_json(address))).collect()
+    }
+}
+
+/// ## Info
+/// a file of a [`MockTorrent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockFile {
+    /// the path of the file, relative to the save path (eg: `"folder/file.mkv"`).
+    pub name: String,
+    pub size: i64,
+    /// `0` (don't download), `1` (normal), `6` (high) or `7` (maximal).
+    pub priority: i64,
+}
+
+impl MockFile {
+    /// ## Usage
+    /// creates a new [`MockFile`] with normal priority.
+    pub fn new(name: impl Into<String>, size: i64) -> Self {
+        Self { name: name.into(), size, priority: 1 }
+    }
+}
+
+/// the torrents, in queue order.
+#[derive(Debug, Default)]
+pub(super) struct Torrents {
+    list: Vec<MockTorrent>,
+}
+
+impl Torrents {
+    pub(super) fn get(&self, hash: &str) -> Option<&MockTorrent> {
+        self.list.iter().find(|torrent| torrent.hash.eq_ignore_ascii_case(hash))
+    }
+
+    fn get_mut(&mut self, hash: &str) -> Option<&mut MockTorrent> {
+        self.list.iter_mut().find(|torrent| torrent.hash.eq_ignore_ascii_case(hash))
+    }
+
+    pub(super) fn insert(&mut self, torrent: MockTorrent) {
+        match self.get_mut(&torrent.hash) {
+            Some(existing) => *existing = torrent,
+            None => self.list.push(torrent),
+        }
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &MockTorrent> {
+        self.list.iter()
+    }
+
+    /// the torrents selected by a `hashes` parameter: either `all`, or `|` separated hashes. unknown hashes are ignored.
+    fn select(&mut self, request: &MockRequest) -> impl Iterator<Item = &mut MockTorrent> {
+        let hashes = request.list("hashes", '|');
+        let all = hashes.iter().any(|hash| hash == "all");
+
+        self.list
+            .iter_mut()
+            .filter(move |torrent| all || hashes.iter().any(|hash| hash.eq_ignore_ascii_case(&torrent.hash)))
+    }
+
+    /// the 1-based position of the torrent in the queue, or `0` if queueing is disabled.
+    pub(super) fn info(&self, queueing: bool) -> Vec<Value> {
+        self.list
+            .iter()
+            .enumerate()
+            .map(|(i, torrent)| torrent.info(if queueing { i as i64 + 1 } else { 0 }))
+            .collect()
+    }
+}
+
+pub(super) fn handle(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    match endpoint {
+        "info" => info(state, request),
+        "add" => add(state, request),
+        "properties" => with_torrent(state, request, |torrent| json(torrent.properties())),
+        "trackers" => with_torrent(state, request, |torrent| json(torrent.trackers_json())),
+        "webseeds" => with_torrent(state, request, |torrent| {
+            json(Value::Array(torrent.web_seeds.iter().map(|url| json!({ "url": url })).collect()))
+        }),
+        "files" => {
+            let indexes = request.param("indexes").map(|indexes| indexes.split('|').filter_map(|i| i.parse().ok()).collect());
+            with_torrent(state, request, |torrent| json(torrent.files_json(indexes)))
+        }
+        "pieceStates" => with_torrent(state, request, |torrent| {
+            let have = torrent.pieces_have();
+            json(Value::Array((0..torrent.pieces()).map(|i| json!(if i < have { 2 } else { 0 })).collect()))
+        }),
+        "pieceHashes" => with_torrent(state, request, |torrent| {
+            json(Value::Array((0..torrent.pieces()).map(|i| json!(fake_hash(format!("{}{}", torrent.hash, i).as_bytes()))).collect()))
+        }),
+
+        "pause" | "stop" => for_each(state, request, |torrent| torrent.paused = true),
+        "resume" | "start" => for_each(state, request, |torrent| torrent.paused = false),
+        "recheck" | "reannounce" => ok(),
+        "delete" => {
+            let selected: Vec<String> = state.torrents.select(request).map(|torrent| torrent.hash.clone()).collect();
+            state.torrents.list.retain(|torrent| !selected.contains(&torrent.hash));
+            ok()
+        }
+
+        "increasePrio" | "decreasePrio" | "topPrio" | "bottomPrio" => prio(state, endpoint, request),
+
+        "addTrackers" => with_torrent_mut(state, request, |torrent| {
+            for url in request.list("urls", '\n') {
+                if !torrent.trackers.contains(&url) {
+                    torrent.trackers.push(url);
+                }
+            }
+            ok()
+        }),
+        "editTracker" => edit_tracker(state, request),
+        "removeTrackers" => with_torrent_mut(state, request, |torrent| {
+            let urls = request.list("urls", '|');
+            let before = torrent.trackers.len();
+            torrent.trackers.retain(|url| !urls.contains(url));
+
+            if torrent.trackers.len() == before {
+                return text(409, "");
+            }
+            ok()
+        }),
+        "addPeers" => add_peers(state, request),
+        "filePrio" => file_prio(state, request),
+
+        "downloadLimit" => limits(state, request, |torrent| torrent.dl_limit),
+        "uploadLimit" => limits(state, request, |torrent| torrent.up_limit),
+        "setDownloadLimit" => {
+            let limit = request.int("limit").unwrap_or_default().max(0);
+            for_each(state, request, |torrent| torrent.dl_limit = limit)
+        }
+        "setUploadLimit" => {
+            let limit = request.int("limit").unwrap_or_default().max(0);
+            for_each(state, request, |torrent| torrent.up_limit = limit)
+        }
+        "setShareLimits" => {
+            let (Some(ratio), Some(seeding), Some(inactive)) = (
+                request.param("ratioLimit").and_then(|ratio| ratio.parse::<f64>().ok()),
+                request.int("seedingTimeLimit"),
+                request.int("inactiveSeedingTimeLimit"),
+            ) else {
+                return text(400, "");
+            };
+
+            for_each(state, request, |torrent| {
+                torrent.ratio_limit = ratio;
+                torrent.seeding_time_limit = seeding;
+                torrent.inactive_seeding_time_limit = inactive;
+            })
+        }
+        "setLocation" => {
+            let location = request.param("location").unwrap_or_default().to_string();
+            if location.is_empty() {
+                return text(400, "Save path cannot be empty");
+            }
+            for_each(state, request, |torrent| torrent.save_path = location.clone())
+        }
+        "rename" => {
+            let name = request.param("name").unwrap_or_default().trim().to_string();
+            with_torrent_mut(state, request, |torrent| {
+                if name.is_empty() {
+                    return text(409, "Incorrect torrent name");
+                }
+                torrent.name = name.clone();
+                ok()
+            })
+        }
+
+        "setCategory" => {
+            let category = request.param("category").unwrap_or_default().to_string();
+            if !category.is_empty() && !state.categories.contains_key(&category) {
+                return text(409, "Incorrect category name");
+            }
+            for_each(state, request, |torrent| torrent.category = category.clone())
+        }
+        "categories" => json(Value::Object(
+            state
+                .categories
+                .iter()
+                .map(|(name, save_path)| (name.clone(), json!({ "name": name, "savePath": save_path })))
+                .collect(),
+        )),
+        "createCategory" | "editCategory" => {
+            let name = request.param("category").unwrap_or_default().to_string();
+            if name.is_empty() {
+                return text(400, "Category cannot be empty");
+            }
+
+            let exists = state.categories.contains_key(&name);
+            match (endpoint, exists) {
+                ("createCategory", true) => return text(409, "Incorrect category name"),
+                ("editCategory", false) => return text(409, "Editing category failed"),
+                _ => {}
+            }
+
+            state.categories.insert(name, request.param("savePath").unwrap_or_default().to_string());
+            ok()
+        }
+        "removeCategories" => {
+            let removed = request.list("categories", '\n');
+            state.categories.retain(|name, _| !removed.contains(name));
+
+            for torrent in state.torrents.list.iter_mut().filter(|torrent| removed.contains(&torrent.category)) {
+                torrent.category.clear();
+            }
+            ok()
+        }
+
+        "tags" => json(json!(state.tags)),
+        "createTags" => {
+            for tag in request.list("tags", ',') {
+                if !state.tags.contains(&tag) {
+                    state.tags.push(tag);
+                }
+            }
+            ok()
+        }
+        "deleteTags" => {
+            let removed = request.list("tags", ',');
+            state.tags.retain(|tag| !removed.contains(tag));
+
+            for torrent in state.torrents.list.iter_mut() {
+                torrent.tags.retain(|tag| !removed.contains(tag));
+            }
+            ok()
+        }
+
+        "setAutoManagement" => {
+            let enable = request.bool("enable").unwrap_or(false);
+            for_each(state, request, |torrent| torrent.auto_tmm = enable)
+        }
+        "toggleSequentialDownload" => for_each(state, request, |torrent| torrent.seq_dl = !torrent.seq_dl),
+        "toggleFirstLastPiecePrio" => for_each(state, request, |torrent| torrent.f_l_piece_prio = !torrent.f_l_piece_prio),
+        "setForceStart" => {
+            let value = request.bool("value").unwrap_or(false);
+            for_each(state, request, |torrent| torrent.force_start = value)
+        }
+        "setSuperSeeding" => {
+            let value = request.bool("value").unwrap_or(false);
+            for_each(state, request, |torrent| torrent.super_seeding = value)
+        }
+        "renameFile" | "renameFolder" => rename_path(state, endpoint, request),
+
+        _ => not_found(),
+    }
+}
+
+/// answers with `f(torrent)`, or `404 Not Found` if the `hash` parameter doesn't match any torrent.
+fn with_torrent(state: &MockState, request: &MockRequest, f: impl FnOnce(&MockTorrent) -> TransportResponse) -> TransportResponse {
+    match request.param("hash").and_then(|hash| state.torrents.get(hash)) {
+        Some(torrent) => f(torrent),
+        None => text(404, "Torrent hash was not found"),
+    }
+}
+
+fn with_torrent_mut(state: &mut MockState, request: &MockRequest, f: impl FnOnce(&mut MockTorrent) -> TransportResponse) -> TransportResponse {
+    match request.param("hash").and_then(|hash| state.torrents.get_mut(hash)) {
+        Some(torrent) => f(torrent),
+        None => text(404, "Torrent hash was not found"),
+    }
+}
+
+/// applies `f` to every torrent selected by the `hashes` parameter.
+fn for_each(state: &mut MockState, request: &MockRequest, f: impl FnMut(&mut MockTorrent)) -> TransportResponse {
+    state.torrents.select(request).for_each(f);
+    ok()
+}
+
+fn limits(state: &mut MockState, request: &MockRequest, limit: impl Fn(&MockTorrent) -> i64) -> TransportResponse {
+    json(Value::Object(state.torrents.select(request).map(|torrent| (torrent.hash.clone(), json!(limit(torrent)))).collect()))
+}
+
+fn info(state: &MockState, request: &MockRequest) -> TransportResponse {
+    let queueing = state.preferences.get("queueing_enabled").and_then(Value::as_bool).unwrap_or(false);
+    let hashes = request.list("hashes", '|');
+
+    let mut torrents: Vec<Value> = state
+        .torrents
+        .info(queueing)
+        .into_iter()
+        .filter(|torrent| matches_filter(torrent, request.param("filter").unwrap_or("all")))
+        .filter(|torrent| request.param("category").is_none_or(|category| torrent["category"] == category))
+        .filter(|torrent| {
+            request.param("tag").is_none_or(|tag| {
+                torrent["tags"].as_str().unwrap_or_default().split(", ").any(|t| t == tag) || (tag.is_empty() && torrent["tags"] == "")
+            })
+        })
+        .filter(|torrent| hashes.is_empty() || hashes.iter().any(|hash| torrent["hash"] == hash.as_str()))
+        .collect();
+
+    if let Some(sort) = request.param("sort") {
+        torrents.sort_by(|a, b| compare(&a[sort], &b[sort]));
+    }
+
+    if request.bool("reverse").unwrap_or(false) {
+        torrents.reverse();
+    }
+
+    let len = torrents.len() as i64;
+    let offset = match request.int("offset").unwrap_or(0) {
+        offset if offset < 0 => (len + offset).max(0),
+        offset => offset.min(len),
+    } as usize;
+    let limit = request.int("limit").filter(|limit| *limit > 0).map_or(usize::MAX, |limit| limit as usize);
+
+    json(Value::Array(torrents.into_iter().skip(offset).take(limit).collect()))
+}
+
+fn matches_filter(torrent: &Value, filter: &str) -> bool {
+    let state = torrent["state"].as_str().unwrap_or_default();
+    let completed = state.ends_with("UP");
+    let paused = state.starts_with("paused") || state.starts_with("stopped");
+
+    match filter {
+        "downloading" => !completed && !paused,
+        "seeding" => completed && !paused,
+        "completed" => completed,
+        "paused" | "stopped" => paused,
+        "resumed" | "running" => !paused,
+        "active" => state == "downloading" || state.starts_with("forced"),
+        "inactive" => state != "downloading" && !state.starts_with("forced"),
+        "stalled" => state.starts_with("stalled"),
+        "stalled_uploading" => state == "stalledUP",
+        "stalled_downloading" => state == "stalledDL",
+        "errored" => state == "error" || state == "missingFiles",
+        _ => true,
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> std::cmp::Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().unwrap_or_default().total_cmp(&b.as_f64().unwrap_or_default()),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        _ => a.as_str().unwrap_or_default().to_lowercase().cmp(&b.as_str().unwrap_or_default().to_lowercase()),
+    }
+}
+
+fn add(state: &mut MockState, request: &MockRequest) -> TransportResponse {
+    let mut added = vec![];
+
+    for bytes in &request.files {
+        if !bytes.windows(6).any(|window| window == b"4:info") {
+            return text(415, "Torrent file is not valid.");
+        }
+
+        let name = bencoded_name(bytes).unwrap_or_else(|| "torrent".to_string());
+        added.push(MockTorrent::new(fake_hash(bytes), name));
+    }
+
+    for url in request.list("urls", '\n') {
+        added.push(from_url(&url));
+    }
+
+    if added.is_empty() {
+        return text(200, "Fails.");
+    }
+
+    let default_save_path = state.preferences.get("save_path").and_then(Value::as_str).unwrap_or("/downloads/").to_string();
+    let default_paused = state.preferences.get("start_paused_enabled").and_then(Value::as_bool).unwrap_or(false);
+
+    for mut torrent in added {
+        if state.torrents.get(&torrent.hash).is_some() {
+            continue;
+        }
+
+        torrent.save_path = request.param("savepath").unwrap_or(&default_save_path).to_string();
+        torrent.category = request.param("category").unwrap_or_default().to_string();
+        torrent.tags = request.list("tags", ',');
+        torrent.paused = request.bool("paused").or(request.bool("stopped")).unwrap_or(default_paused);
+        torrent.dl_limit = request.int("dlLimit").unwrap_or_default();
+        torrent.up_limit = request.int("upLimit").unwrap_or_default();
+        torrent.auto_tmm = request.bool("autoTMM").unwrap_or(false);
+        torrent.seq_dl = request.bool("sequentialDownload").unwrap_or(false);
+        torrent.f_l_piece_prio = request.bool("firstLastPiecePrio").unwrap_or(false);
+
+        if let Some(ratio) = request.param("ratioLimit").and_then(|ratio| ratio.parse().ok()) {
+            torrent.ratio_limit = ratio;
+        }
+
+        if let Some(seeding_time) = request.int("seedingTimeLimit") {
+            torrent.seeding_time_limit = seeding_time;
+        }
+
+        if let Some(rename) = request.param("rename").filter(|rename| !rename.is_empty()) {
+            torrent.name = rename.to_string();
+        }
+
+        for tag in &torrent.tags {
+            if !state.tags.contains(tag) {
+                state.tags.push(tag.clone());
+            }
+        }
+
+        if !torrent.category.is_empty() && !state.categories.contains_key(&torrent.category) {
+            state.categories.insert(torrent.category.clone(), String::new());
+        }
+
+        state.log.push(2, format!("Added new torrent. Torrent: \"{}\"", torrent.name));
+        state.torrents.insert(torrent);
+    }
+
+    text(200, "Ok.")
+}
+
+/// a torrent added from a magnet link (`xt=urn:btih:...`, `dn=...`) or from the url of a .torrent file.
+fn from_url(url: &str) -> MockTorrent {
+    let param = |name: &str| {
+        url.split(['?', '&']).find_map(|pair| pair.strip_prefix(name).map(|value| value.to_string()))
+    };
+
+    match param("xt=urn:btih:") {
+        Some(hash) => MockTorrent::new(hash.to_lowercase(), param("dn=").unwrap_or_else(|| hash.clone())),
+        None => {
+            let name = url.rsplit('/').next().unwrap_or(url).trim_end_matches(".torrent");
+            MockTorrent::new(fake_hash(url.as_bytes()), name)
+        }
+    }
+}
+
+/// reads the `name` key of the `info` dictionary of a bencoded .torrent file.
+fn bencoded_name(bytes: &[u8]) -> Option<String> {
+    let start = bytes.windows(6).position(|window| window == b"4:name")? + 6;
+    let rest = &bytes[start..];
+    let colon = rest.iter().position(|b| *b == b':')?;
+    let len: usize = std::str::from_utf8(&rest[..colon]).ok()?.parse().ok()?;
+
+    rest.get(colon + 1..colon + 1 + len).map(|name| String::from_utf8_lossy(name).into_owned())
+}
+
+/// moves the selected torrents in the queue. qbittorrent refuses if queueing is disabled.
+fn prio(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    if !state.preferences.get("queueing_enabled").and_then(Value::as_bool).unwrap_or(false) {
+        return text(409, "Torrent queueing must be enabled");
+    }
+
+    let selected: Vec<String> = state.torrents.select(request).map(|torrent| torrent.hash.clone()).collect();
+    let list = &mut state.torrents.list;
+
+    match endpoint {
+        "increasePrio" => {
+            for i in 1..list.len() {
+                if selected.contains(&list[i].hash) && !selected.contains(&list[i - 1].hash) {
+                    list.swap(i - 1, i);
+                }
+            }
+        }
+        "decreasePrio" => {
+            for i in (0..list.len().saturating_sub(1)).rev() {
+                if selected.contains(&list[i].hash) && !selected.contains(&list[i + 1].hash) {
+                    list.swap(i, i + 1);
+                }
+            }
+        }
+        "topPrio" => list.sort_by_key(|torrent| !selected.contains(&torrent.hash)),
+        _ => list.sort_by_key(|torrent| selected.contains(&torrent.hash)),
+    }
+
+    ok()
+}
+
+fn edit_tracker(state: &mut MockState, request: &MockRequest) -> TransportResponse {
+    let orig_url = request.param("origUrl").unwrap_or_default().to_string();
+    let new_url = request.param("newUrl").unwrap_or_default().to_string();
+
+    if reqwest::Url::parse(&new_url).is_err() {
+        return text(400, "New tracker URL is invalid");
+    }
+
+    with_torrent_mut(state, request, |torrent| {
+        if torrent.trackers.contains(&new_url) {
+            return text(409, "New tracker URL already exists");
+        }
+
+        match torrent.trackers.iter_mut().find(|url| **url == orig_url) {
+            Some(url) => {
+                *url = new_url;
+                ok()
+            }
+            None => text(409, "Tracker not found"),
+        }
+    })
+}
+
+fn add_peers(state: &mut MockState, request: &MockRequest) -> TransportResponse {
+    let peers: Vec<String> = request
+        .list("peers", '|')
+        .into_iter()
+        .filter(|peer| peer.parse::<std::net::SocketAddr>().is_ok())
+        .collect();
+
+    if peers.is_empty() {
+        return text(400, "");
+    }
+
+    for torrent in state.torrents.select(request) {
+        for peer in &peers {
+            if !torrent.peers.contains(peer) {
+                torrent.peers.push(peer.clone());
+            }
+        }
+    }
+
+    ok()
+}
+
+fn file_prio(state: &mut MockState, request: &MockRequest) -> TransportResponse {
+    let priority = request.int("priority");
+    let ids: Vec<Option<usize>> = request.list("id", '|').iter().map(|id| id.parse().ok()).collect();
+
+    with_torrent_mut(state, request, |torrent| {
+        let Some(priority) = priority.filter(|priority| [0, 1, 6, 7].contains(priority)) else {
+            return text(400, "Priority is not valid");
+        };
+
+        if ids.iter().any(Option::is_none) {
+            return text(400, "File IDs must be integers");
+        }
+
+        if ids.iter().flatten().any(|id| *id >= torrent.files.len()) {
+            return text(409, "File IDs are not valid");
+        }
+
+        for id in ids.into_iter().flatten() {
+            torrent.files[id].priority = priority;
+        }
+
+        ok()
+    })
+}
+
+fn rename_path(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    let (Some(old_path), Some(new_path)) = (request.param("oldPath"), request.param("newPath")) else {
+        return text(400, "");
+    };
+
+    let (old_path, new_path) = (old_path.trim_end_matches('/').to_string(), new_path.trim_end_matches('/').to_string());
+
+    with_torrent_mut(state, request, |torrent| {
+        let rename = |name: &str| -> Option<String> {
+            if endpoint == "renameFile" {
+                (name == old_path).then(|| new_path.clone())
+            } else {
+                name.strip_prefix(&format!("{}/", old_path)).map(|rest| format!("{}/{}", new_path, rest))
+            }
+        };
+
+        let renamed: Vec<(usize, String)> = torrent.files.iter().enumerate().filter_map(|(i, file)| rename(&file.name).map(|name| (i, name))).collect();
+
+        let taken = renamed.iter().any(|(_, name)| torrent.files.iter().any(|file| &file.name == name));
+        if renamed.is_empty() || new_path.is_empty() || taken {
+            return text(409, "");
+        }
+
+        for (i, name) in renamed {
+            torrent.files[i].name = name;
+        }
+
+        ok()
+    })
+}
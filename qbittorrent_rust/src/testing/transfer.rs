@@ -0,0 +1,61 @@
+use serde_json::json;
+
+use crate::core::transport::TransportResponse;
+
+use super::{json, not_found, ok, text, MockRequest, MockState};
+
+/// the global speed limits (in bytes/s, 0 meaning unlimited) and the banned peers.
+#[derive(Debug, Default)]
+pub(super) struct TransferState {
+    pub(super) dl_limit: u64,
+    pub(super) up_limit: u64,
+    pub(super) alt_speed_limits: bool,
+    pub(super) banned_peers: Vec<String>,
+}
+
+pub(super) fn handle(state: &mut MockState, endpoint: &str, request: &MockRequest) -> TransportResponse {
+    let transfer = &mut state.transfer;
+
+    match endpoint {
+        "info" => json(json!({
+            "dl_info_speed": 0,
+            "dl_info_data": 0,
+            "up_info_speed": 0,
+            "up_info_data": 0,
+            "dl_rate_limit": transfer.dl_limit,
+            "up_rate_limit": transfer.up_limit,
+            "dht_nodes": 312,
+            "connection_status": "connected",
+        })),
+        "speedLimitsMode" => text(200, if transfer.alt_speed_limits { "1" } else { "0" }),
+        "toggleSpeedLimitsMode" => {
+            transfer.alt_speed_limits = !transfer.alt_speed_limits;
+            ok()
+        }
+        "downloadLimit" => text(200, transfer.dl_limit.to_string()),
+        "uploadLimit" => text(200, transfer.up_limit.to_string()),
+        "setDownloadLimit" | "setUploadLimit" => {
+            let Some(limit) = request.int("limit") else {
+                return text(400, "");
+            };
+
+            let limit = limit.max(0) as u64;
+            if endpoint == "setDownloadLimit" {
+                transfer.dl_limit = limit;
+            } else {
+                transfer.up_limit = limit;
+            }
+            ok()
+        }
+        "banPeers" => {
+            for peer in request.list("peers", '|') {
+                let ip = peer.rsplit_once(':').map_or(peer.as_str(), |(ip, _)| ip).trim_matches(['[', ']']).to_string();
+
+                state.log.push_peer(&ip, true, "Manually banned");
+                state.transfer.banned_peers.push(peer);
+            }
+            ok()
+        }
+        _ => not_found(),
+    }
+}
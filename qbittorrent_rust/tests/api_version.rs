@@ -0,0 +1,109 @@
+use qbittorrent_rust::{core::version::ApiVersion, error_handling::error_type::ErrorType};
+
+#[test]
+fn versions_are_parsed_and_ordered() {
+    assert_eq!("2.9.3".parse::<ApiVersion>().unwrap(), ApiVersion::new(2, 9, 3));
+    assert_eq!("v2.8".parse::<ApiVersion>().unwrap(), ApiVersion::new(2, 8, 0));
+    assert_eq!(" 2.11.2\n".parse::<ApiVersion>().unwrap().to_string(), "2.11.2");
+
+    for invalid in ["", "2", "2.x", "2.9.3.1", "v4.6.7-beta"] {
+        let err = invalid.parse::<ApiVersion>().unwrap_err();
+        assert!(matches!(err.err_type, ErrorType::UnexpectedResponse(_)), "`{}` was parsed", invalid);
+    }
+
+    // components compare as numbers, not as text.
+    assert!(ApiVersion::new(2, 10, 0) > ApiVersion::new(2, 9, 3));
+    assert!(ApiVersion::new(2, 7, 0).require(ApiVersion::new(2, 7, 0)).is_ok());
+
+    let err = ApiVersion::new(2, 6, 2).require(ApiVersion::new(2, 7, 0)).unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::Unsupported { required, actual } if required == ApiVersion::new(2, 7, 0) && actual == ApiVersion::new(2, 6, 2)));
+}
+
+#[test]
+fn minimum_versions() {
+    assert_eq!(ApiVersion::minimum_for("/torrents/renameFolder"), Some(ApiVersion::new(2, 7, 0)));
+    assert_eq!(ApiVersion::minimum_for("/torrents/info"), None);
+}
+
+#[cfg(all(feature = "testing", feature = "torrents"))]
+mod mock {
+    use qbittorrent_rust::{
+        core::version::ApiVersion,
+        error_handling::error_type::ErrorType,
+        testing::{MockQbittorrent, MockTorrent, MOCK_WEB_API_VERSION},
+        TorrentHash, TorrentHashesDesc, TorrentListGetConfig,
+    };
+
+    const DEBIAN: &str = "8c212779b4abde7c6bc608063a0d008b7e40ce32";
+
+    fn mock() -> MockQbittorrent {
+        MockQbittorrent::new().with_torrent(MockTorrent::new(DEBIAN, "debian.iso"))
+    }
+
+    #[tokio::test]
+    async fn the_version_is_detected_when_connecting() {
+        let mock = mock();
+        let api = mock.api().await.unwrap();
+
+        let expected: ApiVersion = MOCK_WEB_API_VERSION.parse().unwrap();
+        assert_eq!(api.cached_api_version(), Some(expected));
+        assert_eq!(api.api_version().await.unwrap(), expected);
+        assert_eq!(api.clone().cached_api_version(), Some(expected));
+        assert_eq!(mock.hits("/app/webapiVersion"), 1);
+    }
+
+    #[tokio::test]
+    async fn too_old_versions_are_refused_before_sending() {
+        let mock = mock();
+        let api = mock.builder().api_version(ApiVersion::new(2, 6, 2)).build().await.unwrap();
+
+        let err = api.torrents_rename_folder(TorrentHash::new("", DEBIAN), "debian", "debian-12").await.unwrap_err();
+
+        assert!(matches!(err.err_type, ErrorType::Unsupported { required, actual } if required == ApiVersion::new(2, 7, 0) && actual == ApiVersion::new(2, 6, 2)));
+        assert_eq!((err.method, err.endpoint.as_deref()), (Some("torrents_rename_folder"), Some("/torrents/renameFolder")));
+        assert_eq!(mock.hits("/torrents/renameFolder"), 0);
+        assert_eq!(mock.hits("/app/webapiVersion"), 0);
+
+        // only a non-default inactive seeding time limit needs qbittorrent 4.6.
+        api.torrents_set_torrent_share_limit(TorrentHashesDesc::All, 2.0, -2, -2).await.unwrap();
+        let err = api.torrents_set_torrent_share_limit(TorrentHashesDesc::All, 2.0, -2, 60).await.unwrap_err();
+        assert!(matches!(err.err_type, ErrorType::Unsupported { .. }));
+        assert_eq!(err.method, Some("torrents_set_torrent_share_limit"));
+
+        // categories could be created from the start, but only with a save path since 2.1.0.
+        let api = mock.builder().api_version(ApiVersion::new(2, 0, 1)).build().await.unwrap();
+        api.torrents_add_new_category("iso", None::<String>).await.unwrap();
+        let err = api.torrents_add_new_category("linux", Some("/downloads/linux")).await.unwrap_err();
+        assert!(matches!(err.err_type, ErrorType::Unsupported { required, .. } if required == ApiVersion::new(2, 1, 0)));
+        assert_eq!((err.method, err.endpoint.as_deref()), (Some("torrents_add_new_category"), Some("/torrents/createCategory")));
+        assert_eq!(mock.hits("/torrents/createCategory"), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_detections_are_retried_when_needed() {
+        let mock = mock();
+        mock.respond_with("/app/webapiVersion", 500, "");
+
+        let api = mock.api().await.unwrap();
+        assert_eq!(api.cached_api_version(), None);
+
+        // endpoints without a minimum version don't need it.
+        api.torrents_get_torrent_list_raw(TorrentListGetConfig::new()).await.unwrap();
+        assert_eq!(mock.hits("/app/webapiVersion"), 1);
+
+        // while it can't be detected, the requests are sent anyway, without asking for the version before each of them.
+        api.torrents_add_new_tags(vec!["iso"]).await.unwrap();
+        api.torrents_add_new_tags(vec!["linux"]).await.unwrap();
+        assert_eq!(mock.hits("/app/webapiVersion"), 1);
+
+        // asking for it explicitly always tries again.
+        assert!(api.api_version().await.is_err());
+        assert_eq!(mock.hits("/app/webapiVersion"), 2);
+
+        mock.respond_with("/app/webapiVersion", 200, "2.2.0");
+        assert_eq!(api.api_version().await.unwrap(), ApiVersion::new(2, 2, 0));
+        let err = api.torrents_add_new_tags(vec!["linux"]).await.unwrap_err();
+        assert!(matches!(err.err_type, ErrorType::Unsupported { .. }));
+        assert_eq!(api.cached_api_version(), Some(ApiVersion::new(2, 2, 0)));
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg(all(feature = "blocking", feature = "testing", feature = "app", feature = "transfer"))]
+
+use qbittorrent_rust::{blocking::QbitApi, core::creds::Credentials, error_handling::error_type::ErrorType, testing::MockQbittorrent};
+
+#[test]
+fn blocking_methods_return_the_responses() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/app/version", 200, "v5.0.0");
+    mock.respond_with("/transfer/downloadLimit", 200, "1024");
+
+    let api = QbitApi::new(mock.url(), Credentials::new("admin", "adminadmin")).unwrap();
+
+    assert_eq!(api.app_version().unwrap(), "v5.0.0");
+    assert_eq!(api.transfer_get_global_download_limit().unwrap(), 1024);
+}
+
+#[test]
+fn blocking_methods_return_the_errors() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/transfer/speedLimitsMode", 200, "garbage");
+
+    let api = QbitApi::new(mock.url(), Credentials::new("admin", "adminadmin")).unwrap();
+    let err = api.transfer_get_alternative_speed_limits().unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::UnexpectedResponse(_)));
+}
+
+#[test]
+fn clones_share_the_session() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/app/version", 200, "v5.0.0");
+
+    let api = QbitApi::new(mock.url(), Credentials::new("admin", "adminadmin")).unwrap();
+    let clone = api.clone();
+
+    let handle = std::thread::spawn(move || clone.app_version().unwrap());
+
+    assert_eq!(api.app_version().unwrap(), "v5.0.0");
+    assert_eq!(handle.join().unwrap(), "v5.0.0");
+    assert_eq!(mock.hits("/auth/login"), 1);
+}
+
+#[test]
+fn the_last_clone_can_be_dropped_inside_an_async_runtime() {
+    let mock = MockQbittorrent::new();
+    let api = QbitApi::new(mock.url(), Credentials::new("admin", "adminadmin")).unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    runtime.block_on(async move { drop(api) });
+}
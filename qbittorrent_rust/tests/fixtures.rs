@@ -0,0 +1,122 @@
+#![cfg(feature = "app")]
+
+use std::sync::Arc;
+
+use qbittorrent_rust::{
+    core::{
+        api::QbitApi,
+        creds::Credentials,
+        fixtures::{Fixture, FixtureRequest, FixtureResponse, RecordingTransport, ReplayTransport},
+    },
+    error_handling::error_type::ErrorType,
+};
+
+fn fixture(path: &str, body: &str) -> Fixture {
+    Fixture {
+        request: FixtureRequest { method: "POST".to_string(), path: path.to_string(), params: vec![], files: vec![] },
+        response: FixtureResponse { status: 200, headers: vec![], body: body.to_string() },
+    }
+}
+
+fn login() -> Fixture {
+    let mut login = fixture("/auth/login", "Ok.");
+    login.request.params = vec![("password".to_string(), "<redacted>".to_string()), ("username".to_string(), "admin".to_string())];
+    login.response.headers = vec![("set-cookie".to_string(), "SID=<redacted>; HttpOnly; path=/".to_string())];
+    login
+}
+
+async fn replay(fixtures: Vec<Fixture>) -> QbitApi {
+    QbitApi::builder("http://localhost:6011/", Credentials::new("admin", "any password works"))
+        .transport(Arc::new(ReplayTransport::new(fixtures)))
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn responses_are_served_in_order_then_repeated() {
+    let api = replay(vec![login(), fixture("/app/version", "v4.6.7"), fixture("/app/version", "v5.0.0")]).await;
+
+    assert_eq!(api.app_version().await.unwrap(), "v4.6.7");
+    assert_eq!(api.app_version().await.unwrap(), "v5.0.0");
+    assert_eq!(api.app_version().await.unwrap(), "v5.0.0");
+}
+
+#[tokio::test]
+async fn requests_that_were_not_recorded_fail() {
+    let api = replay(vec![login()]).await;
+
+    let err = api.app_version().await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::MiscError(_)));
+    assert_eq!(err.endpoint.as_deref(), Some("/app/version"));
+}
+
+#[tokio::test]
+async fn fixture_files_are_json() {
+    let json = serde_json::to_string(&vec![login(), fixture("/app/webapiVersion", "2.9.3")]).unwrap();
+    let path = std::env::temp_dir().join(format!("qbit-fixtures-{}.json", std::process::id()));
+    std::fs::write(&path, json).unwrap();
+
+    let api = QbitApi::builder("http://localhost:6011/", Credentials::new("admin", "secret"))
+        .replay_fixtures(path.to_string_lossy())
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(api.app_web_api_version().await.unwrap(), "2.9.3");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn recordings_are_written_when_saved() {
+    let dir = std::env::temp_dir().join(format!("qbit-unwritable-{}", std::process::id()));
+    let path = dir.join("fixtures.json").to_string_lossy().to_string();
+    let recording = Arc::new(RecordingTransport::new(Arc::new(ReplayTransport::new(vec![login(), fixture("/app/version", "v4.6.7")])), Some(&path)));
+
+    let api = QbitApi::builder("http://localhost:6011/", Credentials::new("admin", "secret")).transport(recording.clone()).build().await.unwrap();
+
+    // the directory doesn't exist, but that's only a problem once the fixtures are saved.
+    assert_eq!(api.app_version().await.unwrap(), "v4.6.7");
+    assert_eq!(recording.fixtures().len(), 2);
+    assert!(matches!(recording.save().unwrap_err().err_type, ErrorType::MiscError(_)));
+
+    std::fs::create_dir(&dir).unwrap();
+    recording.save().unwrap();
+    let saved: Vec<Fixture> = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(saved, recording.fixtures());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(all(feature = "testing", feature = "torrents"))]
+#[tokio::test]
+async fn record_then_replay() {
+    use qbittorrent_rust::{
+        testing::{MockQbittorrent, MockTorrent},
+        TorrentListGetConfig,
+    };
+
+    let path = std::env::temp_dir().join(format!("qbit-recording-{}.json", std::process::id()));
+    let path = path.to_string_lossy().to_string();
+
+    let mock = MockQbittorrent::new().with_torrent(MockTorrent::new("8c212779b4abde7c6bc608063a0d008b7e40ce32", "debian.iso"));
+    let recorded = mock.builder().record_fixtures(&path).build().await.unwrap();
+    let version = recorded.app_version().await.unwrap();
+    let torrents = recorded.torrents_get_torrent_list(TorrentListGetConfig::new()).await.unwrap();
+    drop(recorded);
+
+    let file = std::fs::read_to_string(&path).unwrap();
+    assert!(!file.contains("adminadmin"));
+    assert!(!file.contains("mock-session"));
+
+    let replayed = QbitApi::builder("http://qbittorrent.mock/", Credentials::new("admin", "adminadmin"))
+        .replay_fixtures(&path)
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(replayed.app_version().await.unwrap(), version);
+    assert_eq!(replayed.torrents_get_torrent_list(TorrentListGetConfig::new()).await.unwrap(), torrents);
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,57 @@
+#![cfg(all(feature = "testing", feature = "app"))]
+
+use std::time::{Duration, Instant};
+
+use futures_util::future::join_all;
+use qbittorrent_rust::{
+    core::{api::QbitApi, creds::Credentials},
+    testing::{MockQbittorrent, MockResponse},
+};
+
+#[tokio::test]
+async fn concurrent_requests_are_capped() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with_sequence("/app/version", [MockResponse::new(200, "v5.0.0").with_delay(Duration::from_millis(50))]);
+
+    let api = QbitApi::builder(mock.url(), Credentials::new("admin", "adminadmin"))
+        .max_concurrent_requests(2)
+        .build()
+        .await
+        .unwrap();
+
+    let results = join_all((0..8).map(|_| api.app_version())).await;
+
+    assert!(results.iter().all(|r| r.is_ok()));
+    assert_eq!(mock.hits("/app/version"), 8);
+    assert_eq!(mock.max_in_flight(), 2);
+}
+
+#[tokio::test]
+async fn requests_per_second_are_spread_out() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/app/version", 200, "v5.0.0");
+
+    let api = QbitApi::builder(mock.url(), Credentials::new("admin", "adminadmin"))
+        .max_requests_per_second(20.0)
+        .build()
+        .await
+        .unwrap();
+
+    let start = Instant::now();
+    let results = join_all((0..5).map(|_| api.app_version())).await;
+
+    assert!(results.iter().all(|r| r.is_ok()));
+    // the first request goes out right away, the other 4 are 50ms apart.
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn invalid_limits_are_rejected() {
+    let mock = MockQbittorrent::new();
+
+    let zero = QbitApi::builder(mock.url(), Credentials::new("admin", "adminadmin")).max_concurrent_requests(0).build().await;
+    let negative = QbitApi::builder(mock.url(), Credentials::new("admin", "adminadmin")).max_requests_per_second(-1.0).build().await;
+
+    assert!(zero.is_err());
+    assert!(negative.is_err());
+}
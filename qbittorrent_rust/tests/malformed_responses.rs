@@ -0,0 +1,150 @@
+#![cfg(all(feature = "testing", feature = "torrents", feature = "search", feature = "transfer", feature = "sync"))]
+
+use qbittorrent_rust::{error_handling::error_type::ErrorType, testing::MockQbittorrent, Categories, SearchPluginsSpec, TorrentHash, TorrentListGetConfig};
+
+fn hash() -> TorrentHash {
+    TorrentHash::new("test", "8c212779b4abde7c6bc608063a0d008b7e40ce32")
+}
+
+#[tokio::test]
+async fn search_start_without_id_is_an_error() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/search/start", 200, "{}");
+
+    let err = mock.api().await.unwrap().search_start("ubuntu", SearchPluginsSpec::All, Categories::All).await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::UnexpectedResponse(_)));
+    assert_eq!(err.endpoint.as_deref(), Some("/search/start"));
+    assert_eq!(err.body.as_deref(), Some("{}"));
+}
+
+#[tokio::test]
+async fn unhandled_status_codes_are_errors() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/search/start", 500, "boom");
+
+    let err = mock.api().await.unwrap().search_start("ubuntu", SearchPluginsSpec::All, Categories::All).await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::MiscNetError(500)));
+    assert_eq!(err.code, Some(500));
+    assert_eq!(err.body.as_deref(), Some("boom"));
+}
+
+#[tokio::test]
+async fn documented_status_codes_keep_their_reason() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/search/start", 409, "");
+
+    let err = mock.api().await.unwrap().search_start("ubuntu", SearchPluginsSpec::All, Categories::All).await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::Conflict(ref reason) if reason.contains("'Running' searches")));
+}
+
+#[tokio::test]
+async fn alternative_speed_limits_with_garbage_is_an_error() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    mock.respond_with("/transfer/speedLimitsMode", 200, "");
+    let err = api.transfer_get_alternative_speed_limits().await.unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::UnexpectedResponse(_)));
+
+    mock.respond_with("/transfer/speedLimitsMode", 200, "1");
+    assert_eq!(api.transfer_get_alternative_speed_limits().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn files_ids_with_malformed_contents_is_an_error() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    mock.respond_with("/torrents/files", 200, r#"{"name": "not a list"}"#);
+    let err = api.torrents_get_files_ids(hash()).await.unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::UnexpectedResponse(_)));
+
+    mock.respond_with("/torrents/files", 200, r#"[{"index": 0}]"#);
+    let err = api.torrents_get_files_ids(hash()).await.unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::UnexpectedResponse(_)));
+
+    mock.respond_with("/torrents/files", 200, "not json");
+    let err = api.torrents_get_files_ids(hash()).await.unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::JsonSerdeError(_)));
+}
+
+#[tokio::test]
+async fn files_ids_are_read_from_the_contents() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/torrents/files", 200, r#"[{"index": 0, "name": "a.mkv"}, {"index": 1, "name": "b.srt"}]"#);
+
+    let files = mock.api().await.unwrap().torrents_get_files_ids(hash()).await.unwrap();
+
+    assert_eq!(files.iter().map(|f| (f.id, f.name.as_str())).collect::<Vec<_>>(), vec![(0, "a.mkv"), (1, "b.srt")]);
+}
+
+#[tokio::test]
+async fn search_plugins_that_are_not_a_list_is_an_error() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/search/plugins", 200, r#"{"enabled": true}"#);
+
+    let err = mock.api().await.unwrap().search_get_search_plugins().await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::UnexpectedResponse(_)));
+}
+
+#[tokio::test]
+async fn search_plugins_without_a_name_are_skipped() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with(
+        "/search/plugins",
+        200,
+        r#"[
+            {"fullName": "Nameless", "supportedCategories": [{"id": "all", "name": "All categories"}]},
+            {"name": "eztv", "supportedCategories": [{"id": "tv", "name": "TV shows"}]}
+        ]"#,
+    );
+
+    let plugins = mock.api().await.unwrap().search_get_search_plugins().await.unwrap();
+
+    assert_eq!(plugins.len(), 1);
+    assert_eq!((plugins[0].get_name(), plugins[0].get_categories_ids()), ("eztv".to_string(), vec!["tv".to_string()]));
+}
+
+#[tokio::test]
+async fn missing_hash_is_reported_with_its_endpoint() {
+    let mock = MockQbittorrent::new();
+
+    let err = mock.api().await.unwrap().torrents_get_torrent_generic_properties(hash()).await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::TorrentHashNotFound));
+    assert_eq!(err.code, Some(404));
+    assert_eq!(err.endpoint.as_deref(), Some("/torrents/properties"));
+}
+
+#[tokio::test]
+async fn malformed_sync_data_is_an_error() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/sync/maindata", 200, r#"{"rid": "#);
+
+    let err = mock.api().await.unwrap().sync_get_main_data_delta(0).await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::JsonSerdeError(_)));
+}
+
+#[tokio::test]
+async fn json_errors_keep_the_method_the_endpoint_and_the_body() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    mock.respond_with("/torrents/info", 200, r#"[{"hash": 42}]"#);
+    let err = api.torrents_get_torrent_list_typed(TorrentListGetConfig::new()).await.unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::JsonSerdeError(_)));
+    assert_eq!(err.method, Some("torrents_get_torrent_list_typed"));
+    assert_eq!(err.endpoint.as_deref(), Some("/torrents/info"));
+    assert_eq!(err.body.as_deref(), Some(r#"[{"hash": 42}]"#));
+
+    mock.respond_with("/sync/maindata", 200, "not json");
+    let err = api.sync_get_main_data_delta(0).await.unwrap_err();
+    assert_eq!(err.method, Some("sync_get_main_data_delta"));
+    assert_eq!(err.endpoint.as_deref(), Some("/sync/maindata"));
+    assert_eq!(err.body.as_deref(), Some("not json"));
+}
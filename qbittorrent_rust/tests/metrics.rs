@@ -0,0 +1,128 @@
+#![cfg(all(feature = "metrics", feature = "testing"))]
+
+use std::time::Duration;
+
+use qbittorrent_rust::{
+    metrics::MetricsExporter,
+    testing::{MockQbittorrent, MockResponse, MockTorrent},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    time::{sleep, timeout},
+};
+
+const TRACKER: &str = "udp://tracker.opentrackr.org:1337/announce";
+
+fn mock() -> MockQbittorrent {
+    let mut debian = MockTorrent::new("8c212779b4abde7c6bc608063a0d008b7e40ce32", "debian.iso");
+    debian.category = "linux".to_string();
+
+    let mut arch = MockTorrent::new("c4b2b8a7e1d9f3a6b5c4d3e2f1a0b9c8d7e6f5a4", "arch.iso");
+    arch.paused = true;
+    arch.progress = 1.0;
+    arch.trackers.push("https://tracker.example.org/announce".to_string());
+
+    MockQbittorrent::new().with_torrent(debian).with_torrent(arch)
+}
+
+#[tokio::test]
+async fn refresh_renders_the_metrics() {
+    let mock = mock();
+    let api = mock.api().await.unwrap();
+    api.transfer_set_global_download_limit(1024).await.unwrap();
+
+    let mut exporter = MetricsExporter::new(api);
+    assert_eq!(exporter.metrics().lines().last(), Some("qbittorrent_up 0"));
+
+    let metrics = exporter.refresh().await.unwrap().to_string();
+    let lines: Vec<&str> = metrics.lines().collect();
+
+    assert!(lines.contains(&"# TYPE qbittorrent_session_downloaded_bytes_total counter"));
+    for expected in [
+        "qbittorrent_up 1",
+        "qbittorrent_connection_status{status=\"connected\"} 1",
+        "qbittorrent_download_limit_bytes_per_second 1024",
+        "qbittorrent_dht_nodes 312",
+        "qbittorrent_free_disk_space_bytes 500000000000",
+        "qbittorrent_torrents{state=\"downloading\"} 1",
+        "qbittorrent_torrents{state=\"pausedUP\"} 1",
+        "qbittorrent_torrents{state=\"stoppedUP\"} 0",
+        "qbittorrent_torrents{state=\"moving\"} 0",
+        "qbittorrent_category_torrents{category=\"linux\"} 1",
+        "qbittorrent_category_torrents{category=\"\"} 1",
+        "qbittorrent_tracker_torrents{tracker=\"udp://tracker.opentrackr.org:1337/announce\"} 2",
+        "qbittorrent_tracker_working_torrents{tracker=\"https://tracker.example.org/announce\"} 0",
+    ] {
+        assert!(lines.contains(&expected), "missing `{}` in:\n{}", expected, metrics);
+    }
+
+    // the next refresh only gets what changed from /sync/maindata, and still counts every torrent.
+    let metrics = exporter.refresh().await.unwrap();
+    assert!(metrics.contains(&format!("qbittorrent_tracker_working_torrents{{tracker=\"{}\"}} 2", TRACKER)));
+    assert!(metrics.contains("qbittorrent_torrents{state=\"pausedUP\"} 1"));
+}
+
+#[tokio::test]
+async fn failed_refreshes_are_exported_as_down() {
+    let mock = mock();
+    let mut exporter = MetricsExporter::new(mock.api().await.unwrap());
+    exporter.refresh().await.unwrap();
+
+    mock.respond_with("/transfer/info", 500, "");
+    assert!(exporter.refresh().await.is_err());
+    assert_eq!(exporter.metrics().lines().last(), Some("qbittorrent_up 0"));
+    assert!(!exporter.metrics().contains("qbittorrent_torrents"));
+
+    mock.reset_response("/transfer/info");
+    assert!(exporter.refresh().await.unwrap().contains("qbittorrent_up 1"));
+}
+
+async fn get(addr: std::net::SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn metrics_are_served_over_http() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let exporter = MetricsExporter::new(mock().api().await.unwrap());
+    let server = tokio::spawn(exporter.serve_listener(listener));
+
+    let response = get(addr, "/metrics").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+    assert!(response.contains("\r\n\r\n# HELP qbittorrent_up "));
+    assert!(response.contains("qbittorrent_up 1\n"));
+
+    assert!(get(addr, "/").await.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn scrapes_wait_neither_for_refreshes_nor_for_other_scrapes() {
+    let mock = mock();
+    let api = mock.api().await.unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let exporter = MetricsExporter::new(api).with_interval(Duration::from_millis(10));
+    let server = tokio::spawn(exporter.serve_listener(listener));
+    assert!(get(addr, "/metrics").await.contains("qbittorrent_up 1\n"));
+
+    // a refresh is now stuck on qbittorrent, and a client connected without sending its request.
+    mock.respond_with_sequence("/transfer/info", [MockResponse::new(200, "{}").with_delay(Duration::from_secs(3600))]);
+    sleep(Duration::from_millis(50)).await;
+    let _idle = TcpStream::connect(addr).await.unwrap();
+
+    let response = timeout(Duration::from_secs(1), get(addr, "/metrics")).await.expect("the scrape waited");
+    assert!(response.contains("qbittorrent_up 1\n"));
+
+    server.abort();
+}
@@ -0,0 +1,127 @@
+#![cfg(all(feature = "testing", feature = "app"))]
+
+use qbittorrent_rust::{
+    error_handling::error_type::ErrorType,
+    testing::{MockQbittorrent, MOCK_VERSION, MOCK_WEB_API_VERSION},
+    QBittorrentConfig, ScanDirs, ScanDirsValue,
+};
+
+#[tokio::test]
+async fn versions() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    assert_eq!(api.app_version().await.unwrap(), MOCK_VERSION);
+    assert_eq!(api.app_web_api_version().await.unwrap(), MOCK_WEB_API_VERSION);
+}
+
+#[tokio::test]
+async fn build_info() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    let raw = api.app_build_info_raw().await.unwrap();
+    let info = api.app_build_info().await.unwrap();
+
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&raw).unwrap(), info);
+    assert_eq!(info["bitness"], 64);
+    assert!(info["libtorrent"].is_string());
+
+    let typed = api.app_build_info_typed().await.unwrap();
+    assert_eq!(typed.bitness, 64);
+    assert_eq!(typed.libtorrent, info["libtorrent"]);
+    assert_eq!(typed.zlib.as_deref(), Some("1.3"));
+}
+
+#[tokio::test]
+async fn preferences_round_trip() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    assert_eq!(api.app_get_default_save_path().await.unwrap(), "/downloads/");
+    assert_eq!(api.app_get_preferences().await.unwrap()["dht"], true);
+
+    let config = QBittorrentConfig::builder().save_path("/data/torrents/").dht(false).build().unwrap();
+    api.app_set_preferences(config).await.unwrap();
+
+    let preferences: serde_json::Value = serde_json::from_str(&api.get_preferences_raw().await.unwrap()).unwrap();
+    assert_eq!(preferences["dht"], false);
+    assert_eq!(preferences, mock.preferences());
+    assert_eq!(api.app_get_default_save_path().await.unwrap(), "/data/torrents/");
+}
+
+#[tokio::test]
+async fn typed_preferences_can_be_read_modified_and_written() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    let mut config = api.app_get_preferences_typed().await.unwrap();
+    assert_eq!(config.save_path.as_deref(), Some("/downloads/"));
+    assert_eq!(config.max_seeding_time, Some(-1));
+    assert_eq!(config.banned_ips.as_deref(), Some(""));
+    // qbittorrent 5 reports the proxy type by name.
+    assert_eq!(config.proxy_type, Some(-1));
+
+    config.max_active_downloads = Some(-1);
+    config.dht = Some(false);
+    config.scan_dirs = Some(vec![ScanDirs::new(vec![("/watch", ScanDirsValue::DownloadToDefaultPath)])]);
+    api.app_set_preferences(config).await.unwrap();
+
+    let preferences = mock.preferences();
+    assert_eq!(preferences["max_active_downloads"], -1);
+    assert_eq!(preferences["dht"], false);
+    assert_eq!(preferences["scan_dirs"], serde_json::json!({ "/watch": 1 }));
+    assert_eq!(preferences["proxy_type"], "None");
+    assert_eq!(preferences["save_path"], "/downloads/");
+
+    let config = api.app_get_preferences_typed().await.unwrap();
+    assert_eq!(config.max_active_downloads, Some(-1));
+    assert!(matches!(config.scan_dirs.as_deref(), Some([_])));
+}
+
+#[tokio::test]
+async fn proxy_types_are_sent_the_way_the_server_reports_them() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    // by name, like qbittorrent 4.6 and later.
+    api.app_set_preferences(QBittorrentConfig::builder().proxy_type(2).build().unwrap()).await.unwrap();
+    assert_eq!(mock.preferences()["proxy_type"], "SOCKS5");
+    assert_eq!(api.app_get_preferences_typed().await.unwrap().proxy_type, Some(2));
+
+    // by number, like the older versions.
+    mock.respond_with("/app/preferences", 200, r#"{"proxy_type": 3}"#);
+    assert_eq!(api.app_get_preferences_typed().await.unwrap().proxy_type, Some(3));
+    api.app_set_preferences(QBittorrentConfig::builder().proxy_type(4).build().unwrap()).await.unwrap();
+    assert_eq!(mock.preferences()["proxy_type"], 4);
+}
+
+#[test]
+fn builder_setters_take_unsigned_values() {
+    let config = QBittorrentConfig::builder().max_active_downloads(8).dl_limit(u32::MAX).build().unwrap();
+
+    assert_eq!(config.max_active_downloads, Some(8));
+    assert_eq!(config.dl_limit, Some(i32::MAX));
+}
+
+#[tokio::test]
+async fn shutdown_makes_it_unreachable() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    api.app_shutdown().await.unwrap();
+
+    assert!(mock.is_shut_down());
+    let err = api.app_version().await.unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::HostUnreachable(_)));
+}
+
+#[tokio::test]
+async fn responses_can_be_overridden() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    mock.respond_with("/app/version", 500, "");
+    assert!(matches!(api.app_version().await.unwrap_err().err_type, ErrorType::MiscNetError(500)));
+
+    mock.reset_response("/app/version");
+    assert_eq!(api.app_version().await.unwrap(), MOCK_VERSION);
+}
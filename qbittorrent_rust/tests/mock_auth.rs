@@ -0,0 +1,78 @@
+#![cfg(all(feature = "testing", feature = "app"))]
+
+use qbittorrent_rust::{
+    core::{
+        api::QbitApi,
+        creds::{AuthMode, Credentials},
+    },
+    error_handling::error_type::ErrorType,
+    testing::MockQbittorrent,
+};
+
+#[tokio::test]
+async fn logs_in_with_the_right_credentials() {
+    let mock = MockQbittorrent::new().with_credentials("user", "secret");
+    let api = mock.api().await.unwrap();
+
+    assert!(api.app_version().await.is_ok());
+    assert_eq!(mock.requests(), ["/auth/login", "/app/webapiVersion", "/app/version"]);
+}
+
+#[tokio::test]
+async fn wrong_credentials_are_refused() {
+    let mock = MockQbittorrent::new();
+
+    let result = QbitApi::builder(mock.url(), Credentials::new("admin", "wrong"))
+        .build()
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(mock.hits("/app/version"), 0);
+}
+
+#[tokio::test]
+async fn requests_without_a_session_are_forbidden() {
+    let mock = MockQbittorrent::new();
+
+    let api = QbitApi::builder(mock.url(), AuthMode::NoAuth)
+        .build()
+        .await
+        .unwrap();
+
+    let err = api.app_version().await.unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::Forbidden));
+}
+
+#[tokio::test]
+async fn without_auth_accepts_everything() {
+    let mock = MockQbittorrent::new().without_auth();
+    let api = mock.api().await.unwrap();
+
+    assert!(api.app_version().await.is_ok());
+    assert_eq!(mock.hits("/auth/login"), 0);
+}
+
+#[tokio::test]
+async fn logout_ends_the_session() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    api.logout().await.unwrap();
+
+    assert_eq!(mock.hits("/auth/logout"), 1);
+
+    // the old session is gone, so the next request logs in again.
+    assert!(api.app_version().await.is_ok());
+    assert_eq!(mock.hits("/auth/login"), 2);
+}
+
+#[tokio::test]
+async fn expired_sessions_are_renewed() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    mock.expire_sessions();
+
+    assert!(api.app_version().await.is_ok());
+    assert_eq!(mock.hits("/auth/login"), 2);
+}
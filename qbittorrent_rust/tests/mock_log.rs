@@ -0,0 +1,56 @@
+#![cfg(all(feature = "testing", feature = "log"))]
+
+use qbittorrent_rust::{testing::MockQbittorrent, GetLogConfig};
+
+fn ids(log: &serde_json::Value) -> Vec<i64> {
+    log.as_array().unwrap().iter().map(|entry| entry["id"].as_i64().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn main_log() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    let log = api.log_get_log(GetLogConfig::new()).await.unwrap();
+    assert_eq!(ids(&log), [0, 1, 2, 3]);
+    assert!(log[0]["message"].as_str().unwrap().starts_with("qBittorrent"));
+
+    let raw = api.log_get_log_raw(GetLogConfig::new()).await.unwrap();
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&raw).unwrap(), log);
+}
+
+#[tokio::test]
+async fn main_log_filters() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    let no_info = GetLogConfig::builder().info(false).build();
+    assert_eq!(ids(&api.log_get_log(no_info).await.unwrap()), [0, 3]);
+
+    let only_warnings = GetLogConfig::builder().normal(false).info(false).critical(false).build();
+    assert_eq!(ids(&api.log_get_log(only_warnings).await.unwrap()), [3]);
+
+    let newer = GetLogConfig::builder().last_known_id(1).build();
+    assert_eq!(ids(&api.log_get_log(newer).await.unwrap()), [2, 3]);
+}
+
+#[tokio::test]
+async fn peer_log_starts_empty() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    assert_eq!(api.log_get_peer_log(None).await.unwrap(), serde_json::json!([]));
+    assert_eq!(api.log_get_peer_log_raw(Some(5)).await.unwrap(), "[]");
+}
+
+#[cfg(feature = "transfer")]
+#[tokio::test]
+async fn banned_peers_show_up_in_the_peer_log() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    api.transfer_ban_peers(vec!["10.0.0.1:6881", "10.0.0.2:51413"]).await.unwrap();
+
+    let log = api.log_get_peer_log(None).await.unwrap();
+    assert_eq!(ids(&log), [0, 1]);
+    assert_eq!(log[1]["ip"], "10.0.0.2");
+    assert_eq!(log[1]["blocked"], true);
+
+    assert_eq!(ids(&api.log_get_peer_log(Some(0)).await.unwrap()), [1]);
+}
@@ -0,0 +1,91 @@
+#![cfg(all(feature = "testing", feature = "rss"))]
+
+use qbittorrent_rust::{testing::MockQbittorrent, RssAutoDownloadRule};
+use serde_json::json;
+
+const FEED: &str = "https://example.org/rss/shows.xml";
+
+#[tokio::test]
+async fn folders_and_feeds() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    api.rss_add_folder("Shows").await.unwrap();
+    api.rss_add_feed(FEED, Some("Shows\\Example")).await.unwrap();
+    api.rss_add_feed("https://example.org/rss/movies.xml", None::<String>).await.unwrap();
+
+    let items = api.rss_get_all_items(None).await.unwrap();
+    assert_eq!(items["Shows"]["Example"]["url"], FEED);
+    assert!(items["Shows"]["Example"].get("articles").is_none());
+    assert!(items["https://example.org/rss/movies.xml"].is_object());
+
+    let with_data: serde_json::Value = serde_json::from_str(&api.rss_get_all_items_raw(Some(true)).await.unwrap()).unwrap();
+    assert_eq!(with_data["Shows"]["Example"]["articles"].as_array().unwrap().len(), 2);
+
+    // the same url can't be added twice, and folders can't be added twice.
+    assert_eq!(api.rss_add_feed(FEED, None::<String>).await.unwrap_err().code, Some(409));
+    assert_eq!(api.rss_add_folder("Shows").await.unwrap_err().code, Some(409));
+}
+
+#[tokio::test]
+async fn move_and_remove_items() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    api.rss_add_folder("Shows").await.unwrap();
+    api.rss_add_feed(FEED, Some("Example")).await.unwrap();
+
+    api.rss_move_item("Example", "Shows\\Example").await.unwrap();
+    let items = api.rss_get_all_items(None).await.unwrap();
+    assert!(items.get("Example").is_none());
+    assert_eq!(items["Shows"]["Example"]["url"], FEED);
+
+    api.rss_remove_item("Shows").await.unwrap();
+    assert_eq!(api.rss_get_all_items(None).await.unwrap(), json!({}));
+
+    assert_eq!(api.rss_remove_item("Shows").await.unwrap_err().code, Some(409));
+}
+
+#[tokio::test]
+async fn mark_as_read_and_refresh() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+    api.rss_add_feed(FEED, Some("Example")).await.unwrap();
+
+    let articles = || async { api.rss_get_all_items(Some(true)).await.unwrap()["Example"]["articles"].clone() };
+
+    let first = articles().await[0]["id"].as_str().unwrap().to_string();
+    api.rss_mark_as_read("Example", Some(first)).await.unwrap();
+    assert_eq!(articles().await[0]["isRead"], true);
+    assert_eq!(articles().await[1]["isRead"], false);
+
+    api.rss_mark_as_read("Example", None::<String>).await.unwrap();
+    assert_eq!(articles().await[1]["isRead"], true);
+
+    api.rss_refresh_item("Example").await.unwrap();
+}
+
+#[tokio::test]
+async fn auto_downloading_rules() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+    api.rss_add_feed(FEED, Some("Example")).await.unwrap();
+
+    let rule = RssAutoDownloadRule::builder()
+        .enabled(true)
+        .must_contain("S01E01")
+        .affected_feeds(vec![FEED.to_string()])
+        .assigned_category("shows")
+        .build();
+    api.rss_set_auto_downloading_rule("Example S01", rule).await.unwrap();
+
+    let rules = api.rss_get_all_auto_downloading_rules().await.unwrap();
+    assert_eq!(rules["Example S01"]["mustContain"], "S01E01");
+    assert_eq!(rules["Example S01"]["assignedCategory"], "shows");
+
+    let matching = api.rss_get_all_articles_matching_a_rule("Example S01").await.unwrap();
+    assert_eq!(matching, json!({ "Example": ["Example.Show.S01E01.1080p.WEB.h264"] }));
+
+    api.rss_rename_auto_downloading_rule("Example S01", "Example").await.unwrap();
+    assert!(api.rss_get_all_auto_downloading_rules_raw().await.unwrap().contains("\"Example\""));
+    assert_eq!(api.rss_get_all_articles_matching_a_rule_raw("Example S01").await.unwrap(), "{}");
+
+    api.rss_remove_auto_downloading_rule("Example").await.unwrap();
+    assert_eq!(api.rss_get_all_auto_downloading_rules().await.unwrap(), json!({}));
+}
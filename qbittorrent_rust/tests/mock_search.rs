@@ -0,0 +1,81 @@
+#![cfg(all(feature = "testing", feature = "search"))]
+
+use qbittorrent_rust::{testing::MockQbittorrent, Categories, SearchPluginsSpec};
+use serde_json::json;
+
+#[tokio::test]
+async fn plugins() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    assert_eq!(api.search_get_search_plugins_names().await.unwrap(), ["legittorrents", "eztv"]);
+
+    let plugins = api.search_get_search_plugins().await.unwrap();
+    assert_eq!(plugins[1].get_categories_ids(), ["all", "tv"]);
+
+    let json = api.search_get_search_plugins_json().await.unwrap();
+    assert_eq!(json[0]["enabled"], true);
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&api.search_get_search_plugins_raw().await.unwrap()).unwrap(), json);
+}
+
+#[tokio::test]
+async fn manage_plugins() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    api.search_install_plugins(vec!["https://example.org/plugins/example.py"]).await.unwrap();
+    assert_eq!(api.search_get_search_plugins_names().await.unwrap(), ["legittorrents", "eztv", "example"]);
+
+    api.search_enable_plugins(vec!["eztv"], true).await.unwrap();
+    assert_eq!(api.search_get_search_plugins_json().await.unwrap()[1]["enabled"], true);
+
+    api.search_uninstall_plugins(vec!["legittorrents", "example"]).await.unwrap();
+    assert_eq!(api.search_get_search_plugins_names().await.unwrap(), ["eztv"]);
+
+    api.search_update_plugins().await.unwrap();
+}
+
+#[tokio::test]
+async fn search_jobs() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    let id = api.search_start("debian", SearchPluginsSpec::All, Categories::All).await.unwrap();
+
+    let status = api.search_status(Some(id)).await.unwrap();
+    assert_eq!(status, json!([{ "id": id, "status": "Running", "total": 6 }]));
+
+    let results = api.search_results(id, Some(2), Some(1)).await.unwrap();
+    assert_eq!(results["total"], 6);
+    assert_eq!(results["results"][0]["fileName"], "debian 2 [legittorrents]");
+    assert_eq!(results["results"].as_array().unwrap().len(), 2);
+
+    api.search_stop(id).await.unwrap();
+    assert!(api.search_status_raw(None).await.unwrap().contains("\"Stopped\""));
+    assert!(api.search_results_raw(id, None, None).await.unwrap().contains("\"Stopped\""));
+
+    api.search_delete(id).await.unwrap();
+    assert_eq!(api.search_status(None).await.unwrap(), json!([]));
+    assert_eq!(api.search_stop(id).await.unwrap_err().code, Some(404));
+}
+
+#[tokio::test]
+async fn search_filters_plugins_and_categories() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    let enabled = api.search_start("debian", SearchPluginsSpec::Enabled, Categories::All).await.unwrap();
+    assert_eq!(api.search_results(enabled, None, None).await.unwrap()["total"], 3);
+
+    let tv = api.search_start("show", SearchPluginsSpec::Custom(vec!["eztv".to_string()]), Categories::Custom(vec!["tv".to_string()])).await.unwrap();
+    let results = api.search_results(tv, None, None).await.unwrap();
+    assert!(results["results"].as_array().unwrap().iter().all(|r| r["fileName"].as_str().unwrap().ends_with("[eztv]")));
+}
+
+#[tokio::test]
+async fn running_searches_are_capped() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    for _ in 0..5 {
+        api.search_start("debian", SearchPluginsSpec::All, Categories::All).await.unwrap();
+    }
+
+    let err = api.search_start("debian", SearchPluginsSpec::All, Categories::All).await.unwrap_err();
+    assert_eq!(err.code, Some(409));
+}
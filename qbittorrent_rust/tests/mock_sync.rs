@@ -0,0 +1,118 @@
+#![cfg(all(feature = "testing", feature = "sync"))]
+
+use qbittorrent_rust::{
+    testing::{MockQbittorrent, MockTorrent},
+    MainDataSync, PeerEvent, PeerSync, SyncEvent, TorrentHash, TorrentHashesDesc,
+};
+
+const DEBIAN: &str = "8c212779b4abde7c6bc608063a0d008b7e40ce32";
+const ARCH: &str = "c4b2b8a7e1d9f3a6b5c4d3e2f1a0b9c8d7e6f5a4";
+
+fn mock() -> MockQbittorrent {
+    let mut debian = MockTorrent::new(DEBIAN, "debian.iso");
+    debian.peers = vec!["10.0.0.1:6881".to_string()];
+
+    MockQbittorrent::new().with_torrent(debian)
+}
+
+#[tokio::test]
+async fn main_data_is_full_then_incremental() {
+    let mock = mock();
+    let api = mock.api().await.unwrap();
+
+    let full = api.sync_get_main_data(0).await.unwrap();
+    assert_eq!(full["full_update"], true);
+    assert_eq!(full["torrents"][DEBIAN]["name"], "debian.iso");
+    assert!(full["server_state"].is_object());
+
+    let rid = full["rid"].as_u64().unwrap();
+    let unchanged: serde_json::Value = serde_json::from_str(&api.sync_get_main_data_raw(rid).await.unwrap()).unwrap();
+    assert_eq!(unchanged, serde_json::json!({ "rid": rid + 1 }));
+
+    api.torrents_set_torrent_name(TorrentHash::new("", DEBIAN), "Debian 12").await.unwrap();
+
+    let delta = api.sync_get_main_data_delta(rid + 1).await.unwrap();
+    assert!(!delta.full_update);
+    let changed = &delta.torrents.unwrap()[DEBIAN];
+    assert!(!changed.contains_key("size"));
+    assert_eq!(changed["name"], "Debian 12");
+}
+
+#[tokio::test]
+async fn main_data_sync_keeps_up() {
+    let mock = mock();
+    let api = mock.api().await.unwrap();
+    let mut sync = MainDataSync::new(api.clone());
+
+    let state = sync.update().await.unwrap();
+    assert_eq!(state.torrents().len(), 1);
+    assert_eq!(state.torrent(DEBIAN).unwrap().name, "debian.iso");
+
+    api.torrents_add_new_tags(vec!["iso"]).await.unwrap();
+    api.torrents_add_new_category("linux", None::<String>).await.unwrap();
+    api.torrents_set_torrent_category(TorrentHashesDesc::All, "linux").await.unwrap();
+
+    let state = sync.update().await.unwrap();
+    assert_eq!(state.tags(), &["iso"]);
+    assert!(state.categories().contains_key("linux"));
+    assert_eq!(state.torrent(DEBIAN).unwrap().category, "linux");
+}
+
+#[tokio::test]
+async fn main_data_sync_events() {
+    let mock = mock();
+    let api = mock.api().await.unwrap();
+    let mut sync = MainDataSync::new(api.clone());
+    sync.update().await.unwrap();
+
+    let mut arch = MockTorrent::new(ARCH, "arch.iso");
+    arch.paused = true;
+    let mock = mock.with_torrent(arch);
+    api.torrents_delete_torrents(TorrentHashesDesc::Hashes(vec![TorrentHash::new("", DEBIAN)]), false).await.unwrap();
+
+    let events = sync.update_events().await.unwrap();
+    assert!(events.iter().any(|e| matches!(e, SyncEvent::TorrentAdded(t) if t.hash == ARCH)));
+    assert!(events.iter().any(|e| matches!(e, SyncEvent::TorrentRemoved(t) if t.hash == DEBIAN)));
+
+    api.torrents_resume_torrents(TorrentHashesDesc::All).await.unwrap();
+
+    let events = sync.update_events().await.unwrap();
+    assert!(events.iter().any(|e| matches!(e, SyncEvent::StateChanged { hash, .. } if hash == ARCH)));
+    assert_eq!(mock.torrents().len(), 1);
+}
+
+#[tokio::test]
+async fn torrent_peers() {
+    let mock = mock();
+    let api = mock.api().await.unwrap();
+
+    let full = api.sync_get_torrent_peers_data(DEBIAN, 0).await.unwrap();
+    assert_eq!(full["full_update"], true);
+    assert_eq!(full["peers"]["10.0.0.1:6881"]["port"], 6881);
+
+    let rid = full["rid"].as_u64().unwrap();
+    let raw = api.sync_get_torrent_peers_data_raw(DEBIAN, rid).await.unwrap();
+    assert!(!raw.contains("full_update"));
+
+    let delta = api.sync_get_torrent_peers_delta(TorrentHash::new("", DEBIAN), 0).await.unwrap();
+    assert!(delta.full_update);
+    assert_eq!(delta.peers.unwrap().len(), 1);
+
+    assert_eq!(api.sync_get_torrent_peers_data(ARCH, 0).await.unwrap_err().code, Some(404));
+}
+
+#[tokio::test]
+async fn peer_sync_events() {
+    let mock = mock();
+    let api = mock.api().await.unwrap();
+    let mut peers = PeerSync::new(api.clone(), TorrentHash::new("debian.iso", DEBIAN));
+
+    let events = peers.update().await.unwrap();
+    assert!(matches!(&events[..], [PeerEvent::Joined(peer)] if peer.address == "10.0.0.1:6881"));
+
+    api.torrents_add_peers(vec![TorrentHash::new("", DEBIAN)], vec!["10.0.0.2:51413"]).await.unwrap();
+
+    let events = peers.update().await.unwrap();
+    assert!(matches!(&events[..], [PeerEvent::Joined(peer)] if peer.ip == "10.0.0.2"));
+    assert_eq!(peers.peers().len(), 2);
+}
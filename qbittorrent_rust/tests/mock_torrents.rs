@@ -0,0 +1,357 @@
+#![cfg(all(feature = "testing", feature = "torrents"))]
+
+use qbittorrent_rust::{
+    core::api::QbitApi,
+    error_handling::error_type::ErrorType,
+    testing::{MockFile, MockQbittorrent, MockTorrent},
+    Category, State, Torrent, TorrentAddDescriptor, TorrentHash, TorrentHashesDesc, TorrentListGetConfig, TorrentState,
+    TorrentType,
+};
+use serde_json::json;
+
+const DEBIAN: &str = "8c212779b4abde7c6bc608063a0d008b7e40ce32";
+const ARCH: &str = "c4b2b8a7e1d9f3a6b5c4d3e2f1a0b9c8d7e6f5a4";
+const UBUNTU: &str = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0";
+
+fn hash(hash: &str) -> TorrentHash {
+    TorrentHash::new("", hash)
+}
+
+fn hashes(list: &[&str]) -> TorrentHashesDesc {
+    TorrentHashesDesc::Hashes(list.iter().map(|h| hash(h)).collect())
+}
+
+/// three torrents: a finished debian, an arch half way through and a paused ubuntu with two files.
+async fn mock() -> (MockQbittorrent, QbitApi) {
+    let mut debian = MockTorrent::new(DEBIAN, "debian.iso");
+    debian.progress = 1.0;
+    debian.category = "linux".to_string();
+
+    let mut arch = MockTorrent::new(ARCH, "arch.iso");
+    arch.progress = 0.5;
+
+    let mut ubuntu = MockTorrent::new(UBUNTU, "ubuntu");
+    ubuntu.paused = true;
+    ubuntu.files = vec![MockFile::new("ubuntu/ubuntu.iso", 6 * 1024 * 1024), MockFile::new("ubuntu/SHA256SUMS", 1024)];
+
+    let mock = MockQbittorrent::new().with_torrent(debian).with_torrent(arch).with_torrent(ubuntu);
+    let api = mock.api().await.unwrap();
+
+    (mock, api)
+}
+
+#[tokio::test]
+async fn add_torrents() {
+    let (mock, api) = mock().await;
+
+    let magnet = Torrent::new(TorrentType::Url("magnet:?xt=urn:btih:ABCDEF0123456789ABCDEF0123456789ABCDEF01&dn=fedora.iso"));
+    let file = Torrent::from_bytes(b"d4:infod6:lengthi1024e4:name8:notes.md12:piece lengthi16384eee".to_vec());
+
+    let descriptor = TorrentAddDescriptor::builder(vec![magnet, file])
+        .category("distros")
+        .tags(vec!["new".to_string(), "linux".to_string()])
+        .paused(true)
+        .build()
+        .unwrap();
+    api.torrents_add_torrent(descriptor).await.unwrap();
+
+    let fedora = mock.torrent("abcdef0123456789abcdef0123456789abcdef01").unwrap();
+    assert_eq!(fedora.name, "fedora.iso");
+    assert_eq!(fedora.category, "distros");
+    assert_eq!(fedora.tags, ["new", "linux"]);
+    assert!(fedora.paused);
+
+    assert_eq!(mock.torrents().len(), 5);
+    assert!(mock.torrents().iter().any(|t| t.name == "notes.md"));
+    assert_eq!(api.torrents_get_all_tags().await.unwrap(), json!(["new", "linux"]));
+}
+
+#[tokio::test]
+async fn invalid_torrent_files_are_refused() {
+    let (_, api) = mock().await;
+
+    let descriptor = TorrentAddDescriptor::new(vec![Torrent::from_bytes(b"not a torrent".to_vec())]).unwrap();
+    let err = api.torrents_add_torrent(descriptor).await.unwrap_err();
+
+    assert_eq!(err.code, Some(415));
+}
+
+#[tokio::test]
+async fn torrent_list() {
+    let (_, api) = mock().await;
+
+    let hashes = api.torrents_get_hashes().await.unwrap();
+    assert_eq!(hashes.iter().map(|h| h.name.as_str()).collect::<Vec<_>>(), ["debian.iso", "arch.iso", "ubuntu"]);
+
+    let typed = api.torrents_get_torrent_list_typed(TorrentListGetConfig::new()).await.unwrap();
+    assert_eq!(typed[0].state, TorrentState::StalledUploading);
+    assert_eq!(typed[2].state, TorrentState::PausedDownloading);
+
+    let list = api.torrents_get_torrent_list(TorrentListGetConfig::new()).await.unwrap();
+    let raw = api.torrents_get_torrent_list_raw(TorrentListGetConfig::new()).await.unwrap();
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&raw).unwrap(), list);
+    assert_eq!(list[1]["progress"], 0.5);
+}
+
+#[tokio::test]
+async fn torrent_list_filters() {
+    let (_, api) = mock().await;
+
+    let names = |list: serde_json::Value| list.as_array().unwrap().iter().map(|t| t["name"].as_str().unwrap().to_string()).collect::<Vec<_>>();
+
+    let paused = TorrentListGetConfig::builder().filter(State::Paused).build();
+    assert_eq!(names(api.torrents_get_torrent_list(paused).await.unwrap()), ["ubuntu"]);
+
+    let linux = TorrentListGetConfig::builder().category(Category::Custom("linux".to_string())).build();
+    assert_eq!(names(api.torrents_get_torrent_list(linux).await.unwrap()), ["debian.iso"]);
+
+    let sorted = TorrentListGetConfig::builder().sort("name").reverse(true).limit(2).build();
+    assert_eq!(names(api.torrents_get_torrent_list(sorted).await.unwrap()), ["ubuntu", "debian.iso"]);
+
+    let by_hash = TorrentListGetConfig::builder().hashes(vec![ARCH.to_string()]).build();
+    assert_eq!(names(api.torrents_get_torrent_list(by_hash).await.unwrap()), ["arch.iso"]);
+}
+
+#[tokio::test]
+async fn properties_trackers_and_web_seeds() {
+    let (_, api) = mock().await;
+
+    let properties = api.torrents_get_torrent_generic_properties(hash(DEBIAN)).await.unwrap();
+    assert_eq!(properties["name"], "debian.iso");
+    assert_eq!(properties["pieces_num"], properties["pieces_have"]);
+    let raw = api.torrents_get_torrent_generic_properties_raw(hash(DEBIAN)).await.unwrap();
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&raw).unwrap(), properties);
+
+    let trackers = api.torrents_get_torrent_trackers(hash(DEBIAN)).await.unwrap();
+    assert_eq!(trackers[3]["url"], "udp://tracker.opentrackr.org:1337/announce");
+    assert!(api.torrents_get_torrent_trackers_raw(hash(DEBIAN)).await.unwrap().contains("** [DHT] **"));
+
+    assert_eq!(api.torrents_get_torrent_web_seeds(hash(DEBIAN)).await.unwrap(), json!([]));
+    assert_eq!(api.torrents_get_torrent_web_seeds_raw(hash(DEBIAN)).await.unwrap(), "[]");
+}
+
+#[tokio::test]
+async fn unknown_hashes_are_not_found() {
+    let (_, api) = mock().await;
+
+    let err = api.torrents_get_torrent_generic_properties(hash("0000000000000000000000000000000000000000")).await.unwrap_err();
+
+    assert_eq!(err.code, Some(404));
+}
+
+#[tokio::test]
+async fn contents_and_pieces() {
+    let (_, api) = mock().await;
+
+    let contents = api.torrents_get_torrent_contents(hash(UBUNTU), None).await.unwrap();
+    assert_eq!(contents.as_array().unwrap().len(), 2);
+    assert_eq!(contents[1]["piece_range"], json!([1, 1]));
+
+    let second = api.torrents_get_torrent_contents_raw(hash(UBUNTU), Some(vec!["1".to_string()])).await.unwrap();
+    assert!(second.contains("SHA256SUMS") && !second.contains("ubuntu.iso"));
+
+    let ids = api.torrents_get_files_ids(hash(UBUNTU)).await.unwrap();
+    assert_eq!(ids.iter().map(|f| (f.id, f.name.as_str())).collect::<Vec<_>>(), [(0, "ubuntu/ubuntu.iso"), (1, "ubuntu/SHA256SUMS")]);
+
+    assert_eq!(api.torrents_get_torrent_pieces_states_vec(hash(DEBIAN)).await.unwrap(), vec![2; 16]);
+    assert_eq!(api.torrents_get_torrent_pieces_states(hash(UBUNTU)).await.unwrap(), json!([0, 0]));
+    assert_eq!(api.torrents_get_torrent_pieces_states_raw(hash(UBUNTU)).await.unwrap(), "[0,0]");
+
+    let piece_hashes = api.torrents_get_torrent_pieces_hashes_vec(hash(UBUNTU)).await.unwrap();
+    assert_eq!(piece_hashes.len(), 2);
+    assert!(piece_hashes.iter().all(|h| h.len() == 40));
+    assert_eq!(api.torrents_get_torrent_pieces_hashes(hash(UBUNTU)).await.unwrap(), json!(piece_hashes));
+    assert!(api.torrents_get_torrent_pieces_hashes_raw(hash(UBUNTU)).await.unwrap().starts_with("[\""));
+}
+
+#[tokio::test]
+async fn pause_resume_and_delete() {
+    let (mock, api) = mock().await;
+
+    api.torrents_pause_torrents(TorrentHashesDesc::All).await.unwrap();
+    assert!(mock.torrents().iter().all(|t| t.paused));
+
+    api.torrents_resume_torrents(hashes(&[ARCH])).await.unwrap();
+    assert!(!mock.torrent(ARCH).unwrap().paused);
+    assert!(mock.torrent(DEBIAN).unwrap().paused);
+
+    api.torrents_recheck_torrents(hashes(&[ARCH])).await.unwrap();
+    api.torrents_reannounce_torrents(TorrentHashesDesc::All).await.unwrap();
+
+    api.torrents_delete_torrents(hashes(&[DEBIAN, UBUNTU]), true).await.unwrap();
+    assert_eq!(mock.torrents().len(), 1);
+    assert_eq!(mock.torrents()[0].hash, ARCH);
+}
+
+#[cfg(feature = "app")]
+#[tokio::test]
+async fn queue_priorities() {
+    use qbittorrent_rust::QBittorrentConfig;
+
+    let (mock, api) = mock().await;
+    let order = || mock.torrents().into_iter().map(|t| t.name).collect::<Vec<_>>();
+
+    // qbittorrent refuses to move torrents in the queue while queueing is disabled.
+    let disabled = QBittorrentConfig::builder().queueing_enabled(false).build().unwrap();
+    api.app_set_preferences(disabled).await.unwrap();
+    let err = api.torrents_increase_priority_torrents(hashes(&[UBUNTU])).await.unwrap_err();
+    assert_eq!(err.code, Some(409));
+
+    let enabled = QBittorrentConfig::builder().queueing_enabled(true).build().unwrap();
+    api.app_set_preferences(enabled).await.unwrap();
+
+    api.torrents_increase_priority_torrents(hashes(&[UBUNTU])).await.unwrap();
+    assert_eq!(order(), ["debian.iso", "ubuntu", "arch.iso"]);
+
+    api.torrents_decrease_priority_torrents(hashes(&[DEBIAN])).await.unwrap();
+    assert_eq!(order(), ["ubuntu", "debian.iso", "arch.iso"]);
+
+    api.torrents_set_top_priority_torrents(hashes(&[ARCH])).await.unwrap();
+    assert_eq!(order(), ["arch.iso", "ubuntu", "debian.iso"]);
+
+    api.torrents_set_bottom_priority_torrents(hashes(&[ARCH])).await.unwrap();
+    assert_eq!(order(), ["ubuntu", "debian.iso", "arch.iso"]);
+
+    let list = api.torrents_get_torrent_list(TorrentListGetConfig::new()).await.unwrap();
+    assert_eq!(list[0]["priority"], 1);
+}
+
+#[tokio::test]
+async fn trackers() {
+    let (mock, api) = mock().await;
+
+    api.torrents_add_trackers_to_torrent(hash(ARCH), vec!["http://tracker.example.org/announce", "udp://open.example.net:6969"]).await.unwrap();
+    assert_eq!(mock.torrent(ARCH).unwrap().trackers.len(), 3);
+
+    api.torrents_edit_trackers(hash(ARCH), "http://tracker.example.org/announce", "https://tracker.example.org/announce").await.unwrap();
+    assert!(mock.torrent(ARCH).unwrap().trackers.contains(&"https://tracker.example.org/announce".to_string()));
+
+    let err = api.torrents_edit_trackers(hash(ARCH), "http://missing.example.org/", "http://other.example.org/").await.unwrap_err();
+    assert_eq!(err.code, Some(409));
+
+    api.torrents_remove_trackers(hash(ARCH), vec!["https://tracker.example.org/announce"]).await.unwrap();
+    assert_eq!(mock.torrent(ARCH).unwrap().trackers, ["udp://tracker.opentrackr.org:1337/announce", "udp://open.example.net:6969"]);
+}
+
+#[tokio::test]
+async fn peers() {
+    let (mock, api) = mock().await;
+
+    api.torrents_add_peers(vec![hash(DEBIAN), hash(ARCH)], vec!["10.0.0.1:6881", "10.0.0.2:51413"]).await.unwrap();
+    assert_eq!(mock.torrent(DEBIAN).unwrap().peers, ["10.0.0.1:6881", "10.0.0.2:51413"]);
+    assert_eq!(mock.torrent(ARCH).unwrap().peers.len(), 2);
+
+    let err = api.torrents_add_peers(vec![hash(DEBIAN)], vec!["not a peer"]).await.unwrap_err();
+    assert!(matches!(err.err_type, ErrorType::BadParameters(_)));
+}
+
+#[tokio::test]
+async fn file_priority_needs_a_priority() {
+    let (_, api) = mock().await;
+
+    let ids = api.torrents_get_files_ids(hash(UBUNTU)).await.unwrap();
+    let err = api.torrents_set_file_priority(hash(UBUNTU), ids).await.unwrap_err();
+
+    // the priority isn't part of the request, and qbittorrent refuses it without one.
+    assert!(matches!(err.err_type, ErrorType::BadParameters(_)));
+}
+
+#[tokio::test]
+async fn speed_and_share_limits() {
+    let (mock, api) = mock().await;
+
+    api.torrents_set_torrent_download_limit(hashes(&[DEBIAN]), 1024).await.unwrap();
+    api.torrents_set_torrent_upload_limit(TorrentHashesDesc::All, 2048).await.unwrap();
+
+    let download = api.torrents_get_torrent_download_limit(hashes(&[DEBIAN, ARCH])).await.unwrap();
+    assert_eq!(download, json!({ DEBIAN: 1024, ARCH: 0 }));
+    assert_eq!(api.torrents_get_torrent_download_limit_raw(hashes(&[DEBIAN])).await.unwrap(), format!("{{\"{}\":1024}}", DEBIAN));
+
+    let upload = api.torrents_get_torrent_upload_limit(TorrentHashesDesc::All).await.unwrap();
+    assert_eq!(upload.as_object().unwrap().values().collect::<Vec<_>>(), [2048, 2048, 2048]);
+    assert_eq!(api.torrents_get_torrent_upload_limit_raw(hashes(&[ARCH])).await.unwrap(), format!("{{\"{}\":2048}}", ARCH));
+
+    api.torrents_set_torrent_share_limit(hashes(&[ARCH]), 2.0, 1440, -1).await.unwrap();
+    let arch = mock.torrent(ARCH).unwrap();
+    assert_eq!((arch.ratio_limit, arch.seeding_time_limit, arch.inactive_seeding_time_limit), (2.0, 1440, -1));
+}
+
+#[tokio::test]
+async fn location_and_name() {
+    let (mock, api) = mock().await;
+
+    api.torrents_set_torrent_download_location(hashes(&[DEBIAN, ARCH]), "/data/isos/").await.unwrap();
+    assert_eq!(mock.torrent(ARCH).unwrap().save_path, "/data/isos/");
+
+    api.torrents_set_torrent_name(hash(DEBIAN), "Debian 12").await.unwrap();
+    assert_eq!(mock.torrent(DEBIAN).unwrap().name, "Debian 12");
+}
+
+#[tokio::test]
+async fn categories() {
+    let (mock, api) = mock().await;
+
+    api.torrents_add_new_category("movies", Some("/data/movies/")).await.unwrap();
+    api.torrents_add_new_category("shows", None::<String>).await.unwrap();
+
+    let err = api.torrents_add_new_category("movies", None::<String>).await.unwrap_err();
+    assert_eq!(err.code, Some(409));
+
+    api.torrents_edit_category("shows", Some("/data/shows/")).await.unwrap();
+
+    let categories = api.torrents_get_all_categories().await.unwrap();
+    assert_eq!(categories["shows"], json!({ "name": "shows", "savePath": "/data/shows/" }));
+    assert!(api.torrents_get_all_categories_raw().await.unwrap().contains("\"movies\""));
+
+    api.torrents_set_torrent_category(hashes(&[ARCH]), "movies").await.unwrap();
+    assert_eq!(mock.torrent(ARCH).unwrap().category, "movies");
+
+    api.torrents_remove_categories(vec!["movies"]).await.unwrap();
+    assert_eq!(mock.torrent(ARCH).unwrap().category, "");
+    assert!(api.torrents_get_all_categories().await.unwrap().get("movies").is_none());
+}
+
+#[tokio::test]
+async fn tags() {
+    let (_, api) = mock().await;
+
+    api.torrents_add_new_tags(vec!["iso", "linux"]).await.unwrap();
+    assert_eq!(api.torrents_get_all_tags().await.unwrap(), json!(["iso", "linux"]));
+
+    api.torrents_remove_tags(vec!["iso"]).await.unwrap();
+    assert_eq!(api.torrents_get_all_tags_raw().await.unwrap(), "[\"linux\"]");
+}
+
+#[tokio::test]
+async fn toggles() {
+    let (mock, api) = mock().await;
+
+    api.torrents_set_automatic_torrents_management(hashes(&[DEBIAN]), true).await.unwrap();
+    api.torrents_toggle_sequential_download(hashes(&[DEBIAN])).await.unwrap();
+    api.torrents_toggle_first_last_piece_priority(hashes(&[DEBIAN])).await.unwrap();
+    api.torrents_set_force_start(hashes(&[DEBIAN]), true).await.unwrap();
+    api.torrents_set_super_seeding(hashes(&[DEBIAN]), true).await.unwrap();
+
+    let debian = mock.torrent(DEBIAN).unwrap();
+    assert!(debian.auto_tmm && debian.seq_dl && debian.f_l_piece_prio && debian.force_start && debian.super_seeding);
+
+    let typed = api.torrents_get_torrent_list_typed(TorrentListGetConfig::new()).await.unwrap();
+    assert_eq!(typed[0].state, TorrentState::ForcedUploading);
+
+    api.torrents_toggle_sequential_download(hashes(&[DEBIAN])).await.unwrap();
+    assert!(!mock.torrent(DEBIAN).unwrap().seq_dl);
+}
+
+#[tokio::test]
+async fn rename_files_and_folders() {
+    let (mock, api) = mock().await;
+
+    api.torrents_rename_file(hash(UBUNTU), "ubuntu/SHA256SUMS", "ubuntu/checksums.txt").await.unwrap();
+    api.torrents_rename_folder(hash(UBUNTU), "ubuntu", "ubuntu-24.04").await.unwrap();
+
+    let names = mock.torrent(UBUNTU).unwrap().files.into_iter().map(|f| f.name).collect::<Vec<_>>();
+    assert_eq!(names, ["ubuntu-24.04/ubuntu.iso", "ubuntu-24.04/checksums.txt"]);
+
+    let err = api.torrents_rename_file(hash(UBUNTU), "missing.txt", "other.txt").await.unwrap_err();
+    assert_eq!(err.code, Some(409));
+}
@@ -0,0 +1,53 @@
+#![cfg(all(feature = "testing", feature = "transfer"))]
+
+use qbittorrent_rust::testing::MockQbittorrent;
+
+#[tokio::test]
+async fn global_limits() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    assert_eq!(api.transfer_get_global_download_limit().await.unwrap(), 0);
+    assert_eq!(api.transfer_get_global_upload_limit().await.unwrap(), 0);
+
+    api.transfer_set_global_download_limit(2_000_000).await.unwrap();
+    api.transfer_set_global_upload_limit(500_000).await.unwrap();
+
+    assert_eq!(api.transfer_get_global_download_limit().await.unwrap(), 2_000_000);
+    assert_eq!(api.transfer_get_global_upload_limit().await.unwrap(), 500_000);
+
+    let info = api.transfer_get_global_transfer_info().await.unwrap();
+    assert_eq!(info["dl_rate_limit"], 2_000_000);
+    assert_eq!(info["up_rate_limit"], 500_000);
+}
+
+#[tokio::test]
+async fn alternative_speed_limits() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    assert_eq!(api.transfer_get_alternative_speed_limits().await.unwrap(), 0);
+    api.transfer_toggle_alternative_speed_limits().await.unwrap();
+    assert_eq!(api.transfer_get_alternative_speed_limits().await.unwrap(), 1);
+    api.transfer_toggle_alternative_speed_limits().await.unwrap();
+    assert_eq!(api.transfer_get_alternative_speed_limits().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn transfer_info() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+
+    let raw = api.transfer_get_global_transfer_info_raw().await.unwrap();
+    let info = api.transfer_get_global_transfer_info().await.unwrap();
+
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&raw).unwrap(), info);
+    assert_eq!(info["connection_status"], "connected");
+}
+
+#[tokio::test]
+async fn ban_peers() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+
+    api.transfer_ban_peers(vec!["10.0.0.1:6881", "[2001:db8::1]:6881"]).await.unwrap();
+
+    assert_eq!(mock.banned_peers(), ["10.0.0.1:6881", "[2001:db8::1]:6881"]);
+}
@@ -0,0 +1,77 @@
+#![cfg(all(feature = "testing", feature = "app", feature = "torrents"))]
+
+use std::time::Duration;
+
+use qbittorrent_rust::{
+    core::{api::QbitApi, creds::Credentials, retry::RetryPolicy},
+    error_handling::error_type::ErrorType,
+    testing::{MockQbittorrent, MockResponse},
+    TorrentHashesDesc,
+};
+
+async fn api_with_retries(mock: &MockQbittorrent) -> QbitApi {
+    let policy = RetryPolicy::builder()
+        .max_attempts(3)
+        .initial_backoff(Duration::from_millis(1))
+        .build();
+
+    QbitApi::builder(mock.url(), Credentials::new("admin", "adminadmin"))
+        .retry_policy(policy)
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn read_only_endpoints_are_retried_on_server_errors() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with_sequence("/app/version", [MockResponse::new(503, ""), MockResponse::new(502, ""), MockResponse::new(200, "v5.0.0")]);
+
+    let version = api_with_retries(&mock).await.app_version().await.unwrap();
+
+    assert_eq!(version, "v5.0.0");
+    assert_eq!(mock.hits("/app/version"), 3);
+}
+
+#[tokio::test]
+async fn retries_stop_after_max_attempts() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/app/version", 503, "busy");
+
+    let err = api_with_retries(&mock).await.app_version().await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::MiscNetError(503)));
+    assert_eq!(mock.hits("/app/version"), 3);
+}
+
+#[tokio::test]
+async fn other_endpoints_are_not_retried() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/torrents/delete", 503, "");
+
+    let api = api_with_retries(&mock).await;
+    assert!(api.torrents_delete_torrents(TorrentHashesDesc::All, false).await.is_err());
+
+    assert_eq!(mock.hits("/torrents/delete"), 1);
+}
+
+#[tokio::test]
+async fn no_retries_by_default() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with_sequence("/app/version", [MockResponse::new(503, ""), MockResponse::new(200, "v5.0.0")]);
+
+    assert!(mock.api().await.unwrap().app_version().await.is_err());
+    assert_eq!(mock.hits("/app/version"), 1);
+}
+
+#[test]
+fn endpoints_are_matched_exactly_or_by_prefix() {
+    let policy = RetryPolicy::builder()
+        .retryable_endpoints(vec!["/sync/".to_string(), "/torrents/info".to_string()])
+        .build();
+
+    assert!(policy.is_retryable_endpoint("/sync/maindata?rid=3"));
+    assert!(policy.is_retryable_endpoint("torrents/info"));
+    assert!(!policy.is_retryable_endpoint("/torrents/infos"));
+    assert!(!policy.is_retryable_endpoint("/torrents/add"));
+}
@@ -0,0 +1,111 @@
+#![cfg(all(feature = "torrents", feature = "app"))]
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+use futures_timer::Delay;
+use futures_util::future::BoxFuture;
+use qbittorrent_rust::{
+    core::{
+        api::QbitApi,
+        creds::Credentials,
+        file_reader::StdFileReader,
+        retry::RetryPolicy,
+        transport::{MultipartValue, RequestBody, Transport, TransportRequest, TransportResponse},
+    },
+    Error, Torrent, TorrentAddDescriptor, TorrentType,
+};
+use reqwest::header::{HeaderValue, SET_COOKIE};
+
+/// the smallest executor there is: polls `future` on the current thread, parking it until it's woken up.
+/// it has no reactor nor timer, so nothing of tokio's can run on it.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// a [`Transport`] that answers after a short delay, failing the first `/app/version` so that it's retried.
+#[derive(Default)]
+struct Delayed {
+    requests: Mutex<Vec<TransportRequest>>,
+}
+
+impl Transport for Delayed {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            Delay::new(Duration::from_millis(5)).await;
+
+            let mut requests = self.requests.lock().unwrap();
+            requests.push(request.clone());
+            let attempts = requests.iter().filter(|sent| sent.path == request.path).count();
+
+            Ok(match (request.path.as_str(), attempts) {
+                ("/auth/login", _) => {
+                    let mut response = TransportResponse::new(200, "Ok.");
+                    response.headers.insert(SET_COOKIE, HeaderValue::from_static("SID=executor; HttpOnly; path=/"));
+                    response
+                }
+                ("/app/webapiVersion", _) => TransportResponse::new(200, "2.9.3"),
+                ("/torrents/add", _) => TransportResponse::new(200, "Ok."),
+                ("/app/version", 1) => TransportResponse::new(503, "Service Unavailable"),
+                ("/app/version", _) => TransportResponse::new(200, "v4.6.7"),
+                _ => TransportResponse::new(404, "Not Found"),
+            })
+        })
+    }
+}
+
+#[test]
+fn the_core_runs_without_tokio() {
+    let path = std::env::temp_dir().join(format!("qbittorrent_rust_runtime_{}.torrent", std::process::id()));
+    std::fs::write(&path, b"d4:infod4:name4:testee").unwrap();
+    let transport = Arc::new(Delayed::default());
+
+    block_on(async {
+        let api = QbitApi::builder("http://qbit.invalid/", Credentials::new("admin", "adminadmin"))
+            .transport(transport.clone())
+            .file_reader(Arc::new(StdFileReader))
+            .retry_policy(RetryPolicy::builder().initial_backoff(Duration::from_millis(10)).build())
+            .max_requests_per_second(100.0)
+            .build()
+            .await
+            .unwrap();
+
+        let descriptor = TorrentAddDescriptor::new(vec![Torrent::new(TorrentType::TorrentFile(path.to_str().unwrap()))]).unwrap();
+        api.torrents_add_torrent(descriptor).await.unwrap();
+
+        assert_eq!(api.app_version().await.unwrap(), "v4.6.7");
+    });
+    std::fs::remove_file(&path).unwrap();
+
+    let requests = transport.requests.lock().unwrap();
+    let paths: Vec<&str> = requests.iter().map(|request| request.path.as_str()).collect();
+    assert_eq!(paths, ["/auth/login", "/app/webapiVersion", "/torrents/add", "/app/version", "/app/version"]);
+
+    let RequestBody::Multipart(fields) = &requests[2].body else {
+        panic!("expected a multipart body");
+    };
+    assert!(matches!(&fields[0].value, MultipartValue::File { bytes, .. } if bytes == b"d4:infod4:name4:testee"));
+}
@@ -0,0 +1,84 @@
+#![cfg(all(feature = "testing", feature = "torrents"))]
+
+use std::sync::{Arc, Mutex};
+
+use futures_util::future::BoxFuture;
+use qbittorrent_rust::{
+    core::{api::QbitApi, creds::Credentials, file_reader::FileReader},
+    error_handling::error_type::ErrorType,
+    testing::MockQbittorrent,
+    Torrent, TorrentAddDescriptor, TorrentType,
+};
+
+/// a [`FileReader`] that remembers which paths were read, and only knows about `known.torrent`.
+#[derive(Default)]
+struct RecordingReader {
+    read: Mutex<Vec<String>>,
+}
+
+impl FileReader for RecordingReader {
+    fn read<'a>(&'a self, path: &'a str) -> BoxFuture<'a, std::io::Result<Vec<u8>>> {
+        Box::pin(async move {
+            self.read.lock().unwrap().push(path.to_string());
+            match path {
+                "known.torrent" => Ok(b"d4:infod4:name4:testee".to_vec()),
+                _ => Err(std::io::ErrorKind::NotFound.into()),
+            }
+        })
+    }
+}
+
+async fn api_with_reader(mock: &MockQbittorrent, reader: Arc<RecordingReader>) -> QbitApi {
+    QbitApi::builder(mock.url(), Credentials::new("admin", "adminadmin"))
+        .file_reader(reader)
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn torrent_files_are_read_with_the_file_reader() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/torrents/add", 200, "Ok.");
+    let reader = Arc::new(RecordingReader::default());
+
+    let descriptor = TorrentAddDescriptor::builder(vec![Torrent::new(TorrentType::TorrentFile("known.torrent"))])
+        .build()
+        .unwrap();
+
+    api_with_reader(&mock, reader.clone()).await.torrents_add_torrent(descriptor).await.unwrap();
+
+    assert_eq!(*reader.read.lock().unwrap(), vec!["known.torrent".to_string()]);
+    assert_eq!(mock.hits("/torrents/add"), 1);
+}
+
+#[tokio::test]
+async fn unreadable_torrent_files_are_an_error() {
+    let mock = MockQbittorrent::new();
+    let reader = Arc::new(RecordingReader::default());
+
+    let descriptor = TorrentAddDescriptor::builder(vec![Torrent::new(TorrentType::TorrentFile("missing.torrent"))])
+        .build()
+        .unwrap();
+
+    let err = api_with_reader(&mock, reader).await.torrents_add_torrent(descriptor).await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::TorrentFilePathError));
+    assert_eq!(mock.hits("/torrents/add"), 0);
+}
+
+#[tokio::test]
+async fn in_memory_torrents_are_not_read() {
+    let mock = MockQbittorrent::new();
+    mock.respond_with("/torrents/add", 200, "Ok.");
+    let reader = Arc::new(RecordingReader::default());
+
+    let descriptor = TorrentAddDescriptor::builder(vec![Torrent::from_bytes(b"d4:infod4:name4:testee".to_vec())])
+        .build()
+        .unwrap();
+
+    api_with_reader(&mock, reader.clone()).await.torrents_add_torrent(descriptor).await.unwrap();
+
+    assert!(reader.read.lock().unwrap().is_empty());
+    assert_eq!(mock.hits("/torrents/add"), 1);
+}
@@ -0,0 +1,185 @@
+#![cfg(all(feature = "tracing", feature = "testing", feature = "app"))]
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use qbittorrent_rust::{core::retry::RetryPolicy, testing::MockQbittorrent};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+#[derive(Debug, Clone, Default)]
+struct CapturedSpan {
+    name: &'static str,
+    parent: Option<u64>,
+    fields: HashMap<String, String>,
+}
+
+/// a subscriber keeping every span (with its fields) and every event of the crate, to look at them once the requests are done.
+/// the ones of its dependencies (eg: hyper's connection logs) are left out.
+#[derive(Default)]
+struct Capture {
+    next_id: AtomicU64,
+    spans: Mutex<HashMap<u64, CapturedSpan>>,
+    events: Mutex<Vec<HashMap<String, String>>>,
+    stack: Mutex<Vec<u64>>,
+}
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target().starts_with("qbittorrent_rust")
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let parent = match attrs.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if attrs.is_contextual() => self.stack.lock().unwrap().last().copied(),
+            None => None,
+        };
+
+        let mut span = CapturedSpan { name: attrs.metadata().name(), parent, fields: HashMap::new() };
+        attrs.record(&mut Fields(&mut span.fields));
+        self.spans.lock().unwrap().insert(id, span);
+
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if let Some(span) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            values.record(&mut Fields(&mut span.fields));
+        }
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = HashMap::new();
+        event.record(&mut Fields(&mut fields));
+        self.events.lock().unwrap().push(fields);
+    }
+
+    fn enter(&self, span: &Id) {
+        self.stack.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.stack.lock().unwrap().pop();
+    }
+}
+
+impl Capture {
+    fn spans(&self, name: &str) -> Vec<(u64, CapturedSpan)> {
+        let mut spans: Vec<_> = self.spans.lock().unwrap().iter().filter(|(_, s)| s.name == name).map(|(id, s)| (*id, s.clone())).collect();
+        spans.sort_by_key(|(id, _)| *id);
+        spans
+    }
+
+    fn messages(&self) -> Vec<String> {
+        self.events.lock().unwrap().iter().filter_map(|e| e.get("message").cloned()).collect()
+    }
+}
+
+/// captures what's traced on this thread, until the guard is dropped.
+fn capture() -> (Arc<Capture>, tracing::subscriber::DefaultGuard) {
+    let capture = Arc::new(Capture::default());
+    let guard = tracing::subscriber::set_default(capture.clone());
+
+    (capture, guard)
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn requests_are_traced() {
+    let api = MockQbittorrent::new().api().await.unwrap();
+    let (capture, _guard) = capture();
+    let version = api.app_version().await.unwrap();
+
+    let calls = capture.spans("qbittorrent.call");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].1.fields["method"], "app_version");
+
+    let requests = capture.spans("qbittorrent.request");
+    assert_eq!(requests.len(), 1);
+
+    let (_, request) = &requests[0];
+    assert_eq!(request.parent, Some(calls[0].0));
+    assert_eq!(request.fields["endpoint"], "/app/version");
+    assert_eq!(request.fields["http.method"], "POST");
+    assert_eq!(request.fields["status"], "200");
+    assert_eq!(request.fields["bytes"], version.len().to_string());
+    assert_eq!(request.fields["retries"], "0");
+    assert!(request.fields.contains_key("latency_ms"));
+    assert!(!request.fields.contains_key("error"));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn relogins_are_traced() {
+    let mock = MockQbittorrent::new();
+    let api = mock.api().await.unwrap();
+    let (capture, _guard) = capture();
+    mock.expire_sessions();
+
+    api.app_version().await.unwrap();
+
+    assert_eq!(capture.messages(), ["session rejected, logged in again"]);
+    assert_eq!(capture.spans("qbittorrent.request")[0].1.fields["status"], "200");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn retries_are_traced() {
+    let mock = MockQbittorrent::new();
+    let policy = RetryPolicy::builder().max_attempts(3).initial_backoff(Duration::from_millis(1)).jitter(false).build();
+    let api = mock.builder().retry_policy(policy).build().await.unwrap();
+    let (capture, _guard) = capture();
+    mock.respond_with("/app/version", 503, "");
+
+    api.app_version().await.unwrap_err();
+
+    assert_eq!(capture.messages(), ["retrying request", "retrying request"]);
+
+    let (_, request) = &capture.spans("qbittorrent.request")[0];
+    assert_eq!(request.fields["status"], "503");
+    assert_eq!(request.fields["retries"], "2");
+    // the request went through: only the status tells it failed.
+    assert!(!request.fields.contains_key("error"));
+}
+
+#[cfg(feature = "torrents")]
+#[tokio::test(flavor = "current_thread")]
+async fn every_method_is_traced() {
+    use qbittorrent_rust::{Torrent, TorrentAddDescriptor, TorrentType};
+
+    let api = MockQbittorrent::new().api().await.unwrap();
+    let (capture, _guard) = capture();
+
+    api.torrents_get_all_tags_raw().await.unwrap();
+    let descriptor = TorrentAddDescriptor::new(vec![Torrent::new(TorrentType::Url("magnet:?xt=urn:btih:ABCDEF0123456789ABCDEF0123456789ABCDEF01"))]).unwrap();
+    api.torrents_add_torrent(descriptor).await.unwrap();
+
+    let calls = capture.spans("qbittorrent.call");
+    let methods: Vec<&str> = calls.iter().map(|(_, call)| call.fields["method"].as_str()).collect();
+    assert_eq!(methods, ["torrents_get_all_tags_raw", "torrents_add_torrent"]);
+
+    let requests = capture.spans("qbittorrent.request");
+    assert_eq!(requests.iter().map(|(_, request)| request.parent).collect::<Vec<_>>(), [Some(calls[0].0), Some(calls[1].0)]);
+}
@@ -0,0 +1,194 @@
+#![cfg(all(feature = "torrents", feature = "app"))]
+
+use std::sync::{Arc, Mutex};
+
+use futures_util::future::BoxFuture;
+use qbittorrent_rust::{
+    core::{
+        api::QbitApi,
+        creds::{AuthMode, Credentials},
+        transport::{MultipartValue, RequestBody, Transport, TransportRequest, TransportResponse},
+    },
+    error_handling::error_type::ErrorType,
+    Error, Torrent, TorrentAddDescriptor, TorrentHashesDesc,
+};
+use reqwest::header::{HeaderValue, SET_COOKIE};
+
+/// an in-process [`Transport`]: records every request, and answers them without any network.
+#[derive(Default)]
+struct InProcess {
+    requests: Mutex<Vec<TransportRequest>>,
+}
+
+impl Transport for InProcess {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            self.requests.lock().unwrap().push(request.clone());
+
+            let response = match request.path.as_str() {
+                "/auth/login" => {
+                    let mut response = TransportResponse::new(200, "Ok.");
+                    response.headers.insert(SET_COOKIE, HeaderValue::from_static("SID=in-process; HttpOnly; path=/"));
+                    response
+                }
+                "/app/version" => TransportResponse::new(200, "v5.0.0"),
+                "/app/webapiVersion" => TransportResponse::new(200, "2.11.2"),
+                "/torrents/add" => TransportResponse::new(200, "Ok."),
+                "/torrents/stop" | "/torrents/pause" => TransportResponse::new(200, ""),
+                _ => TransportResponse::new(404, "Not Found"),
+            };
+
+            Ok(response)
+        })
+    }
+}
+
+async fn api(transport: Arc<InProcess>, auth: impl Into<AuthMode>) -> QbitApi {
+    QbitApi::builder("http://qbit.invalid/", auth)
+        .transport(transport)
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn requests_go_through_the_transport() {
+    let transport = Arc::new(InProcess::default());
+    let api = api(transport.clone(), Credentials::new("admin", "adminadmin")).await;
+
+    assert_eq!(api.app_version().await.unwrap(), "v5.0.0");
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+
+    let login = &requests[0];
+    assert_eq!(login.path, "/auth/login");
+    assert_eq!(
+        login.body,
+        RequestBody::Form(vec![("username".to_string(), "admin".to_string()), ("password".to_string(), "adminadmin".to_string())])
+    );
+
+    // the version of the WebUI API is detected when connecting.
+    assert_eq!(requests[1].path, "/app/webapiVersion");
+
+    let version = &requests[2];
+    assert_eq!(version.path, "/app/version");
+    assert_eq!(version.cookies, vec![("SID".to_string(), "in-process".to_string())]);
+    assert_eq!(version.url().unwrap().as_str(), "http://qbit.invalid/api/v2/app/version");
+}
+
+#[tokio::test]
+async fn query_strings_are_split_from_the_path() {
+    let transport = Arc::new(InProcess::default());
+    let api = api(transport.clone(), AuthMode::NoAuth).await;
+
+    api.torrents_pause_torrents(TorrentHashesDesc::All).await.unwrap();
+
+    let requests = transport.requests.lock().unwrap();
+    let pause = requests.last().unwrap();
+    assert!(pause.path == "/torrents/pause" || pause.path == "/torrents/stop");
+    assert_eq!(pause.query, vec![("hashes".to_string(), "all".to_string())]);
+    assert!(pause.cookies.is_empty());
+}
+
+#[tokio::test]
+async fn torrent_files_are_sent_as_multipart() {
+    let transport = Arc::new(InProcess::default());
+    let api = api(transport.clone(), AuthMode::NoAuth).await;
+
+    let descriptor = TorrentAddDescriptor::builder(vec![Torrent::from_bytes(b"d4:infod4:name4:testee".to_vec())])
+        .build()
+        .unwrap();
+    api.torrents_add_torrent(descriptor).await.unwrap();
+
+    let requests = transport.requests.lock().unwrap();
+    let RequestBody::Multipart(fields) = &requests.last().unwrap().body else {
+        panic!("expected a multipart body");
+    };
+
+    assert_eq!(fields.iter().filter(|field| matches!(field.value, MultipartValue::File { .. })).count(), 1);
+    assert_eq!(fields[0].name, "torrents");
+    assert!(matches!(&fields[0].value, MultipartValue::File { bytes, .. } if bytes == b"d4:infod4:name4:testee"));
+
+    // the options are sent along with the files.
+    assert!(fields.iter().any(|field| field.name == "root_folder"));
+}
+
+#[tokio::test]
+async fn unsuccessful_responses_become_errors() {
+    let transport = Arc::new(InProcess::default());
+    let api = api(transport, AuthMode::NoAuth).await;
+
+    let err = api.app_get_default_save_path().await.unwrap_err();
+
+    assert!(matches!(err.err_type, ErrorType::NotFound(_)));
+    assert_eq!(err.method, Some("app_get_default_save_path"));
+    assert_eq!(err.endpoint.as_deref(), Some("/app/defaultSavePath"));
+    assert!(err.to_string().ends_with("(method: app_get_default_save_path, endpoint: /app/defaultSavePath, status: 404)"), "{}", err);
+
+    // methods that aren't generated by the request macros are named too.
+    let err = api.torrents_delete_torrents(TorrentHashesDesc::All, false).await.unwrap_err();
+    assert_eq!((err.method, err.endpoint.as_deref()), (Some("torrents_delete_torrents"), Some("/torrents/delete")));
+}
+
+#[test]
+fn malformed_set_cookie_headers_are_skipped() {
+    let response = |headers: &[&'static str]| {
+        let mut response = TransportResponse::new(200, "Ok.");
+        for header in headers {
+            response.headers.append(SET_COOKIE, HeaderValue::from_static(header));
+        }
+        response
+    };
+
+    // no `=`, or only attributes.
+    assert_eq!(response(&["SID"]).cookie("SID"), None);
+    assert_eq!(response(&["HttpOnly; SID=abc; path=/"]).cookie("SID"), None);
+    assert_eq!(response(&["; HttpOnly; path=/"]).cookie("SID"), None);
+    assert_eq!(response(&["SID; HttpOnly", "=orphan", "SID=abc; HttpOnly; path=/"]).cookie("SID").as_deref(), Some("abc"));
+
+    // several cookies: the first one with the name wins, names are case sensitive.
+    let several = response(&["theme=dark; path=/", " SID = abc ; HttpOnly", "SID=ignored"]);
+    assert_eq!(several.cookie("theme").as_deref(), Some("dark"));
+    assert_eq!(several.cookie("SID").as_deref(), Some("abc"));
+    assert_eq!(several.cookie("sid"), None);
+
+    // quoted values are kept as they were sent.
+    assert_eq!(response(&["SID=\"abc\"; HttpOnly"]).cookie("SID").as_deref(), Some("\"abc\""));
+
+    // the cookies parsed by the transport come first.
+    let mut parsed = response(&["SID=from-header"]);
+    parsed.cookies.push(("SID".to_string(), "from-transport".to_string()));
+    assert_eq!(parsed.cookie("SID").as_deref(), Some("from-transport"));
+}
+
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn reqwest_parses_the_cookies_like_its_cookie_store() {
+    use qbittorrent_rust::{
+        core::transport::ReqwestTransport,
+        testing::{MockQbittorrent, MockResponse},
+    };
+    use reqwest::{header::HeaderMap, Client, Method};
+
+    let mock = MockQbittorrent::new();
+    let login = MockResponse::new(200, "Ok.")
+        .with_header("set-cookie", "theme=dark; path=/")
+        .with_header("set-cookie", "SID")
+        .with_header("set-cookie", "SID=abc; HttpOnly; SameSite=Strict; path=/");
+    mock.respond_with_sequence("/auth/login", [login]);
+
+    let request = TransportRequest {
+        method: Method::POST,
+        api_url: format!("{}api/v2/", mock.url()).parse().unwrap(),
+        path: "/auth/login".to_string(),
+        query: vec![],
+        headers: HeaderMap::new(),
+        cookies: vec![],
+        body: RequestBody::Empty,
+    };
+    let response = ReqwestTransport::new(Client::new()).send(request).await.unwrap();
+
+    assert_eq!(response.cookies, [("theme".to_string(), "dark".to_string()), ("SID".to_string(), "abc".to_string())]);
+    assert_eq!(response.cookie("SID").as_deref(), Some("abc"));
+}
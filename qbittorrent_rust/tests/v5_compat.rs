@@ -0,0 +1,88 @@
+#![cfg(all(feature = "testing", feature = "torrents"))]
+
+use std::sync::Arc;
+
+use qbittorrent_rust::{
+    core::{api::QbitApi, fixtures::RecordingTransport, transport::ReqwestTransport},
+    testing::{MockQbittorrent, MockTorrent},
+    State, Torrent, TorrentAddDescriptor, TorrentHash, TorrentHashesDesc, TorrentListGetConfig, TorrentType,
+};
+
+const DEBIAN: &str = "8c212779b4abde7c6bc608063a0d008b7e40ce32";
+const FEDORA: &str = "magnet:?xt=urn:btih:ABCDEF0123456789ABCDEF0123456789ABCDEF01&dn=fedora.iso";
+
+/// a mock reporting the WebUI API `version`, and an api that records what it sends to it.
+async fn connect(version: &str) -> (MockQbittorrent, Arc<RecordingTransport>, QbitApi) {
+    let mock = MockQbittorrent::new().with_torrent(MockTorrent::new(DEBIAN, "debian.iso"));
+    mock.respond_with("/app/webapiVersion", 200, version);
+
+    let recording = Arc::new(RecordingTransport::new(Arc::new(ReqwestTransport::new(reqwest::Client::new())), None::<String>));
+    let api = mock.builder().transport(recording.clone()).build().await.unwrap();
+
+    (mock, recording, api)
+}
+
+fn debian() -> TorrentHashesDesc {
+    TorrentHashesDesc::Hashes(vec![TorrentHash::new("", DEBIAN)])
+}
+
+/// the params of the last request sent to `path`.
+fn last_params(recording: &RecordingTransport, path: &str) -> Vec<(String, String)> {
+    recording.fixtures().into_iter().rev().find(|fixture| fixture.request.path == path).unwrap().request.params
+}
+
+#[tokio::test]
+async fn stop_and_start_use_the_endpoints_of_the_server() {
+    let (mock, _, api) = connect("2.11.2").await;
+
+    api.torrents_stop(debian()).await.unwrap();
+    assert!(mock.torrent(DEBIAN).unwrap().paused);
+    api.torrents_resume_torrents(debian()).await.unwrap();
+    assert!(!mock.torrent(DEBIAN).unwrap().paused);
+
+    assert_eq!((mock.hits("/torrents/stop"), mock.hits("/torrents/start")), (1, 1));
+    assert_eq!((mock.hits("/torrents/pause"), mock.hits("/torrents/resume")), (0, 0));
+
+    let (mock, _, api) = connect("2.9.3").await;
+
+    api.torrents_pause_torrents(debian()).await.unwrap();
+    api.torrents_start(debian()).await.unwrap();
+
+    assert_eq!((mock.hits("/torrents/pause"), mock.hits("/torrents/resume")), (1, 1));
+    assert_eq!((mock.hits("/torrents/stop"), mock.hits("/torrents/start")), (0, 0));
+}
+
+#[tokio::test]
+async fn state_filters_are_renamed_for_the_server() {
+    let filter = |state| TorrentListGetConfig::builder().filter(state).build();
+
+    let (_, recording, api) = connect("2.11.2").await;
+    api.torrents_get_torrent_list(filter(State::Paused)).await.unwrap();
+    assert!(last_params(&recording, "/torrents/info").contains(&("filter".to_string(), "stopped".to_string())));
+    api.torrents_get_torrent_list(filter(State::Resumed)).await.unwrap();
+    assert!(last_params(&recording, "/torrents/info").contains(&("filter".to_string(), "running".to_string())));
+
+    let (_, recording, api) = connect("2.9.3").await;
+    api.torrents_get_torrent_list(filter(State::Stopped)).await.unwrap();
+    assert!(last_params(&recording, "/torrents/info").contains(&("filter".to_string(), "paused".to_string())));
+    api.torrents_get_torrent_list(filter(State::Running)).await.unwrap();
+    assert!(last_params(&recording, "/torrents/info").contains(&("filter".to_string(), "resumed".to_string())));
+}
+
+#[tokio::test]
+async fn added_torrents_are_paused_with_the_parameter_of_the_server() {
+    let url = TorrentAddDescriptor::builder(vec![Torrent::new(TorrentType::Url(FEDORA))]).paused(true).build().unwrap();
+    let file = TorrentAddDescriptor::builder(vec![Torrent::from_bytes(b"d4:infod4:name8:notes.mdee".to_vec())]).paused(true).build().unwrap();
+
+    for (version, param) in [("2.11.2", "stopped"), ("2.9.3", "paused")] {
+        for descriptor in [&url, &file] {
+            let (mock, recording, api) = connect(version).await;
+            api.torrents_add_torrent(descriptor).await.unwrap();
+
+            let params = last_params(&recording, "/torrents/add");
+            assert!(params.contains(&(param.to_string(), "true".to_string())), "{:?} sent to {}", params, version);
+            assert!(mock.torrents().iter().filter(|torrent| torrent.hash != DEBIAN).all(|torrent| torrent.paused));
+            assert_eq!(mock.torrents().len(), 2);
+        }
+    }
+}